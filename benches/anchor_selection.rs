@@ -0,0 +1,105 @@
+//! TideMark
+//! ========
+//!
+//! File: benches/anchor_selection.rs
+//! Description: Release anchor selection benchmark on many-tag and deep-history synthetic repositories.
+//!
+//! Responsibility:
+//! - Compare the bulk `select_anchor` query with the former per-tag `commit_exists`/`is_ancestor`/`commit_distance`/`resolve_commit` loop, both with 1000 tags and with a few tags on top of deep history.
+//!
+//! Architectural Position:
+//! - Performance regression harness for the release/anchor layer.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+mod support;
+
+use std::time::SystemTime;
+
+use criterion::{Criterion, criterion_group};
+use support::{RepoShape, SyntheticRepo};
+use tidemark::{
+    CommitInfo, ReleaseTag, TideConfig,
+    config::HistoryMode,
+    core::release,
+    infra::git::{GitProvider, cli::GitCli},
+};
+
+/// Many tags, where the per-tag loop is slow, and a few tags above deep history, where the bulk
+/// walk must stay bounded by the tags instead of the repository.
+const SHAPES: [RepoShape; 2] = [
+    RepoShape {
+        commits: 2000,
+        tags: 1000,
+        merge_every: 0,
+        untagged: 0,
+    },
+    RepoShape {
+        commits: 50_000,
+        tags: 5,
+        merge_every: 0,
+        untagged: 49_950,
+    },
+];
+
+/// Four git processes per tag: the shape `select_anchor` had before the bulk query.
+fn per_tag_baseline(git: &dyn GitProvider, releases: &[ReleaseTag], target: &CommitInfo) -> u32 {
+    let mut best: Option<u32> = None;
+    for release in releases {
+        let commit = release.tag.commit_id.as_str();
+        if !git.commit_exists(commit).expect("commit_exists")
+            || !git
+                .is_ancestor(commit, target.id.as_str())
+                .expect("is_ancestor")
+        {
+            continue;
+        }
+        let distance = git
+            .commit_distance(commit, target.id.as_str())
+            .expect("commit_distance");
+        git.resolve_commit(commit).expect("resolve_commit");
+        best = Some(best.map_or(distance, |best| best.min(distance)));
+    }
+    best.expect("reachable tag")
+}
+
+fn anchor_selection(c: &mut Criterion) {
+    for shape in SHAPES {
+        let repo = SyntheticRepo::generate(shape);
+        let git = GitCli::discover(repo.path()).expect("discover bench repo");
+        let config = TideConfig::default();
+        let releases = release::load_release_tags(&git, &config, true, false)
+            .expect("load tags")
+            .releases;
+        assert_eq!(releases.len(), shape.tags);
+        let target = git.head_commit().expect("head");
+
+        let bulk = release::select_anchor(&git, &releases, &target, "v", HistoryMode::AncestryPath)
+            .expect("select anchor");
+        assert_eq!(bulk.distance, per_tag_baseline(&git, &releases, &target));
+
+        let mut group = c.benchmark_group(format!("select_anchor/{}", shape.label()));
+        group.sample_size(10);
+        group.bench_function("bulk", |b| {
+            b.iter(|| {
+                release::select_anchor(&git, &releases, &target, "v", HistoryMode::AncestryPath)
+                    .expect("select anchor")
+            })
+        });
+        group.bench_function("per_tag_baseline", |b| {
+            b.iter(|| per_tag_baseline(&git, &releases, &target))
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, anchor_selection);
+
+fn main() {
+    let started = SystemTime::now();
+    benches();
+    Criterion::default().configure_from_args().final_summary();
+    support::budget::enforce(started);
+}
@@ -0,0 +1,106 @@
+//! TideMark
+//! ========
+//!
+//! File: benches/resolver.rs
+//! Description: Cold and warm timings for mark, file, and release tag resolution on synthetic repositories.
+//!
+//! Responsibility:
+//! - Time `resolve_mark`, `resolve_file`, and `load_release_tags` through the `Resolver` facade across repository sizes, then enforce `benches/budget.toml`.
+//!
+//! Architectural Position:
+//! - Performance regression suite for the resolver layer.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+mod support;
+
+use std::{path::Path, time::SystemTime};
+
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group};
+use support::{SHAPES, SyntheticRepo};
+use tidemark::{Resolver, TideConfig};
+
+/// Cold runs start from an empty `.git/tidemark-cache` and a freshly discovered repository;
+/// warm runs reuse one resolver whose cache already holds the result.
+fn open(repo: &Path) -> Resolver {
+    Resolver::builder()
+        .repo(repo)
+        .config(TideConfig::default())
+        .local_only(true)
+        .build()
+        .expect("open bench repo")
+}
+
+fn resolver_suite(c: &mut Criterion) {
+    let repos: Vec<(&str, SyntheticRepo)> = SHAPES
+        .iter()
+        .map(|(name, shape)| (*name, SyntheticRepo::generate(*shape)))
+        .collect();
+
+    let mut group = c.benchmark_group("resolve_mark");
+    group.sample_size(20);
+    for (name, repo) in &repos {
+        group.bench_with_input(BenchmarkId::new("cold", name), repo, |b, repo| {
+            b.iter_batched(
+                || {
+                    repo.clear_cache();
+                    open(repo.path())
+                },
+                |resolver| resolver.mark(None).expect("mark"),
+                BatchSize::PerIteration,
+            )
+        });
+        let warm = open(repo.path());
+        warm.mark(None).expect("prime mark cache");
+        group.bench_with_input(BenchmarkId::new("warm", name), &warm, |b, resolver| {
+            b.iter(|| resolver.mark(None).expect("mark"))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("resolve_file");
+    group.sample_size(20);
+    for (name, repo) in &repos {
+        group.bench_with_input(BenchmarkId::new("cold", name), repo, |b, repo| {
+            b.iter_batched(
+                || {
+                    repo.clear_cache();
+                    open(repo.path())
+                },
+                |resolver| resolver.file("main.txt").expect("file"),
+                BatchSize::PerIteration,
+            )
+        });
+        let warm = open(repo.path());
+        warm.file("main.txt").expect("prime file cache");
+        group.bench_with_input(BenchmarkId::new("warm", name), &warm, |b, resolver| {
+            b.iter(|| resolver.file("main.txt").expect("file"))
+        });
+    }
+    group.finish();
+
+    // Tag loading is not cached, so cold and warm differ only by repository discovery.
+    let mut group = c.benchmark_group("load_release_tags");
+    group.sample_size(20);
+    for (name, repo) in &repos {
+        group.bench_with_input(BenchmarkId::new("cold", name), repo, |b, repo| {
+            b.iter(|| open(repo.path()).releases().expect("releases"))
+        });
+        let warm = open(repo.path());
+        group.bench_with_input(BenchmarkId::new("warm", name), &warm, |b, resolver| {
+            b.iter(|| resolver.releases().expect("releases"))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, resolver_suite);
+
+fn main() {
+    let started = SystemTime::now();
+    benches();
+    Criterion::default().configure_from_args().final_summary();
+    support::budget::enforce(started);
+}
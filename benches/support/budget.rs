@@ -0,0 +1,118 @@
+//! TideMark
+//! ========
+//!
+//! File: benches/support/budget.rs
+//! Description: Performance budget check comparing criterion estimates with a same-run baseline.
+//!
+//! Responsibility:
+//! - Compare each benchmark's mean from this run with the `base` baseline and fail the bench process on large relative regressions.
+//! - Report means against the advisory ceilings in `benches/budget.toml` when no baseline exists.
+//!
+//! Architectural Position:
+//! - Benchmark support layer; the CI regression signal for resolver performance.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+    time::SystemTime,
+};
+
+use serde::Deserialize;
+
+/// Criterion baseline the CI job saves from the target branch before measuring this tree.
+const BASELINE: &str = "base";
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BudgetFile {
+    /// Largest allowed ratio of this run's mean to the baseline mean.
+    max_slowdown: f64,
+    /// Benchmark id (`group/function/parameter`) to advisory mean ceiling in milliseconds.
+    budgets_ms: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Estimates {
+    mean: Estimate,
+}
+
+#[derive(Debug, Deserialize)]
+struct Estimate {
+    /// Nanoseconds.
+    point_estimate: f64,
+}
+
+/// Check every budgeted benchmark measured since `started`; others are skipped.
+///
+/// Only runs under `cargo bench` (criterion's `--bench` flag). An id with a `base` baseline
+/// fails when slower than `max_slowdown` times it; an id without one is compared with its
+/// ceiling for information only. Exits 1 on any failure unless `TIDEMARK_BENCH_BUDGET=warn`.
+pub fn enforce(started: SystemTime) {
+    if !env::args().any(|arg| arg == "--bench") {
+        return;
+    }
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let raw = fs::read_to_string(manifest.join("benches").join("budget.toml"))
+        .expect("read benches/budget.toml");
+    let budget: BudgetFile = toml::from_str(raw.as_str()).expect("parse benches/budget.toml");
+    let root = criterion_home(manifest);
+
+    let mut over = 0;
+    for (id, ceiling_ms) in &budget.budgets_ms {
+        let path = root.join(id).join("new").join("estimates.json");
+        let fresh = fs::metadata(&path)
+            .and_then(|meta| meta.modified())
+            .is_ok_and(|modified| modified >= started);
+        if !fresh {
+            continue;
+        }
+        let mean_ms = read_mean_ms(&path).expect("read criterion estimates");
+        match read_mean_ms(&root.join(id).join(BASELINE).join("estimates.json")) {
+            Some(base_ms) => {
+                let ratio = mean_ms / base_ms;
+                let verdict = if ratio <= budget.max_slowdown {
+                    "ok"
+                } else {
+                    over += 1;
+                    "over"
+                };
+                println!("budget\t{id}\t{mean_ms:.3}\tbase={base_ms:.3}\t{ratio:.2}x\t{verdict}");
+            }
+            None => {
+                let verdict = if mean_ms <= *ceiling_ms { "ok" } else { "slow" };
+                println!("budget\t{id}\t{mean_ms:.3}\tceiling={ceiling_ms}\t-\t{verdict}");
+            }
+        }
+    }
+
+    if over > 0 && env::var("TIDEMARK_BENCH_BUDGET").as_deref() != Ok("warn") {
+        eprintln!(
+            "error: {over} benchmark(s) more than {}x slower than the `{BASELINE}` baseline",
+            budget.max_slowdown
+        );
+        process::exit(1);
+    }
+}
+
+/// Mean from a criterion `estimates.json`; `None` when the file does not exist.
+fn read_mean_ms(path: &Path) -> Option<f64> {
+    let estimates: Estimates = serde_json::from_str(fs::read_to_string(path).ok()?.as_str())
+        .expect("parse criterion estimates");
+    Some(estimates.mean.point_estimate / 1_000_000.0)
+}
+
+fn criterion_home(manifest: &Path) -> PathBuf {
+    if let Some(home) = env::var_os("CRITERION_HOME") {
+        return PathBuf::from(home);
+    }
+    env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| manifest.join("target"))
+        .join("criterion")
+}
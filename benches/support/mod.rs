@@ -0,0 +1,219 @@
+//! TideMark
+//! ========
+//!
+//! File: benches/support/mod.rs
+//! Description: Synthetic repository generator shared by the benchmark suites.
+//!
+//! Responsibility:
+//! - Build deterministic repositories of a given commit count, tag count, and merge density through one `git fast-import` stream.
+//! - Expose the shared shapes and the performance budget check.
+//!
+//! Architectural Position:
+//! - Benchmark support layer; never linked into the library or binaries.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+#![allow(dead_code)]
+
+pub mod budget;
+
+use std::{
+    fmt::Write as _,
+    io::Write as _,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use tempfile::TempDir;
+
+const BASE_TIMESTAMP: i64 = 1_700_000_000;
+/// One commit per hour, so every day holds a run of same-day commits.
+const COMMIT_SPACING: i64 = 60 * 60;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RepoShape {
+    /// Commits on `main`, excluding merged side commits.
+    pub commits: usize,
+    /// Annotated `v<n>` tags spread evenly over `main`.
+    pub tags: usize,
+    /// Every n-th `main` commit merges a one-commit side branch; 0 keeps history linear.
+    pub merge_every: usize,
+    /// Leading `main` commits left untagged, so the tags sit on top of deep history.
+    pub untagged: usize,
+}
+
+/// Sizes shared by the resolver suite and `benches/budget.toml`.
+pub const SHAPES: [(&str, RepoShape); 3] = [
+    (
+        "small",
+        RepoShape {
+            commits: 200,
+            tags: 10,
+            merge_every: 0,
+            untagged: 0,
+        },
+    ),
+    (
+        "medium",
+        RepoShape {
+            commits: 2_000,
+            tags: 100,
+            merge_every: 10,
+            untagged: 0,
+        },
+    ),
+    (
+        "large",
+        RepoShape {
+            commits: 10_000,
+            tags: 500,
+            merge_every: 5,
+            untagged: 0,
+        },
+    ),
+];
+
+impl RepoShape {
+    pub fn label(&self) -> String {
+        let label = format!("{}c-{}t-m{}", self.commits, self.tags, self.merge_every);
+        match self.untagged {
+            0 => label,
+            untagged => format!("{label}-u{untagged}"),
+        }
+    }
+}
+
+pub struct SyntheticRepo {
+    dir: TempDir,
+}
+
+impl SyntheticRepo {
+    pub fn generate(shape: RepoShape) -> Self {
+        let dir = tempfile::tempdir().expect("create bench repo dir");
+        git(dir.path(), &["init", "-q", "-b", "main"], None);
+        git(
+            dir.path(),
+            &["fast-import", "--quiet"],
+            Some(fast_import_stream(shape).as_bytes()),
+        );
+        git(dir.path(), &["reset", "-q", "--hard", "main"], None);
+        Self { dir }
+    }
+
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// `.git/tidemark-cache`, removed to measure cold runs.
+    pub fn clear_cache(&self) {
+        let cache = self.path().join(".git").join("tidemark-cache");
+        if cache.exists() {
+            std::fs::remove_dir_all(cache).expect("clear bench cache");
+        }
+    }
+}
+
+fn fast_import_stream(shape: RepoShape) -> String {
+    let mut out = String::new();
+    let tag_every = (shape.commits.saturating_sub(shape.untagged) / shape.tags.max(1)).max(1);
+    let mut next_mark = 1;
+    let mut previous_main: Option<usize> = None;
+    let mut tags = 0;
+
+    for index in 0..shape.commits {
+        let timestamp = BASE_TIMESTAMP + index as i64 * COMMIT_SPACING;
+        let side = match previous_main {
+            Some(parent) if shape.merge_every > 0 && index % shape.merge_every == 0 => {
+                let mark = next_mark;
+                next_mark += 1;
+                commit(
+                    &mut out,
+                    "side",
+                    mark,
+                    timestamp - 1,
+                    &[parent],
+                    "side.txt",
+                    index,
+                );
+                Some(mark)
+            }
+            _ => None,
+        };
+
+        let mark = next_mark;
+        next_mark += 1;
+        let mut parents: Vec<usize> = previous_main.into_iter().collect();
+        parents.extend(side);
+        commit(
+            &mut out, "main", mark, timestamp, &parents, "main.txt", index,
+        );
+        previous_main = Some(mark);
+
+        if tags < shape.tags
+            && index >= shape.untagged
+            && (index - shape.untagged).is_multiple_of(tag_every)
+        {
+            tags += 1;
+            let message = format!("release {tags}");
+            let _ = write!(
+                out,
+                "tag v{tags}\nfrom :{mark}\ntagger Bench <bench@tidemark.local> {timestamp} +0000\ndata {}\n{message}\n",
+                message.len()
+            );
+        }
+    }
+    out
+}
+
+fn commit(
+    out: &mut String,
+    branch: &str,
+    mark: usize,
+    timestamp: i64,
+    parents: &[usize],
+    file: &str,
+    index: usize,
+) {
+    let message = format!("{branch} {index}");
+    let content = format!("{index}\n");
+    let _ = write!(
+        out,
+        "commit refs/heads/{branch}\nmark :{mark}\ncommitter Bench <bench@tidemark.local> {timestamp} +0000\ndata {}\n{message}\n",
+        message.len()
+    );
+    for (position, parent) in parents.iter().enumerate() {
+        let verb = if position == 0 { "from" } else { "merge" };
+        let _ = writeln!(out, "{verb} :{parent}");
+    }
+    let _ = write!(
+        out,
+        "M 644 inline {file}\ndata {}\n{content}\n",
+        content.len()
+    );
+}
+
+fn git(dir: &Path, args: &[&str], stdin: Option<&[u8]>) {
+    let mut child = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .spawn()
+        .expect("spawn git");
+    if let Some(bytes) = stdin {
+        child
+            .stdin
+            .take()
+            .expect("git stdin")
+            .write_all(bytes)
+            .expect("write fast-import stream");
+    }
+    let status = child.wait().expect("wait for git");
+    assert!(status.success(), "git {args:?} failed");
+}
@@ -0,0 +1,227 @@
+//! TideMark
+//! ========
+//!
+//! File: src/api.rs
+//! Description: Stable embedding facade over repository discovery, config, and resolvers.
+//!
+//! Responsibility:
+//! - Offer one builder-configured entry point for mark, file, and release queries.
+//!
+//! Architectural Position:
+//! - Public library boundary; the CLI application layer is built on the same facade.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    config::{self, TideConfig},
+    core::{
+        model::{FileResult, LocateResult, MarkResult, ReleaseInventory},
+        release,
+        resolver::{
+            file::{FileRequest, FilesRequest, resolve_file, resolve_files},
+            locate::{LocateRequest, resolve_locate},
+            mark::{MarkRequest, resolve_mark},
+        },
+    },
+    error::{TideResult, io_err},
+    infra::{
+        cache::CacheStore,
+        git::{GitProvider, cli::GitCli},
+    },
+};
+
+/// Resolves version coordinates for one repository.
+///
+/// ```no_run
+/// let resolver = tidemark::Resolver::builder().local_only(true).build()?;
+/// println!("{}", resolver.mark(None)?.coordinate);
+/// # Ok::<(), tidemark::TideError>(())
+/// ```
+pub struct Resolver {
+    git: Box<dyn GitProvider>,
+    config: TideConfig,
+    cache: CacheStore,
+    local_only: bool,
+    require_remote: bool,
+    metadata_suffix: Option<String>,
+}
+
+/// Builder for [`Resolver`]; every setting is optional.
+#[derive(Default)]
+pub struct ResolverBuilder {
+    repo: Option<PathBuf>,
+    config: Option<TideConfig>,
+    provider: Option<Box<dyn GitProvider>>,
+    overrides: Vec<(String, String)>,
+    local_only: bool,
+    require_remote: bool,
+    metadata_suffix: Option<String>,
+}
+
+impl ResolverBuilder {
+    /// Directory inside the repository to open (default: current directory). Ignored when a
+    /// provider is supplied.
+    pub fn repo(mut self, path: impl Into<PathBuf>) -> Self {
+        self.repo = Some(path.into());
+        self
+    }
+
+    /// Use this config instead of the layered global, repository, and `TIDEMARK_*` config.
+    pub fn config(mut self, config: TideConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Override one dotted config key (e.g. `remote.strategy`) on top of every other layer.
+    /// Ignored when an explicit config is supplied.
+    pub fn config_override(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.overrides.push((key.into(), value.into()));
+        self
+    }
+
+    /// Use a custom git backend instead of the `git` CLI.
+    pub fn provider(mut self, provider: Box<dyn GitProvider>) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+
+    /// Skip remote tag queries and use local tags only.
+    pub fn local_only(mut self, local_only: bool) -> Self {
+        self.local_only = local_only;
+        self
+    }
+
+    /// Fail instead of falling back to local tags when a remote refresh fails.
+    pub fn require_remote(mut self, require_remote: bool) -> Self {
+        self.require_remote = require_remote;
+        self
+    }
+
+    /// Metadata suffix template; overrides `output.metadata_suffix`.
+    pub fn metadata_suffix(mut self, suffix: Option<String>) -> Self {
+        self.metadata_suffix = suffix;
+        self
+    }
+
+    pub fn build(self) -> TideResult<Resolver> {
+        let git: Box<dyn GitProvider> = match self.provider {
+            Some(provider) => provider,
+            None => {
+                let start = match self.repo {
+                    Some(path) => path,
+                    None => env::current_dir().map_err(|err| io_err(".", err))?,
+                };
+                Box::new(GitCli::discover(start.as_path())?)
+            }
+        };
+        let config = match self.config {
+            Some(config) => config,
+            None => config::layer::load_layered(git.repo_root(), &self.overrides)?.config,
+        };
+        let cache = CacheStore::from_config(git.git_dir()?.as_path(), &config.cache)?;
+        Ok(Resolver {
+            git,
+            config,
+            cache,
+            local_only: self.local_only,
+            require_remote: self.require_remote,
+            metadata_suffix: self.metadata_suffix,
+        })
+    }
+}
+
+impl Resolver {
+    pub fn builder() -> ResolverBuilder {
+        ResolverBuilder::default()
+    }
+
+    /// Coordinate for `rev` (any commit-ish), or `HEAD` when `None`.
+    pub fn mark(&self, rev: Option<&str>) -> TideResult<MarkResult> {
+        resolve_mark(
+            self.git.as_ref(),
+            &self.config,
+            &self.cache,
+            MarkRequest {
+                target_rev: rev.map(str::to_string),
+                local_only: self.local_only,
+                require_remote: self.require_remote,
+                metadata_suffix: self.metadata_suffix.clone(),
+            },
+        )
+    }
+
+    /// Coordinate of the last commit that modified `path` (relative to the repository root).
+    pub fn file(&self, path: impl AsRef<Path>) -> TideResult<FileResult> {
+        resolve_file(
+            self.git.as_ref(),
+            &self.config,
+            &self.cache,
+            FileRequest {
+                path: path.as_ref().to_path_buf(),
+                local_only: self.local_only,
+                require_remote: self.require_remote,
+                metadata_suffix: self.metadata_suffix.clone(),
+            },
+        )
+    }
+
+    /// Coordinates of every file matched by `patterns`: files, directories (recursively), and
+    /// glob patterns, relative to the repository root. Sorted by path; one history walk.
+    pub fn files(&self, patterns: &[String]) -> TideResult<Vec<FileResult>> {
+        resolve_files(
+            self.git.as_ref(),
+            &self.config,
+            &self.cache,
+            FilesRequest {
+                patterns: patterns.to_vec(),
+                local_only: self.local_only,
+                require_remote: self.require_remote,
+                metadata_suffix: self.metadata_suffix.clone(),
+            },
+        )
+    }
+
+    /// Commits reachable from `from` (or `HEAD`) whose coordinate is `coordinate` (`x.y.z`).
+    pub fn locate(&self, coordinate: &str, from: Option<&str>) -> TideResult<LocateResult> {
+        resolve_locate(
+            self.git.as_ref(),
+            &self.config,
+            &self.cache,
+            LocateRequest {
+                coordinate: coordinate.to_string(),
+                from_rev: from.map(str::to_string),
+                local_only: self.local_only,
+                require_remote: self.require_remote,
+            },
+        )
+    }
+
+    /// Release tags recognized under the current config, with remote outcomes and conflicts.
+    pub fn releases(&self) -> TideResult<ReleaseInventory> {
+        release::load_release_tags(
+            self.git.as_ref(),
+            &self.config,
+            self.local_only,
+            self.require_remote,
+        )
+    }
+
+    pub fn config(&self) -> &TideConfig {
+        &self.config
+    }
+
+    pub fn repo_root(&self) -> &Path {
+        self.git.repo_root()
+    }
+
+    pub fn git(&self) -> &dyn GitProvider {
+        self.git.as_ref()
+    }
+}
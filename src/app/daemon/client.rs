@@ -0,0 +1,146 @@
+//! TideMark
+//! ========
+//!
+//! File: src/app/daemon/client.rs
+//! Description: CLI-side connection to a running `tide serve` daemon.
+//!
+//! Responsibility:
+//! - Find the daemon advertised in the repository's git dir without spawning git, send one JSON-RPC call, and report when the CLI must resolve in process instead.
+//!
+//! Architectural Position:
+//! - Application layer transport used by the CLI runner before it opens a resolver.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+use std::{
+    env, fs,
+    io::{BufRead, BufReader, Write},
+    os::unix::{fs::MetadataExt, net::UnixStream},
+    path::{Path, PathBuf},
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+
+use super::{
+    ClientContext, DISCOVERY_FILE_NAME, JSONRPC_VERSION, RESOLUTION_FAILED, RpcRequest,
+    RpcResponse, Served,
+};
+use crate::{
+    config::layer::load_layered,
+    error::{TideError, TideResult},
+};
+
+pub struct DaemonClient {
+    stream: BufReader<UnixStream>,
+    next_id: u64,
+    repo_root: PathBuf,
+}
+
+impl DaemonClient {
+    /// Connect to the daemon advertised for the repository containing `cwd`.
+    ///
+    /// `None` when no daemon is advertised or listening, when `GIT_DIR`/`GIT_WORK_TREE` redirect
+    /// git, or when `.git` is a file (linked worktrees and submodules resolve in process). Also
+    /// `None` unless both the discovery file and the socket belong to this user and nobody else
+    /// can write them: anyone able to write `.git` could otherwise redirect clients.
+    pub fn connect(cwd: &Path) -> Option<Self> {
+        if env::var_os("GIT_DIR").is_some() || env::var_os("GIT_WORK_TREE").is_some() {
+            return None;
+        }
+        let dot_git = cwd
+            .ancestors()
+            .map(|dir| dir.join(".git"))
+            .find(|candidate| candidate.exists())?;
+        if !dot_git.is_dir() {
+            return None;
+        }
+        let discovery = dot_git.join(DISCOVERY_FILE_NAME);
+        if !owned_privately(discovery.as_path()) {
+            return None;
+        }
+        let socket = fs::read_to_string(&discovery).ok()?;
+        let socket = Path::new(socket.trim());
+        if !owned_privately(socket) {
+            return None;
+        }
+        let stream = UnixStream::connect(socket).ok()?;
+        Some(Self {
+            stream: BufReader::new(stream),
+            next_id: 1,
+            repo_root: dot_git.parent()?.to_path_buf(),
+        })
+    }
+
+    /// The context to send with each call, built from this client's own config layers. `None`
+    /// when the config does not load; resolving in process then reports the error.
+    pub fn context(
+        &self,
+        overrides: &[(String, String)],
+        metadata_suffix: Option<&str>,
+    ) -> Option<ClientContext> {
+        let layered = load_layered(self.repo_root.as_path(), overrides).ok()?;
+        ClientContext::current(overrides, &layered.config, metadata_suffix).ok()
+    }
+
+    /// Call `method`. `Ok(None)` means the daemon cannot answer for this client (context
+    /// mismatch, protocol or transport failure) and the caller should resolve in process;
+    /// resolution failures come back as [`TideError::Daemon`].
+    pub fn call<P: Serialize, T: DeserializeOwned>(
+        &mut self,
+        method: &str,
+        params: &P,
+    ) -> TideResult<Option<Served<T>>> {
+        let Ok(params) = serde_json::to_value(params) else {
+            return Ok(None);
+        };
+        let request = RpcRequest {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id: Value::from(self.next_id),
+            method: method.to_string(),
+            params,
+        };
+        self.next_id += 1;
+        let Ok(mut line) = serde_json::to_vec(&request) else {
+            return Ok(None);
+        };
+        line.push(b'\n');
+        if self.stream.get_mut().write_all(&line).is_err() {
+            return Ok(None);
+        }
+
+        let mut reply = String::new();
+        if self.stream.read_line(&mut reply).is_err() {
+            return Ok(None);
+        }
+        let Ok(response) = serde_json::from_str::<RpcResponse>(reply.as_str()) else {
+            return Ok(None);
+        };
+        if let Some(error) = response.error {
+            return match error.data {
+                Some(data) if error.code == RESOLUTION_FAILED => Err(TideError::Daemon {
+                    message: error.message,
+                    exit_code: data.exit_code,
+                }),
+                _ => Ok(None),
+            };
+        }
+        Ok(response
+            .result
+            .and_then(|result| serde_json::from_value(result).ok()))
+    }
+}
+
+/// `path` itself (symlinks are not followed) is owned by the effective user and is neither group-
+/// nor world-writable.
+fn owned_privately(path: &Path) -> bool {
+    fs::symlink_metadata(path)
+        .is_ok_and(|meta| meta.uid() == effective_uid() && meta.mode() & 0o022 == 0)
+}
+
+fn effective_uid() -> u32 {
+    // SAFETY: geteuid takes no arguments, has no preconditions, and always succeeds.
+    unsafe { libc::geteuid() }
+}
@@ -0,0 +1,250 @@
+//! TideMark
+//! ========
+//!
+//! File: src/app/daemon/mod.rs
+//! Description: Wire protocol, discovery file, and client context shared by `tide serve` and the CLI.
+//!
+//! Responsibility:
+//! - Define newline-delimited JSON-RPC 2.0 messages for `mark`, `file`, `releases`, and `locate`, and decide when a daemon may answer for a client.
+//!
+//! Architectural Position:
+//! - Application layer front end beside the CLI runner; shared by the daemon and its clients.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+#[cfg(unix)]
+pub mod client;
+#[cfg(unix)]
+pub mod server;
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env,
+    path::PathBuf,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    config::{TideConfig, layer::ENV_PREFIX},
+    core::{model::FileResult, resolver::mark::metadata_template},
+    error::TideResult,
+};
+
+/// Written to `<git-dir>/tidemark-daemon` by `tide serve`; holds the socket path.
+pub const DISCOVERY_FILE_NAME: &str = "tidemark-daemon";
+pub const JSONRPC_VERSION: &str = "2.0";
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+/// Resolution failed; `data.exit_code` is the exit status the CLI would have returned.
+pub const RESOLUTION_FAILED: i64 = -32000;
+/// The client's config layers or environment differ from the daemon's; resolve in process.
+pub const CONTEXT_MISMATCH: i64 = -32001;
+
+/// Variables outside `TIDEMARK_*` that change resolution: the global config location, the
+/// zone behind `time.timezone = "local"`, and the GitHub tag-build marker consulted by
+/// `branch.ci_env`.
+const CONTEXT_ENV_VARS: [&str; 4] = ["HOME", "XDG_CONFIG_HOME", "TZ", "GITHUB_REF_TYPE"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcRequest {
+    pub jsonrpc: String,
+    #[serde(default)]
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcResponse {
+    pub jsonrpc: String,
+    pub id: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<RpcErrorData>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcErrorData {
+    pub exit_code: u8,
+}
+
+/// Settings from outside the repository that a CLI client resolved under. Third-party clients
+/// omit it and get the daemon's own context.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClientContext {
+    /// `-c key=value` overrides, in order.
+    #[serde(default)]
+    pub overrides: Vec<(String, String)>,
+    /// The client's values for variables that affect resolution; unset variables are absent.
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+}
+
+impl ClientContext {
+    /// Context for a client resolving under `config`. Only variables that can change resolution
+    /// are captured, so nothing else from the environment leaves the process.
+    pub fn current(
+        overrides: &[(String, String)],
+        config: &TideConfig,
+        metadata_suffix: Option<&str>,
+    ) -> TideResult<Self> {
+        let template = metadata_template(metadata_suffix.map(str::to_string), config)?;
+        let names = context_env_names(
+            config,
+            template.iter().flat_map(|template| template.env_names()),
+        );
+        let env = env::vars()
+            .filter(|(name, _)| name.starts_with(ENV_PREFIX) || names.contains(name))
+            .collect();
+        Ok(Self {
+            overrides: overrides.to_vec(),
+            env,
+        })
+    }
+
+    /// Whether a daemon started with `daemon` overrides and `config` resolves exactly as this
+    /// client would. `template_env` lists variables read by the active metadata template.
+    pub fn matches_daemon<'a>(
+        &self,
+        daemon: &[(String, String)],
+        config: &TideConfig,
+        template_env: impl Iterator<Item = &'a str>,
+    ) -> bool {
+        if self.overrides != daemon {
+            return false;
+        }
+        let mut names = context_env_names(config, template_env);
+        names.extend(
+            self.env
+                .keys()
+                .filter(|name| name.starts_with(ENV_PREFIX))
+                .cloned(),
+        );
+        names.extend(
+            env::vars()
+                .map(|(name, _)| name)
+                .filter(|name| name.starts_with(ENV_PREFIX)),
+        );
+        names
+            .iter()
+            .all(|name| self.env.get(name).cloned() == env::var(name).ok())
+    }
+}
+
+/// Variables outside `TIDEMARK_*` that `config` and the active template read.
+fn context_env_names<'a>(
+    config: &TideConfig,
+    template_env: impl Iterator<Item = &'a str>,
+) -> BTreeSet<String> {
+    let mut names: BTreeSet<String> = CONTEXT_ENV_VARS
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    names.extend(config.branch.ci_env.iter().cloned());
+    names.extend(template_env.map(str::to_string));
+    names
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MarkParams {
+    /// Commit-ish to resolve (default: `HEAD`).
+    #[serde(default)]
+    pub rev: Option<String>,
+    #[serde(default)]
+    pub local_only: bool,
+    #[serde(default)]
+    pub require_remote: bool,
+    #[serde(default)]
+    pub metadata_suffix: Option<String>,
+    #[serde(default)]
+    pub context: Option<ClientContext>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FileParams {
+    pub path: PathBuf,
+    #[serde(default)]
+    pub local_only: bool,
+    #[serde(default)]
+    pub require_remote: bool,
+    #[serde(default)]
+    pub metadata_suffix: Option<String>,
+    #[serde(default)]
+    pub context: Option<ClientContext>,
+}
+
+/// Files, directories, and glob patterns resolved together (see `FilesRequest`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FilesParams {
+    pub patterns: Vec<String>,
+    #[serde(default)]
+    pub local_only: bool,
+    #[serde(default)]
+    pub require_remote: bool,
+    #[serde(default)]
+    pub metadata_suffix: Option<String>,
+    #[serde(default)]
+    pub context: Option<ClientContext>,
+}
+
+/// Result of the `files` method, sorted by path.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileList {
+    pub files: Vec<FileResult>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReleasesParams {
+    #[serde(default)]
+    pub local_only: bool,
+    #[serde(default)]
+    pub require_remote: bool,
+    #[serde(default)]
+    pub context: Option<ClientContext>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LocateParams {
+    pub coordinate: String,
+    /// Search commits reachable from this revision (default: `HEAD`).
+    #[serde(default)]
+    pub from: Option<String>,
+    #[serde(default)]
+    pub local_only: bool,
+    #[serde(default)]
+    pub require_remote: bool,
+    #[serde(default)]
+    pub context: Option<ClientContext>,
+}
+
+/// A method result plus the stderr warnings the CLI would have printed for it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Served<T> {
+    #[serde(flatten)]
+    pub value: T,
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
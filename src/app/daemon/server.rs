@@ -0,0 +1,538 @@
+//! TideMark
+//! ========
+//!
+//! File: src/app/daemon/server.rs
+//! Description: `tide serve`: a Unix-socket JSON-RPC daemon with warm config, tags, and history.
+//!
+//! Responsibility:
+//! - Keep one repository's config, memoized git answers, and in-memory cache tier alive, dropping them whenever refs or config files change.
+//!
+//! Architectural Position:
+//! - Application layer front end; answers the same queries as the CLI through the core resolvers.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+use std::{
+    fs,
+    io::{self, BufRead, BufReader, Write},
+    os::unix::{
+        fs::{DirBuilderExt, MetadataExt, OpenOptionsExt, PermissionsExt},
+        net::{UnixListener, UnixStream},
+    },
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+
+use super::{
+    CONTEXT_MISMATCH, ClientContext, DISCOVERY_FILE_NAME, FileList, FileParams, FilesParams,
+    INVALID_PARAMS, INVALID_REQUEST, JSONRPC_VERSION, LocateParams, METHOD_NOT_FOUND, MarkParams,
+    PARSE_ERROR, RESOLUTION_FAILED, ReleasesParams, RpcError, RpcErrorData, RpcRequest,
+    RpcResponse, Served,
+};
+use crate::{
+    config::{self, ConflictPolicy, TideConfig},
+    core::{
+        model::TagConflict,
+        release,
+        resolver::{
+            file::{FileRequest, FilesRequest, resolve_file, resolve_files, tag_conflicts},
+            locate::{LocateRequest, resolve_locate},
+            mark::{MarkRequest, metadata_template, resolve_mark},
+        },
+    },
+    error::{TideError, TideResult, io_err},
+    infra::{
+        cache::CacheStore,
+        git::{GitProvider, cli::GitCli, warm::WarmGit},
+    },
+    interface::output,
+};
+
+#[derive(Debug, Clone)]
+pub struct ServeRequest {
+    /// Directory inside the repository to serve.
+    pub start_dir: PathBuf,
+    pub socket: PathBuf,
+    /// `-c key=value` overrides applied to every answer; clients must pass the same ones.
+    pub overrides: Vec<(String, String)>,
+}
+
+/// A bound daemon. Dropping it removes the socket and, if it still names this daemon, the
+/// discovery file.
+pub struct Daemon {
+    listener: UnixListener,
+    socket: PathBuf,
+    discovery: PathBuf,
+    state: Arc<Mutex<DaemonState>>,
+}
+
+impl Daemon {
+    pub fn bind(req: ServeRequest) -> TideResult<Self> {
+        let git = GitCli::discover(req.start_dir.as_path())?;
+        let git_dir = git.git_dir()?;
+        let socket = if req.socket.is_absolute() {
+            req.socket
+        } else {
+            req.start_dir.join(req.socket)
+        };
+        let state = DaemonState::open(git, req.overrides)?;
+        let listener = bind_socket(socket.as_path())?;
+        let discovery = git_dir.join(DISCOVERY_FILE_NAME);
+        write_discovery(discovery.as_path(), socket.as_path())?;
+        Ok(Self {
+            listener,
+            socket,
+            discovery,
+            state: Arc::new(Mutex::new(state)),
+        })
+    }
+
+    pub fn socket(&self) -> &Path {
+        self.socket.as_path()
+    }
+
+    /// Accept connections until the listener fails; each connection gets its own thread, and
+    /// requests are answered one at a time against the shared state.
+    pub fn run(&self) -> TideResult<()> {
+        for stream in self.listener.incoming() {
+            let stream = stream.map_err(|err| io_err(&self.socket, err))?;
+            let state = Arc::clone(&self.state);
+            thread::spawn(move || serve_connection(stream, &state));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Daemon {
+    fn drop(&mut self) {
+        let advertised = fs::read_to_string(&self.discovery).unwrap_or_default();
+        if Path::new(advertised.trim()) == self.socket {
+            let _ = fs::remove_file(&self.discovery);
+        }
+        let _ = fs::remove_file(&self.socket);
+    }
+}
+
+/// Bind `path` with mode 0600, replacing a socket file left behind by a daemon that is no
+/// longer listening.
+///
+/// Clients refuse sockets that anyone else can write, and `bind` applies the process umask, so
+/// the socket is created inside a private 0700 directory, restricted there, and only then
+/// renamed into place; it is never reachable with broader permissions.
+fn bind_socket(path: &Path) -> TideResult<UnixListener> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let staging = parent.join(format!(".tide-bind-{}", std::process::id()));
+    match fs::remove_dir_all(&staging) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(io_err(&staging, err)),
+        _ => {}
+    }
+    fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&staging)
+        .map_err(|err| io_err(&staging, err))?;
+    let staged = staging.join("s");
+    let bound = UnixListener::bind(&staged)
+        .and_then(|listener| {
+            fs::set_permissions(&staged, fs::Permissions::from_mode(0o600))?;
+            Ok(listener)
+        })
+        .map_err(|err| io_err(&staged, err))
+        .and_then(|listener| {
+            if UnixStream::connect(path).is_ok() {
+                return Err(io_err(path, io::Error::from(io::ErrorKind::AddrInUse)));
+            }
+            fs::rename(&staged, path).map_err(|err| io_err(path, err))?;
+            Ok(listener)
+        });
+    let _ = fs::remove_dir_all(&staging);
+    bound
+}
+
+/// Replace the discovery file with a fresh one owned by this user and writable only by it.
+fn write_discovery(discovery: &Path, socket: &Path) -> TideResult<()> {
+    match fs::remove_file(discovery) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(io_err(discovery, err)),
+    }
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o644)
+        .open(discovery)
+        .and_then(|mut file| writeln!(file, "{}", socket.display()))
+        .map_err(|err| io_err(discovery, err))
+}
+
+fn serve_connection(stream: UnixStream, state: &Mutex<DaemonState>) {
+    let Ok(mut writer) = stream.try_clone() else {
+        return;
+    };
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else {
+            return;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Ok(mut state) = state.lock() else {
+            return;
+        };
+        let response = state.handle(line.as_str());
+        let Ok(mut encoded) = serde_json::to_vec(&response) else {
+            return;
+        };
+        encoded.push(b'\n');
+        if writer.write_all(&encoded).is_err() {
+            return;
+        }
+    }
+}
+
+struct DaemonState {
+    git: WarmGit<GitCli>,
+    overrides: Vec<(String, String)>,
+    config: TideConfig,
+    cache: CacheStore,
+    watch: Watch,
+}
+
+impl DaemonState {
+    fn open(git: GitCli, overrides: Vec<(String, String)>) -> TideResult<Self> {
+        let config = config::layer::load_layered(git.repo_root(), &overrides)?.config;
+        let git_dir = git.git_dir()?;
+        let cache = CacheStore::from_config(git_dir.as_path(), &config.cache)?.with_memory();
+        let watch = Watch::new(git.repo_root(), git_dir.as_path());
+        Ok(Self {
+            git: WarmGit::new(git),
+            overrides,
+            config,
+            cache,
+            watch,
+        })
+    }
+
+    fn handle(&mut self, line: &str) -> RpcResponse {
+        let (id, outcome) = match serde_json::from_str::<RpcRequest>(line) {
+            Ok(request) => (request.id.clone(), self.dispatch(request)),
+            Err(err) => (
+                Value::Null,
+                Err(rpc_error(
+                    if serde_json::from_str::<Value>(line).is_ok() {
+                        INVALID_REQUEST
+                    } else {
+                        PARSE_ERROR
+                    },
+                    err.to_string(),
+                )),
+            ),
+        };
+        let (result, error) = match outcome {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+        RpcResponse {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id,
+            result,
+            error,
+        }
+    }
+
+    fn dispatch(&mut self, request: RpcRequest) -> Result<Value, RpcError> {
+        if request.jsonrpc != JSONRPC_VERSION {
+            return Err(rpc_error(INVALID_REQUEST, "jsonrpc must be \"2.0\""));
+        }
+        self.refresh().map_err(failure)?;
+        match request.method.as_str() {
+            "mark" => {
+                let params: MarkParams = params(request.params)?;
+                self.check_context(params.context.as_ref(), params.metadata_suffix.as_deref())?;
+                let result = resolve_mark(
+                    &self.git,
+                    &self.config,
+                    &self.cache,
+                    MarkRequest {
+                        target_rev: params.rev,
+                        local_only: params.local_only,
+                        require_remote: params.require_remote,
+                        metadata_suffix: params.metadata_suffix,
+                    },
+                )
+                .map_err(failure)?;
+                let warnings = self.warnings(&result.explain.tag_conflicts);
+                to_value(Served {
+                    value: result,
+                    warnings,
+                })
+            }
+            "file" => {
+                let params: FileParams = params(request.params)?;
+                self.check_context(params.context.as_ref(), params.metadata_suffix.as_deref())?;
+                let result = resolve_file(
+                    &self.git,
+                    &self.config,
+                    &self.cache,
+                    FileRequest {
+                        path: params.path,
+                        local_only: params.local_only,
+                        require_remote: params.require_remote,
+                        metadata_suffix: params.metadata_suffix,
+                    },
+                )
+                .map_err(failure)?;
+                let warnings = self.warnings(&result.mark.explain.tag_conflicts);
+                to_value(Served {
+                    value: result,
+                    warnings,
+                })
+            }
+            "files" => {
+                let params: FilesParams = params(request.params)?;
+                self.check_context(params.context.as_ref(), params.metadata_suffix.as_deref())?;
+                let files = resolve_files(
+                    &self.git,
+                    &self.config,
+                    &self.cache,
+                    FilesRequest {
+                        patterns: params.patterns,
+                        local_only: params.local_only,
+                        require_remote: params.require_remote,
+                        metadata_suffix: params.metadata_suffix,
+                    },
+                )
+                .map_err(failure)?;
+                let warnings = self.warnings(&tag_conflicts(&files));
+                to_value(Served {
+                    value: FileList { files },
+                    warnings,
+                })
+            }
+            "releases" => {
+                let params: ReleasesParams = params(request.params)?;
+                self.check_context(params.context.as_ref(), None)?;
+                let inventory = release::load_release_tags(
+                    &self.git,
+                    &self.config,
+                    params.local_only,
+                    params.require_remote,
+                )
+                .map_err(failure)?;
+                let warnings = self.warnings(&inventory.conflicts);
+                to_value(Served {
+                    value: inventory,
+                    warnings,
+                })
+            }
+            "locate" => {
+                let params: LocateParams = params(request.params)?;
+                self.check_context(params.context.as_ref(), None)?;
+                let result = resolve_locate(
+                    &self.git,
+                    &self.config,
+                    &self.cache,
+                    LocateRequest {
+                        coordinate: params.coordinate,
+                        from_rev: params.from,
+                        local_only: params.local_only,
+                        require_remote: params.require_remote,
+                    },
+                )
+                .map_err(failure)?;
+                to_value(Served {
+                    value: result,
+                    warnings: Vec::new(),
+                })
+            }
+            other => Err(rpc_error(
+                METHOD_NOT_FOUND,
+                format!("unknown method `{other}`"),
+            )),
+        }
+    }
+
+    /// Drop memoized git answers when refs moved, and reload config when a config file changed.
+    fn refresh(&mut self) -> TideResult<()> {
+        let (refs_moved, config_changed) = self.watch.poll();
+        if refs_moved {
+            self.git.invalidate();
+        }
+        if config_changed {
+            let config = config::layer::load_layered(self.git.repo_root(), &self.overrides)?.config;
+            self.cache = CacheStore::from_config(self.git.git_dir()?.as_path(), &config.cache)?
+                .with_memory();
+            self.config = config;
+            self.watch.commit_config();
+        }
+        Ok(())
+    }
+
+    fn check_context(
+        &self,
+        context: Option<&ClientContext>,
+        metadata_suffix: Option<&str>,
+    ) -> Result<(), RpcError> {
+        let Some(context) = context else {
+            return Ok(());
+        };
+        let template = metadata_template(metadata_suffix.map(str::to_string), &self.config)
+            .map_err(failure)?;
+        let template_env = template.iter().flat_map(|template| template.env_names());
+        if context.matches_daemon(&self.overrides, &self.config, template_env) {
+            Ok(())
+        } else {
+            Err(rpc_error(
+                CONTEXT_MISMATCH,
+                "client config overrides or environment differ from the daemon's",
+            ))
+        }
+    }
+
+    fn warnings(&self, conflicts: &[TagConflict]) -> Vec<String> {
+        if self.config.remote.on_conflict != ConflictPolicy::Warn {
+            return Vec::new();
+        }
+        conflicts
+            .iter()
+            .map(output::format_tag_conflict_warning)
+            .collect()
+    }
+}
+
+fn params<T: DeserializeOwned>(raw: Value) -> Result<T, RpcError> {
+    let raw = if raw.is_null() {
+        Value::Object(Default::default())
+    } else {
+        raw
+    };
+    serde_json::from_value(raw).map_err(|err| rpc_error(INVALID_PARAMS, err.to_string()))
+}
+
+fn to_value<T: Serialize>(value: T) -> Result<Value, RpcError> {
+    serde_json::to_value(value).map_err(|err| {
+        failure(TideError::Internal {
+            message: err.to_string(),
+        })
+    })
+}
+
+fn rpc_error(code: i64, message: impl Into<String>) -> RpcError {
+    RpcError {
+        code,
+        message: message.into(),
+        data: None,
+    }
+}
+
+fn failure(err: TideError) -> RpcError {
+    RpcError {
+        code: RESOLUTION_FAILED,
+        message: err.to_string(),
+        data: Some(RpcErrorData {
+            exit_code: err.exit_status(),
+        }),
+    }
+}
+
+/// Stat fingerprint of one file: inode, modification time, and length.
+type Stamp = Option<(u64, i64, i64, u64)>;
+
+/// Change detection for `HEAD`, `packed-refs`, `shallow`, every loose ref, and the config
+/// files, compared before each request so an answer never trails a ref update.
+struct Watch {
+    /// The git dir plus, for linked worktrees, the common dir holding shared refs.
+    git_dirs: Vec<PathBuf>,
+    config_files: Vec<PathBuf>,
+    refs: Vec<(PathBuf, Stamp)>,
+    config: Vec<(PathBuf, Stamp)>,
+    /// Config snapshot taken by the last poll; adopted once the reload succeeds.
+    pending_config: Vec<(PathBuf, Stamp)>,
+}
+
+impl Watch {
+    fn new(repo_root: &Path, git_dir: &Path) -> Self {
+        let mut git_dirs = vec![git_dir.to_path_buf()];
+        if let Ok(common) = fs::read_to_string(git_dir.join("commondir")) {
+            git_dirs.push(git_dir.join(common.trim()));
+        }
+        let mut config_files = vec![repo_root.join(config::CONFIG_FILE_NAME)];
+        config_files.extend(config::layer::global_config_path());
+        let mut watch = Self {
+            git_dirs,
+            config_files,
+            refs: Vec::new(),
+            config: Vec::new(),
+            pending_config: Vec::new(),
+        };
+        watch.refs = watch.refs_snapshot();
+        watch.config = watch.config_snapshot();
+        watch
+    }
+
+    /// `(refs moved, config changed)` since the last poll.
+    fn poll(&mut self) -> (bool, bool) {
+        let refs = self.refs_snapshot();
+        let refs_moved = refs != self.refs;
+        self.refs = refs;
+        self.pending_config = self.config_snapshot();
+        (refs_moved, self.pending_config != self.config)
+    }
+
+    fn commit_config(&mut self) {
+        self.config = std::mem::take(&mut self.pending_config);
+    }
+
+    fn refs_snapshot(&self) -> Vec<(PathBuf, Stamp)> {
+        let mut files = Vec::new();
+        for dir in &self.git_dirs {
+            for name in ["HEAD", "packed-refs", "shallow"] {
+                files.push(dir.join(name));
+            }
+            collect_files(dir.join("refs").as_path(), &mut files);
+        }
+        files
+            .into_iter()
+            .map(|path| {
+                let stamp = stamp(&path);
+                (path, stamp)
+            })
+            .collect()
+    }
+
+    fn config_snapshot(&self) -> Vec<(PathBuf, Stamp)> {
+        self.config_files
+            .iter()
+            .map(|path| (path.clone(), stamp(path)))
+            .collect()
+    }
+}
+
+fn stamp(path: &Path) -> Stamp {
+    let meta = fs::metadata(path).ok()?;
+    Some((meta.ino(), meta.mtime(), meta.mtime_nsec(), meta.len()))
+}
+
+/// Every file below `dir`, in sorted order so snapshots compare positionally.
+fn collect_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            collect_files(path.as_path(), out);
+        } else {
+            out.push(path);
+        }
+    }
+}
@@ -0,0 +1,300 @@
+//! TideMark
+//! ========
+//!
+//! File: src/app/lsp.rs
+//! Description: Minimal Language Server Protocol front end for per-file coordinates.
+//!
+//! Responsibility:
+//! - Answer `textDocument/hover` and `textDocument/codeLens` with the open file's last-modifying coordinate, anchor tag, and commit over stdio.
+//!
+//! Architectural Position:
+//! - Application layer front end beside the CLI and daemon; resolves through `Resolver::file` and its cache.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+use std::{
+    fs,
+    io::{BufRead, Write},
+    path::{Path, PathBuf},
+};
+
+use serde_json::{Value, json};
+
+use super::daemon::{
+    INVALID_PARAMS, INVALID_REQUEST, JSONRPC_VERSION, METHOD_NOT_FOUND, PARSE_ERROR, RpcError,
+    RpcRequest, RpcResponse,
+};
+use crate::{
+    api::Resolver,
+    core::model::FileResult,
+    error::{TideError, TideResult, io_err},
+    interface::output,
+};
+
+/// LSP `ServerNotInitialized`.
+const SERVER_NOT_INITIALIZED: i64 = -32002;
+/// LSP `RequestFailed`.
+const REQUEST_FAILED: i64 = -32803;
+/// Command id attached to code lenses; clients without a handler show the lens as a label.
+const LENS_COMMAND: &str = "tidemark.file";
+
+/// Serve one editor session until `exit` or end of input. Files resolve with local tags only,
+/// through one resolver per repository.
+pub fn serve(
+    input: impl BufRead,
+    mut output: impl Write,
+    overrides: &[(String, String)],
+) -> TideResult<()> {
+    let mut session = Session {
+        overrides: overrides.to_vec(),
+        resolvers: Vec::new(),
+        initialized: false,
+        shutdown: false,
+    };
+    let mut input = input;
+    while let Some(body) = read_message(&mut input)? {
+        let request = match serde_json::from_slice::<RpcRequest>(&body) {
+            Ok(request) => request,
+            Err(err) => {
+                let code = if serde_json::from_slice::<Value>(&body).is_ok() {
+                    INVALID_REQUEST
+                } else {
+                    PARSE_ERROR
+                };
+                write_message(&mut output, &reply(Value::Null, Err(error(code, err))))?;
+                continue;
+            }
+        };
+        if request.method == "exit" {
+            break;
+        }
+        // Notifications (no id) never get a response.
+        let is_notification = request.id.is_null();
+        let id = request.id.clone();
+        let outcome = session.handle(request);
+        if !is_notification {
+            write_message(&mut output, &reply(id, outcome))?;
+        }
+    }
+    Ok(())
+}
+
+struct Session {
+    overrides: Vec<(String, String)>,
+    resolvers: Vec<Resolver>,
+    initialized: bool,
+    shutdown: bool,
+}
+
+impl Session {
+    fn handle(&mut self, request: RpcRequest) -> Result<Value, RpcError> {
+        match request.method.as_str() {
+            "initialize" => {
+                self.initialized = true;
+                Ok(json!({
+                    "capabilities": {
+                        "hoverProvider": true,
+                        "codeLensProvider": { "resolveProvider": false },
+                    },
+                    "serverInfo": { "name": "tidemark", "version": env!("CARGO_PKG_VERSION") },
+                }))
+            }
+            _ if !self.initialized => Err(error(SERVER_NOT_INITIALIZED, "server not initialized")),
+            "shutdown" => {
+                self.shutdown = true;
+                Ok(Value::Null)
+            }
+            _ if self.shutdown => Err(error(INVALID_REQUEST, "server is shutting down")),
+            "textDocument/hover" => Ok(match self.file_for(&request.params)? {
+                Some(file) => json!({
+                    "contents": { "kind": "markdown", "value": output::format_file_hover(&file) },
+                }),
+                None => Value::Null,
+            }),
+            "textDocument/codeLens" => Ok(match self.file_for(&request.params)? {
+                Some(file) => json!([{
+                    "range": {
+                        "start": { "line": 0, "character": 0 },
+                        "end": { "line": 0, "character": 0 },
+                    },
+                    "command": {
+                        "title": output::format_file_lens(&file),
+                        "command": LENS_COMMAND,
+                        "arguments": [file.path],
+                    },
+                }]),
+                None => json!([]),
+            }),
+            // Other notifications (`initialized`, `textDocument/didOpen`, ...) need no action.
+            _ if request.id.is_null() => Ok(Value::Null),
+            other => Err(error(METHOD_NOT_FOUND, format!("unknown method `{other}`"))),
+        }
+    }
+
+    /// `None` for documents outside a repository or without committed history.
+    fn file_for(&mut self, params: &Value) -> Result<Option<FileResult>, RpcError> {
+        let uri = params["textDocument"]["uri"]
+            .as_str()
+            .ok_or_else(|| error(INVALID_PARAMS, "missing textDocument.uri"))?;
+        let Some(path) = uri_to_path(uri) else {
+            return Ok(None);
+        };
+        // Match the canonical repository root that git reports.
+        let path = fs::canonicalize(&path).unwrap_or(path);
+        let resolver = match self.resolver_for(path.as_path()) {
+            Ok(resolver) => resolver,
+            Err(TideError::NotGitRepository { .. }) => return Ok(None),
+            Err(err) => return Err(error(REQUEST_FAILED, err)),
+        };
+        match resolver.file(path.as_path()) {
+            Ok(file) => Ok(Some(file)),
+            Err(TideError::FileHistoryNotFound { .. }) => Ok(None),
+            Err(err) => Err(error(REQUEST_FAILED, err)),
+        }
+    }
+
+    /// The resolver of the innermost work tree holding `path`, so files of nested repositories
+    /// and submodules never resolve against an enclosing repository.
+    fn resolver_for(&mut self, path: &Path) -> TideResult<&Resolver> {
+        // `.git` is a directory in a repository and a file in submodules and linked worktrees.
+        let work_tree = path
+            .ancestors()
+            .skip(1)
+            .find(|dir| dir.join(".git").exists());
+        if let Some(pos) = self
+            .resolvers
+            .iter()
+            .position(|resolver| Some(resolver.repo_root()) == work_tree)
+        {
+            return Ok(&self.resolvers[pos]);
+        }
+        let start = path.parent().unwrap_or(path);
+        let resolver = self
+            .overrides
+            .iter()
+            .fold(Resolver::builder().repo(start), |builder, (key, value)| {
+                builder.config_override(key.as_str(), value.as_str())
+            })
+            .local_only(true)
+            .build()?;
+        // The `.git` search can miss roots git still finds (e.g. `core.worktree`); keep one each.
+        if let Some(pos) = self
+            .resolvers
+            .iter()
+            .position(|known| known.repo_root() == resolver.repo_root())
+        {
+            return Ok(&self.resolvers[pos]);
+        }
+        self.resolvers.push(resolver);
+        Ok(self.resolvers.last().expect("resolver just pushed"))
+    }
+}
+
+fn error(code: i64, message: impl ToString) -> RpcError {
+    RpcError {
+        code,
+        message: message.to_string(),
+        data: None,
+    }
+}
+
+fn reply(id: Value, outcome: Result<Value, RpcError>) -> RpcResponse {
+    let (result, error) = match outcome {
+        Ok(result) => (Some(result), None),
+        Err(error) => (None, Some(error)),
+    };
+    RpcResponse {
+        jsonrpc: JSONRPC_VERSION.to_string(),
+        id,
+        result,
+        error,
+    }
+}
+
+/// One `Content-Length`-framed message body; `None` at end of input.
+fn read_message(input: &mut impl BufRead) -> TideResult<Option<Vec<u8>>> {
+    let mut length = None;
+    loop {
+        let mut line = String::new();
+        if input
+            .read_line(&mut line)
+            .map_err(|err| io_err("stdin", err))?
+            == 0
+        {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            if length.is_some() {
+                break;
+            }
+            continue;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            length = value.trim().parse::<usize>().ok();
+        }
+    }
+    let mut body = vec![0u8; length.unwrap_or_default()];
+    input
+        .read_exact(&mut body)
+        .map_err(|err| io_err("stdin", err))?;
+    Ok(Some(body))
+}
+
+fn write_message(output: &mut impl Write, response: &RpcResponse) -> TideResult<()> {
+    let body = serde_json::to_vec(response).map_err(|err| TideError::Internal {
+        message: err.to_string(),
+    })?;
+    output
+        .write_all(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes())
+        .and_then(|()| output.write_all(&body))
+        .and_then(|()| output.flush())
+        .map_err(|err| io_err("stdout", err))
+}
+
+/// Local path of a `file:` URI, percent-decoded; `None` for other schemes and remote hosts.
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let rest = uri.strip_prefix("file://")?;
+    let path = rest.strip_prefix("localhost").unwrap_or(rest);
+    if !path.starts_with('/') {
+        return None;
+    }
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = path.get(i + 1..i + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    Some(PathBuf::from(String::from_utf8(decoded).ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_uris_decode_to_local_paths() {
+        assert_eq!(
+            uri_to_path("file:///home/dev/my%20repo/src/lib.rs"),
+            Some(PathBuf::from("/home/dev/my repo/src/lib.rs"))
+        );
+        assert_eq!(
+            uri_to_path("file://localhost/tmp/a.rs"),
+            Some(PathBuf::from("/tmp/a.rs"))
+        );
+        assert_eq!(uri_to_path("untitled:Untitled-1"), None);
+        assert_eq!(uri_to_path("file://server/share/a.rs"), None);
+        assert_eq!(uri_to_path("file:///bad%2"), None);
+    }
+}
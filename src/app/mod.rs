@@ -14,153 +14,498 @@
 //! Email: silan.hu@u.nus.edu
 //! Copyright (c) 2026-2027 easynet. All rights reserved.
 
+pub mod daemon;
+pub mod lsp;
+
 use std::{
     env,
     io::{self, Write},
+    path::{Path, PathBuf},
+    time::Duration,
 };
 
+use serde::{Serialize, de::DeserializeOwned};
+
+use self::daemon::{
+    ClientContext, FileList, FilesParams, LocateParams, MarkParams, ReleasesParams,
+};
 use crate::{
-    config,
-    core::{
-        release,
-        resolver::{
-            file::{FileRequest, resolve_file},
-            mark::{MarkRequest, resolve_mark},
-        },
-    },
-    error::{TideResult, io_err},
+    api::Resolver,
+    config::{self, ConflictPolicy, TideConfig},
+    core::{model::TagConflict, resolver::file::tag_conflicts},
+    error::{TideError, TideResult, io_err},
     infra::{
         cache::CacheStore,
         git::{GitProvider, cli::GitCli},
     },
     interface::{
-        cli::{Cli, Commands, ConfigSubcommand, ReleaseSubcommand, ServiceSubcommand},
+        cli::{
+            CacheSubcommand, Cli, Commands, ConfigSubcommand, GenerateLang, MarkArgs,
+            ReleaseSubcommand, ServiceSubcommand,
+        },
         output,
     },
-    ops::service::{self, ServiceInstallRequest, ServiceUninstallRequest},
+    ops::{
+        cache::{self, PruneRequest},
+        generate::{self, BuildInfo, TargetLang},
+        service::{self, ServiceInstallRequest, ServiceUninstallRequest},
+        stamp::{self, StampRequest, StampStatus},
+    },
 };
 
 pub fn run(cli: Cli) -> TideResult<()> {
     let cwd = env::current_dir().map_err(|err| io_err(".", err))?;
-    let git = GitCli::discover(cwd.as_path())?;
+    let overrides = cli
+        .overrides
+        .iter()
+        .map(|raw| config::layer::parse_override(raw))
+        .collect::<TideResult<Vec<_>>>()?;
 
-    let command = cli.command.unwrap_or(Commands::Mark(crate::interface::cli::MarkArgs {
+    let no_daemon = cli.no_daemon;
+    let command = cli.command.unwrap_or(Commands::Mark(MarkArgs {
         explain: false,
         local_only: true,
+        require_remote: false,
         metadata_suffix: None,
     }));
 
     match command {
         Commands::Config(config_cmd) => match config_cmd.command {
             ConfigSubcommand::Init => {
-                let path = config::init_default(git.repo_root())?;
+                let path = config::init_default(repo_root(cwd.as_path())?.as_path())?;
                 write_stdout(format!("{}\n", path.display()).as_str())
             }
+            ConfigSubcommand::Show(args) => {
+                let root = repo_root(cwd.as_path())?;
+                let layered = config::layer::load_layered(root.as_path(), &overrides)?;
+                write_stdout(output::format_config(&layered.entries()?, args.origin).as_str())
+            }
+            ConfigSubcommand::Migrate => {
+                let repo_file = repo_root(cwd.as_path())?.join(config::CONFIG_FILE_NAME);
+                let files: Vec<PathBuf> =
+                    [config::layer::global_config_path(), Some(repo_file.clone())]
+                        .into_iter()
+                        .flatten()
+                        .filter(|path| path.exists())
+                        .collect();
+                if files.is_empty() {
+                    return Err(TideError::Io {
+                        path: repo_file,
+                        source: io::Error::from(io::ErrorKind::NotFound),
+                    });
+                }
+                // Environment and `-c` layers hold single keys and have nothing to rewrite.
+                for path in files {
+                    let outcome = config::migrate::migrate_file(path.as_path())?;
+                    write_stdout(output::format_migration(&outcome).as_str())?;
+                }
+                Ok(())
+            }
+            ConfigSubcommand::Validate(args) => {
+                let git = GitCli::discover(cwd.as_path())?;
+                let report = config::validate::validate(&git, &overrides, args.local_only)?;
+                write_stdout(output::format_validation(&report, args.json).as_str())?;
+                if report.is_valid() {
+                    Ok(())
+                } else {
+                    Err(TideError::ConfigInvalid {
+                        errors: report.error_count(),
+                    })
+                }
+            }
         },
 
+        Commands::Cache(cache_cmd) => {
+            let resolver = open(cwd, &overrides, true, false, None)?;
+            let store = CacheStore::from_config(
+                resolver.git().git_dir()?.as_path(),
+                &resolver.config().cache,
+            )?;
+            match cache_cmd.command {
+                CacheSubcommand::Stats => {
+                    write_stdout(output::format_cache_stats(&cache::stats(&store)?).as_str())
+                }
+                CacheSubcommand::Prune(args) => {
+                    let removed = cache::prune(
+                        &store,
+                        resolver.git(),
+                        &PruneRequest {
+                            older_than: args
+                                .older_than_days
+                                .map(|days| Duration::from_secs(u64::from(days) * 24 * 60 * 60)),
+                            unreachable: args.unreachable,
+                        },
+                    )?;
+                    write_stdout(output::format_cache_removed(&removed).as_str())
+                }
+                CacheSubcommand::Clear(args) => {
+                    let removed = cache::clear(&store, args.namespace.as_deref())?;
+                    write_stdout(output::format_cache_removed(&removed).as_str())
+                }
+                CacheSubcommand::Verify => {
+                    let problems = cache::verify(&store)?;
+                    write_stdout(output::format_cache_problems(&problems).as_str())?;
+                    if problems.is_empty() {
+                        Ok(())
+                    } else {
+                        Err(TideError::CacheCorrupt {
+                            entries: problems.len(),
+                        })
+                    }
+                }
+            }
+        }
+
         Commands::Mark(mark_args) => {
-            let cfg = config::load_or_default(git.repo_root())?;
-            let cache = CacheStore::new(git.git_dir()?.as_path(), cfg.cache.enabled);
-            let result = resolve_mark(
-                &git,
-                &cfg,
-                &cache,
-                MarkRequest {
-                    target_rev: None,
+            let daemon = DaemonCall::new(cwd.as_path(), no_daemon, &overrides);
+            let asked = daemon.ask("mark", mark_args.metadata_suffix.as_deref(), |context| {
+                MarkParams {
+                    rev: None,
                     local_only: mark_args.local_only,
-                    metadata_suffix: mark_args.metadata_suffix,
-                },
-            )?;
+                    require_remote: mark_args.require_remote,
+                    metadata_suffix: mark_args.metadata_suffix.clone(),
+                    context: Some(context),
+                }
+            })?;
+            let result = match asked {
+                Some(result) => result,
+                None => {
+                    let resolver = open(
+                        cwd,
+                        &overrides,
+                        mark_args.local_only,
+                        mark_args.require_remote,
+                        mark_args.metadata_suffix,
+                    )?;
+                    let result = resolver.mark(None)?;
+                    warn_tag_conflicts(resolver.config(), &result.explain.tag_conflicts)?;
+                    result
+                }
+            };
             write_stdout(output::format_mark(&result, mark_args.explain).as_str())
         }
 
         Commands::File(file_args) => {
-            let cfg = config::load_or_default(git.repo_root())?;
-            let cache = CacheStore::new(git.git_dir()?.as_path(), cfg.cache.enabled);
-            let result = resolve_file(
-                &git,
-                &cfg,
-                &cache,
-                FileRequest {
-                    path: file_args.path,
+            let mut patterns = file_args.paths.clone();
+            if file_args.stdin {
+                for line in io::stdin().lines() {
+                    let line = line.map_err(|err| io_err("stdin", err))?;
+                    if !line.trim().is_empty() {
+                        patterns.push(line.trim().to_string());
+                    }
+                }
+            }
+            let daemon = DaemonCall::new(cwd.as_path(), no_daemon, &overrides);
+            let asked = daemon.ask("files", file_args.metadata_suffix.as_deref(), |context| {
+                FilesParams {
+                    patterns: patterns.clone(),
                     local_only: file_args.local_only,
-                    metadata_suffix: file_args.metadata_suffix,
+                    require_remote: file_args.require_remote,
+                    metadata_suffix: file_args.metadata_suffix.clone(),
+                    context: Some(context),
+                }
+            })?;
+            let files = match asked.map(|list: FileList| list.files) {
+                Some(files) => files,
+                None => {
+                    let resolver = open(
+                        cwd,
+                        &overrides,
+                        file_args.local_only,
+                        file_args.require_remote,
+                        file_args.metadata_suffix,
+                    )?;
+                    let files = resolver.files(&patterns)?;
+                    warn_tag_conflicts(resolver.config(), &tag_conflicts(&files))?;
+                    files
+                }
+            };
+            // A single plain file keeps the bare-coordinate output scripts rely on.
+            match (patterns.as_slice(), files.as_slice()) {
+                ([pattern], [file])
+                    if !file_args.stdin
+                        && !file_args.json
+                        && !pattern.contains(['*', '?', '['])
+                        && Path::new(pattern).ends_with(file.path.as_str()) =>
+                {
+                    write_stdout(output::format_file(file).as_str())
+                }
+                _ => write_stdout(output::format_files(&files, file_args.json).as_str()),
+            }
+        }
+
+        Commands::Locate(locate_args) => {
+            let daemon = DaemonCall::new(cwd.as_path(), no_daemon, &overrides);
+            let asked = daemon.ask("locate", None, |context| LocateParams {
+                coordinate: locate_args.coordinate.clone(),
+                from: locate_args.from.clone(),
+                local_only: locate_args.local_only,
+                require_remote: locate_args.require_remote,
+                context: Some(context),
+            })?;
+            let result = match asked {
+                Some(result) => result,
+                None => open(
+                    cwd,
+                    &overrides,
+                    locate_args.local_only,
+                    locate_args.require_remote,
+                    None,
+                )?
+                .locate(locate_args.coordinate.as_str(), locate_args.from.as_deref())?,
+            };
+            write_stdout(output::format_locate(&result).as_str())
+        }
+
+        Commands::Stamp(stamp_args) => {
+            let resolver = open(
+                cwd,
+                &overrides,
+                stamp_args.local_only,
+                stamp_args.require_remote,
+                stamp_args.metadata_suffix,
+            )?;
+            let result = resolver.mark(None)?;
+            warn_tag_conflicts(resolver.config(), &result.explain.tag_conflicts)?;
+            let outcomes = stamp::stamp_manifests(
+                &StampRequest {
+                    repo_root: resolver.repo_root().to_path_buf(),
+                    targets: stamp_args.into,
+                    check: stamp_args.check,
                 },
+                &result.coordinate,
             )?;
-            write_stdout(output::format_file(&result).as_str())
+            write_stdout(output::format_stamp(&outcomes).as_str())?;
+
+            let stale: Vec<String> = outcomes
+                .iter()
+                .filter(|outcome| outcome.status == StampStatus::Stale)
+                .map(|outcome| outcome.path.display().to_string())
+                .collect();
+            if stale.is_empty() {
+                Ok(())
+            } else {
+                Err(TideError::ManifestOutOfDate { paths: stale })
+            }
+        }
+
+        Commands::Generate(generate_args) => {
+            let resolver = open(
+                cwd,
+                &overrides,
+                generate_args.local_only,
+                generate_args.require_remote,
+                generate_args.metadata_suffix,
+            )?;
+            let result = resolver.mark(None)?;
+            warn_tag_conflicts(resolver.config(), &result.explain.tag_conflicts)?;
+            let info = BuildInfo::from_mark(&result, resolver.git().is_dirty()?);
+            let lang = to_target_lang(generate_args.lang);
+            let content = generate::render(lang, &info, generate_args.package.as_str());
+            match generate_args.out {
+                Some(path) => {
+                    let written = generate::write_if_changed(path.as_path(), content.as_str())?;
+                    write_stdout(
+                        format!(
+                            "{}\t{}\n",
+                            path.display(),
+                            if written { "updated" } else { "unchanged" }
+                        )
+                        .as_str(),
+                    )
+                }
+                None => write_stdout(content.as_str()),
+            }
         }
 
         Commands::Release(release_cmd) => match release_cmd.command {
             ReleaseSubcommand::List(args) => {
-                let cfg = config::load_or_default(git.repo_root())?;
-                let (releases, _remote_status) =
-                    release::load_release_tags(&git, &cfg, args.local_only)?;
-                write_stdout(output::format_release_list(&releases).as_str())
+                let daemon = DaemonCall::new(cwd.as_path(), no_daemon, &overrides);
+                let asked = daemon.ask("releases", None, |context| ReleasesParams {
+                    local_only: args.local_only,
+                    require_remote: args.require_remote,
+                    context: Some(context),
+                })?;
+                let inventory = match asked {
+                    Some(inventory) => inventory,
+                    None => {
+                        let resolver =
+                            open(cwd, &overrides, args.local_only, args.require_remote, None)?;
+                        let inventory = resolver.releases()?;
+                        warn_tag_conflicts(resolver.config(), &inventory.conflicts)?;
+                        inventory
+                    }
+                };
+                write_stdout(output::format_release_list(&inventory, args.json).as_str())
             }
         },
 
-        Commands::Service(service_cmd) => match service_cmd.command {
-            ServiceSubcommand::Install(args) => {
-                let plan = service::install_user_service(&to_install_request(
-                    git.repo_root(),
-                    args.interval_minutes,
-                    args.unit_name,
-                    args.allow_remote,
-                    args.compact,
-                    args.metadata_suffix,
-                ))?;
-                write_stdout(
-                    format!(
-                        "unit_name={}\nservice_file={}\ntimer_file={}\n",
-                        plan.unit_name,
-                        plan.service_file.display(),
-                        plan.timer_file.display()
+        Commands::Serve(serve_args) => serve(cwd, overrides, serve_args.socket),
+
+        Commands::Lsp => lsp::serve(io::stdin().lock(), io::stdout().lock(), &overrides),
+
+        Commands::Service(service_cmd) => {
+            let root = repo_root(cwd.as_path())?;
+            match service_cmd.command {
+                ServiceSubcommand::Install(args) => {
+                    let plan = service::install_user_service(&to_install_request(
+                        root.as_path(),
+                        args.interval_minutes,
+                        args.unit_name,
+                        args.allow_remote,
+                        args.compact,
+                        args.metadata_suffix,
+                    ))?;
+                    write_stdout(
+                        format!(
+                            "unit_name={}\nservice_file={}\ntimer_file={}\n",
+                            plan.unit_name,
+                            plan.service_file.display(),
+                            plan.timer_file.display()
+                        )
+                        .as_str(),
                     )
-                    .as_str(),
-                )
-            }
-            ServiceSubcommand::Uninstall(args) => {
-                let plan = service::uninstall_user_service(&ServiceUninstallRequest {
-                    repo_root: git.repo_root().to_path_buf(),
-                    unit_name: args.unit_name,
-                })?;
-                write_stdout(
-                    format!(
-                        "unit_name={}\nservice_file={}\ntimer_file={}\n",
-                        plan.unit_name,
-                        plan.service_file.display(),
-                        plan.timer_file.display()
+                }
+                ServiceSubcommand::Uninstall(args) => {
+                    let plan = service::uninstall_user_service(&ServiceUninstallRequest {
+                        repo_root: root,
+                        unit_name: args.unit_name,
+                    })?;
+                    write_stdout(
+                        format!(
+                            "unit_name={}\nservice_file={}\ntimer_file={}\n",
+                            plan.unit_name,
+                            plan.service_file.display(),
+                            plan.timer_file.display()
+                        )
+                        .as_str(),
                     )
-                    .as_str(),
-                )
-            }
-            ServiceSubcommand::Plan(args) => {
-                let plan = service::plan_service(&to_install_request(
-                    git.repo_root(),
-                    args.interval_minutes,
-                    args.unit_name,
-                    args.allow_remote,
-                    args.compact,
-                    args.metadata_suffix,
-                ))?;
-                write_stdout(
-                    format!(
-                        "unit_name={}\nservice_file={}\ntimer_file={}\n---service---\n{}---timer---\n{}",
-                        plan.unit_name,
-                        plan.service_file.display(),
-                        plan.timer_file.display(),
-                        plan.service_content,
-                        plan.timer_content,
+                }
+                ServiceSubcommand::Plan(args) => {
+                    let plan = service::plan_service(&to_install_request(
+                        root.as_path(),
+                        args.interval_minutes,
+                        args.unit_name,
+                        args.allow_remote,
+                        args.compact,
+                        args.metadata_suffix,
+                    ))?;
+                    write_stdout(
+                        format!(
+                            "unit_name={}\nservice_file={}\ntimer_file={}\n---service---\n{}---timer---\n{}",
+                            plan.unit_name,
+                            plan.service_file.display(),
+                            plan.timer_file.display(),
+                            plan.service_content,
+                            plan.timer_content,
+                        )
+                        .as_str(),
                     )
-                    .as_str(),
-                )
+                }
             }
-        },
+        }
+    }
+}
+
+/// One CLI invocation's route to a running `tide serve` daemon.
+#[cfg_attr(not(unix), allow(dead_code))]
+struct DaemonCall<'a> {
+    cwd: &'a Path,
+    no_daemon: bool,
+    overrides: &'a [(String, String)],
+}
+
+impl<'a> DaemonCall<'a> {
+    fn new(cwd: &'a Path, no_daemon: bool, overrides: &'a [(String, String)]) -> Self {
+        Self {
+            cwd,
+            no_daemon,
+            overrides,
+        }
     }
+
+    /// Answer from the daemon, printing its warnings; `None` means no daemon can answer for this
+    /// invocation and the caller resolves in process. `params` receives the client context,
+    /// which is only computed once a trusted daemon is reachable.
+    #[cfg(unix)]
+    fn ask<P: Serialize, T: DeserializeOwned>(
+        &self,
+        method: &str,
+        metadata_suffix: Option<&str>,
+        params: impl FnOnce(ClientContext) -> P,
+    ) -> TideResult<Option<T>> {
+        if self.no_daemon {
+            return Ok(None);
+        }
+        let Some(mut client) = daemon::client::DaemonClient::connect(self.cwd) else {
+            return Ok(None);
+        };
+        let Some(context) = client.context(self.overrides, metadata_suffix) else {
+            return Ok(None);
+        };
+        let Some(served) = client.call::<P, T>(method, &params(context))? else {
+            return Ok(None);
+        };
+        for warning in &served.warnings {
+            write_stderr(warning.as_str())?;
+        }
+        Ok(Some(served.value))
+    }
+
+    #[cfg(not(unix))]
+    fn ask<P: Serialize, T: DeserializeOwned>(
+        &self,
+        _method: &str,
+        _metadata_suffix: Option<&str>,
+        _params: impl FnOnce(ClientContext) -> P,
+    ) -> TideResult<Option<T>> {
+        Ok(None)
+    }
+}
+
+#[cfg(unix)]
+fn serve(cwd: PathBuf, overrides: Vec<(String, String)>, socket: PathBuf) -> TideResult<()> {
+    let daemon = daemon::server::Daemon::bind(daemon::server::ServeRequest {
+        start_dir: cwd,
+        socket,
+        overrides,
+    })?;
+    write_stdout(format!("serving\t{}\n", daemon.socket().display()).as_str())?;
+    io::stdout().flush().map_err(|err| io_err("stdout", err))?;
+    daemon.run()
+}
+
+#[cfg(not(unix))]
+fn serve(_cwd: PathBuf, _overrides: Vec<(String, String)>, _socket: PathBuf) -> TideResult<()> {
+    Err(TideError::UnsupportedPlatform {
+        feature: "tide serve (Unix domain sockets)".to_string(),
+    })
+}
+
+fn open(
+    cwd: PathBuf,
+    overrides: &[(String, String)],
+    local_only: bool,
+    require_remote: bool,
+    metadata_suffix: Option<String>,
+) -> TideResult<Resolver> {
+    overrides
+        .iter()
+        .fold(Resolver::builder().repo(cwd), |builder, (key, value)| {
+            builder.config_override(key.as_str(), value.as_str())
+        })
+        .local_only(local_only)
+        .require_remote(require_remote)
+        .metadata_suffix(metadata_suffix)
+        .build()
+}
+
+fn repo_root(cwd: &Path) -> TideResult<PathBuf> {
+    Ok(GitCli::discover(cwd)?.repo_root().to_path_buf())
 }
 
 fn to_install_request(
-    repo_root: &std::path::Path,
+    repo_root: &Path,
     interval_minutes: u32,
     unit_name: Option<String>,
     allow_remote: bool,
@@ -177,6 +522,33 @@ fn to_install_request(
     }
 }
 
+fn to_target_lang(lang: GenerateLang) -> TargetLang {
+    match lang {
+        GenerateLang::Rust => TargetLang::Rust,
+        GenerateLang::C => TargetLang::C,
+        GenerateLang::Go => TargetLang::Go,
+        GenerateLang::Python => TargetLang::Python,
+        GenerateLang::Ts => TargetLang::TypeScript,
+    }
+}
+
+fn warn_tag_conflicts(cfg: &TideConfig, conflicts: &[TagConflict]) -> TideResult<()> {
+    if cfg.remote.on_conflict != ConflictPolicy::Warn {
+        return Ok(());
+    }
+    for conflict in conflicts {
+        write_stderr(output::format_tag_conflict_warning(conflict).as_str())?;
+    }
+    Ok(())
+}
+
+fn write_stderr(text: &str) -> TideResult<()> {
+    let mut stderr = io::stderr().lock();
+    stderr
+        .write_all(text.as_bytes())
+        .map_err(|err| io_err("stderr", err))
+}
+
 fn write_stdout(text: &str) -> TideResult<()> {
     let mut stdout = io::stdout().lock();
     stdout
@@ -0,0 +1,478 @@
+//! TideMark
+//! ========
+//!
+//! File: src/config/layer.rs
+//! Description: Layered configuration merge across defaults, global file, repo file, environment, and CLI overrides.
+//!
+//! Responsibility:
+//! - Merge configuration layers in a fixed precedence order and record which layer set each effective key.
+//!
+//! Architectural Position:
+//! - Configuration loading boundary beneath `config::load_or_default` and the `Resolver` builder.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+use std::{
+    collections::BTreeMap,
+    env, fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use toml::{Table, Value};
+
+use super::{CONFIG_FILE_NAME, TideConfig, migrate};
+use crate::{
+    core::{suffix::SuffixTemplate, time::TimezonePolicy},
+    error::{TideError, TideResult, io_err},
+    infra::cache::backend::RemoteSpec,
+};
+
+pub const ENV_PREFIX: &str = "TIDEMARK_";
+pub const GLOBAL_CONFIG_DIR: &str = "tidemark";
+pub const GLOBAL_CONFIG_FILE_NAME: &str = "config.toml";
+
+/// Layer that supplied an effective config value; later layers win.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigOrigin {
+    Default,
+    Global(PathBuf),
+    Repo(PathBuf),
+    /// Environment variable name, e.g. `TIDEMARK_REMOTE_STRATEGY`.
+    Env(String),
+    /// `-c key=value` on the command line.
+    Cli,
+}
+
+impl fmt::Display for ConfigOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::Global(path) => write!(f, "global:{}", path.display()),
+            Self::Repo(path) => write!(f, "repo:{}", path.display()),
+            Self::Env(name) => write!(f, "env:{name}"),
+            Self::Cli => write!(f, "cli"),
+        }
+    }
+}
+
+/// Effective config plus the origin of every key set by a non-default layer.
+#[derive(Debug, Clone)]
+pub struct LayeredConfig {
+    pub config: TideConfig,
+    origins: BTreeMap<String, ConfigOrigin>,
+}
+
+/// One effective leaf value, rendered as a TOML literal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigEntry {
+    pub key: String,
+    pub value: String,
+    pub origin: ConfigOrigin,
+}
+
+impl LayeredConfig {
+    /// Every effective value sorted by dotted key, including built-in defaults.
+    pub fn entries(&self) -> TideResult<Vec<ConfigEntry>> {
+        let table = to_table(&self.config)?;
+        let mut leaves = BTreeMap::new();
+        flatten(&table, "", &mut leaves);
+        Ok(leaves
+            .into_iter()
+            .map(|(key, value)| ConfigEntry {
+                origin: self.origin(key.as_str()),
+                value: value.to_string(),
+                key,
+            })
+            .collect())
+    }
+
+    pub fn origin(&self, key: &str) -> ConfigOrigin {
+        self.origins
+            .get(key)
+            .cloned()
+            .unwrap_or(ConfigOrigin::Default)
+    }
+}
+
+/// Loads defaults, `$XDG_CONFIG_HOME/tidemark/config.toml`, `<repo>/.tidemark.toml`,
+/// `TIDEMARK_*` environment variables, then `overrides`, each layer overriding the previous.
+pub fn load_layered(repo_root: &Path, overrides: &[(String, String)]) -> TideResult<LayeredConfig> {
+    merge_layers(
+        global_config_path().as_deref(),
+        repo_root.join(CONFIG_FILE_NAME).as_path(),
+        env::vars(),
+        overrides,
+    )
+}
+
+/// `load_layered` that leaves out every layer failing to parse or validate instead of stopping
+/// at the first, returning the environment and `-c` failures as `(origin, message)`. Failing
+/// files are skipped silently; callers check files on their own.
+pub(super) fn load_layered_skipping_invalid(
+    repo_root: &Path,
+    overrides: &[(String, String)],
+) -> TideResult<(LayeredConfig, Vec<(String, String)>)> {
+    let mut rejected = Vec::new();
+    let layered = merge_layers_with(
+        global_config_path().as_deref(),
+        repo_root.join(CONFIG_FILE_NAME).as_path(),
+        env::vars(),
+        overrides,
+        |err| match err {
+            TideError::InvalidConfigOverride { origin, message } => {
+                rejected.push((origin, message));
+                Ok(())
+            }
+            TideError::ConfigParse { .. } | TideError::UnsupportedConfigSchema { .. } => Ok(()),
+            err => Err(err),
+        },
+    )?;
+    Ok((layered, rejected))
+}
+
+/// Splits a `-c key=value` argument into its dotted key and raw value.
+pub fn parse_override(raw: &str) -> TideResult<(String, String)> {
+    let invalid = |message: &str| TideError::InvalidConfigOverride {
+        origin: format!("-c {raw}"),
+        message: message.to_string(),
+    };
+    let (key, value) = raw
+        .split_once('=')
+        .ok_or_else(|| invalid("expected key=value"))?;
+    let key = key.trim();
+    if key.split('.').count() < 2 || key.split('.').any(str::is_empty) {
+        return Err(invalid("key must be a dotted path such as remote.strategy"));
+    }
+    Ok((key.to_string(), value.trim().to_string()))
+}
+
+pub(crate) fn global_config_path() -> Option<PathBuf> {
+    let base = match env::var_os("XDG_CONFIG_HOME").filter(|value| !value.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    Some(base.join(GLOBAL_CONFIG_DIR).join(GLOBAL_CONFIG_FILE_NAME))
+}
+
+fn merge_layers(
+    global: Option<&Path>,
+    repo: &Path,
+    vars: impl IntoIterator<Item = (String, String)>,
+    overrides: &[(String, String)],
+) -> TideResult<LayeredConfig> {
+    merge_layers_with(global, repo, vars, overrides, Err)
+}
+
+/// Merges every layer; a layer that fails goes to `rejected`, which either stops the merge by
+/// returning the error or lets it continue without that layer.
+fn merge_layers_with(
+    global: Option<&Path>,
+    repo: &Path,
+    vars: impl IntoIterator<Item = (String, String)>,
+    overrides: &[(String, String)],
+    mut rejected: impl FnMut(TideError) -> TideResult<()>,
+) -> TideResult<LayeredConfig> {
+    let mut merged = Table::new();
+    let mut origins = BTreeMap::new();
+
+    for (path, origin) in [
+        global.map(|path| (path, ConfigOrigin::Global(path.to_path_buf()))),
+        Some((repo, ConfigOrigin::Repo(repo.to_path_buf()))),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        match read_file_layer(path) {
+            Ok(Some(layer)) => apply_layer(&mut merged, &mut origins, layer, origin),
+            Ok(None) => {}
+            Err(err) => rejected(err)?,
+        }
+    }
+
+    let mut env_layers: Vec<(String, String, String)> = vars
+        .into_iter()
+        .filter_map(|(name, value)| {
+            let key = env_key(name.as_str())?;
+            Some((name, key, value))
+        })
+        .collect();
+    env_layers.sort();
+    for (name, key, value) in env_layers {
+        let origin = ConfigOrigin::Env(name.clone());
+        match value_layer(key.as_str(), value.as_str(), name.as_str()) {
+            Ok(layer) => apply_layer(&mut merged, &mut origins, layer, origin),
+            Err(err) => rejected(err)?,
+        }
+    }
+
+    for (key, value) in overrides {
+        let label = format!("-c {key}={value}");
+        match value_layer(key.as_str(), value.as_str(), label.as_str()) {
+            Ok(layer) => apply_layer(&mut merged, &mut origins, layer, ConfigOrigin::Cli),
+            Err(err) => rejected(err)?,
+        }
+    }
+
+    let config = Value::Table(merged)
+        .try_into::<TideConfig>()
+        .map_err(|err| TideError::Internal {
+            message: format!("merged config failed to deserialize: {err}"),
+        })?;
+    Ok(LayeredConfig { config, origins })
+}
+
+fn read_file_layer(path: &Path) -> TideResult<Option<Table>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let raw = fs::read_to_string(path).map_err(|err| io_err(path, err))?;
+    let parse_err = |message: String| TideError::ConfigParse {
+        path: path.to_path_buf(),
+        message,
+    };
+    let (doc, _) = migrate::upgrade(raw.as_str(), path)?;
+    let table = doc
+        .to_string()
+        .parse::<Table>()
+        .map_err(|err| parse_err(err.to_string()))?;
+    validate_layer(&table).map_err(parse_err)?;
+    Ok(Some(table))
+}
+
+/// Builds a single-key layer; values that do not parse as TOML are taken as bare strings.
+fn value_layer(key: &str, raw: &str, origin: &str) -> TideResult<Table> {
+    let value = format!("value = {raw}")
+        .parse::<Table>()
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| Value::String(raw.to_string()));
+
+    let segments: Vec<&str> = key.split('.').collect();
+    let layer = nest(&segments, value);
+    validate_layer(&layer).map_err(|message| TideError::InvalidConfigOverride {
+        origin: origin.to_string(),
+        message,
+    })?;
+    Ok(layer)
+}
+
+/// Wraps `value` in one table per segment of the dotted `path`.
+pub(super) fn nest<S: AsRef<str>>(path: &[S], value: Value) -> Table {
+    let mut layer = Table::new();
+    let Some((leaf, parents)) = path.split_last() else {
+        return layer;
+    };
+    layer.insert(leaf.as_ref().to_string(), value);
+    for segment in parents.iter().rev() {
+        let mut parent = Table::new();
+        parent.insert(segment.as_ref().to_string(), Value::Table(layer));
+        layer = parent;
+    }
+    layer
+}
+
+/// Type-checks one layer on its own so errors name the layer that caused them.
+pub(super) fn validate_layer(layer: &Table) -> Result<(), String> {
+    let config = Value::Table(layer.clone())
+        .try_into::<TideConfig>()
+        .map_err(|err| err.message().trim().to_string())?;
+    TimezonePolicy::parse(config.time.timezone.as_str()).map_err(|err| err.to_string())?;
+    if let Some(template) = config.output.metadata_suffix.as_deref() {
+        SuffixTemplate::parse(template.trim()).map_err(|err| err.to_string())?;
+    }
+    if let Some(remote) = config
+        .cache
+        .remote
+        .as_deref()
+        .filter(|raw| !raw.trim().is_empty())
+    {
+        RemoteSpec::parse(remote).map_err(|err| err.to_string())?;
+    }
+    Ok(())
+}
+
+fn apply_layer(
+    merged: &mut Table,
+    origins: &mut BTreeMap<String, ConfigOrigin>,
+    layer: Table,
+    origin: ConfigOrigin,
+) {
+    let mut leaves = BTreeMap::new();
+    flatten(&layer, "", &mut leaves);
+    for key in leaves.into_keys() {
+        origins.insert(key, origin.clone());
+    }
+    deep_merge(merged, layer);
+}
+
+fn deep_merge(base: &mut Table, layer: Table) {
+    for (key, value) in layer {
+        match (base.get_mut(key.as_str()), value) {
+            (Some(Value::Table(existing)), Value::Table(incoming)) => {
+                deep_merge(existing, incoming);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn flatten(table: &Table, prefix: &str, out: &mut BTreeMap<String, Value>) {
+    for (key, value) in table {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match value {
+            Value::Table(inner) => flatten(inner, path.as_str(), out),
+            leaf => {
+                out.insert(path, leaf.clone());
+            }
+        }
+    }
+}
+
+/// Maps `TIDEMARK_<SECTION>_<KEY>` to `section.key`; variables naming no config key (such as
+/// the wrappers' `TIDEMARK_CACHE_DIR`) are ignored.
+fn env_key(name: &str) -> Option<String> {
+    let rest = name.strip_prefix(ENV_PREFIX)?.to_ascii_lowercase();
+    let (section, key) = rest.split_once('_')?;
+    if key.is_empty() {
+        return None;
+    }
+    let key = format!("{section}.{key}");
+    is_schema_key(key.as_str()).then_some(key)
+}
+
+/// Whether `key` names a config field, probed with a placeholder value so optional keys
+/// absent from the serialized defaults still count.
+fn is_schema_key(key: &str) -> bool {
+    let segments: Vec<&str> = key.split('.').collect();
+    match Value::Table(nest(&segments, Value::Boolean(false))).try_into::<TideConfig>() {
+        Ok(_) => true,
+        Err(err) => !err.message().contains("unknown field"),
+    }
+}
+
+fn to_table(config: &TideConfig) -> TideResult<Table> {
+    match Value::try_from(config) {
+        Ok(Value::Table(table)) => Ok(table),
+        Ok(_) => Err(TideError::Internal {
+            message: "config did not serialize to a table".to_string(),
+        }),
+        Err(err) => Err(TideError::Internal {
+            message: format!("config failed to serialize: {err}"),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RemoteStrategy;
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn later_layers_override_earlier_ones() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let global = dir.path().join("global.toml");
+        let repo = dir.path().join(CONFIG_FILE_NAME);
+        fs::write(
+            &global,
+            "[release]\ntag_prefix = \"rel-\"\n[remote]\nname = \"up\"\n",
+        )
+        .expect("write global");
+        fs::write(&repo, "[remote]\nname = \"origin\"\ndeepen = true\n").expect("write repo");
+
+        let layered = merge_layers(
+            Some(global.as_path()),
+            repo.as_path(),
+            vars(&[
+                ("TIDEMARK_REMOTE_STRATEGY", "local-only"),
+                ("TIDEMARK_REMOTE_DEEPEN_STEP", "50"),
+                ("TIDEMARK_VERSION", "ignored"),
+            ]),
+            &[("remote.deepen_step".to_string(), "25".to_string())],
+        )
+        .expect("merge layers");
+
+        let cfg = &layered.config;
+        assert_eq!(cfg.release.tag_prefix, "rel-");
+        assert_eq!(cfg.remote.name, "origin");
+        assert!(cfg.remote.deepen);
+        assert_eq!(cfg.remote.strategy, RemoteStrategy::LocalOnly);
+        assert_eq!(cfg.remote.deepen_step, 25);
+        assert_eq!(
+            layered.origin("release.tag_prefix"),
+            ConfigOrigin::Global(global)
+        );
+        assert_eq!(layered.origin("remote.name"), ConfigOrigin::Repo(repo));
+        assert_eq!(
+            layered.origin("remote.strategy"),
+            ConfigOrigin::Env("TIDEMARK_REMOTE_STRATEGY".to_string())
+        );
+        assert_eq!(layered.origin("remote.deepen_step"), ConfigOrigin::Cli);
+        assert_eq!(layered.origin("time.timezone"), ConfigOrigin::Default);
+    }
+
+    #[test]
+    fn env_vars_naming_no_config_key_are_ignored() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let repo = dir.path().join(CONFIG_FILE_NAME);
+        let layered = merge_layers(
+            None,
+            repo.as_path(),
+            vars(&[
+                ("TIDEMARK_CACHE_DIR", "/tmp/tidemark-cache"),
+                ("TIDEMARK_CACHE_MAX_BYTES", "4096"),
+                ("TIDEMARK_OUTPUT_METADATA_SUFFIX", "{short_sha}"),
+            ]),
+            &[],
+        )
+        .expect("merge layers");
+
+        assert_eq!(layered.config.cache.max_bytes, Some(4096));
+        assert_eq!(
+            layered.config.output.metadata_suffix.as_deref(),
+            Some("{short_sha}")
+        );
+        assert_eq!(layered.origin("cache.dir"), ConfigOrigin::Default);
+    }
+
+    #[test]
+    fn invalid_override_names_its_layer() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let repo = dir.path().join(CONFIG_FILE_NAME);
+        let err = merge_layers(
+            None,
+            repo.as_path(),
+            vars(&[("TIDEMARK_REMOTE_STRATEGY", "sometimes")]),
+            &[],
+        )
+        .expect_err("invalid strategy");
+        assert!(
+            matches!(&err, TideError::InvalidConfigOverride { origin, .. } if origin == "TIDEMARK_REMOTE_STRATEGY")
+        );
+    }
+
+    #[test]
+    fn parse_override_requires_dotted_key() {
+        assert_eq!(
+            parse_override("remote.strategy=local-only").expect("parse"),
+            ("remote.strategy".to_string(), "local-only".to_string())
+        );
+        assert!(parse_override("remote.strategy").is_err());
+        assert!(parse_override("strategy=local-only").is_err());
+        assert!(parse_override("remote..strategy=x").is_err());
+    }
+}
@@ -0,0 +1,281 @@
+//! TideMark
+//! ========
+//!
+//! File: src/config/migrate.rs
+//! Description: Config schema detection and format-preserving migrations to the current schema.
+//!
+//! Responsibility:
+//! - Upgrade older config documents step by step while keeping comments and layout intact.
+//! - Rewrite migrated files atomically so an interrupted migration never leaves a partial file.
+//!
+//! Architectural Position:
+//! - Configuration compatibility boundary used by layered loading and `tide config migrate`.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use toml_edit::{DocumentMut, Item, Key, Table, TableLike, value};
+
+use super::CURRENT_SCHEMA;
+use crate::error::{TideError, TideResult, io_err};
+
+type Migration = fn(&mut DocumentMut);
+
+/// `MIGRATIONS[n]` upgrades schema `n` to `n + 1`; `upgrade` records the new `schema` after each
+/// step. Steps reshape documents with `rename_key` and `remove_key`.
+const MIGRATIONS: [Migration; CURRENT_SCHEMA as usize] = [v0_to_v1];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationOutcome {
+    pub path: PathBuf,
+    pub from: u32,
+    pub to: u32,
+    pub changed: bool,
+}
+
+/// Schema version declared by `doc`; a missing `schema` key means the unversioned shape (0).
+pub fn schema_of(doc: &DocumentMut, path: &Path) -> TideResult<u32> {
+    schema_within(doc, path, CURRENT_SCHEMA)
+}
+
+fn schema_within(doc: &DocumentMut, path: &Path, supported: u32) -> TideResult<u32> {
+    let Some(item) = doc.get("schema") else {
+        return Ok(0);
+    };
+    let found = item
+        .as_integer()
+        .and_then(|raw| u32::try_from(raw).ok())
+        .ok_or_else(|| TideError::ConfigParse {
+            path: path.to_path_buf(),
+            message: format!(
+                "`schema` must be a non-negative integer, got {}",
+                item_repr(item)
+            ),
+        })?;
+    if found > supported {
+        return Err(TideError::UnsupportedConfigSchema {
+            path: path.to_path_buf(),
+            found,
+            supported,
+        });
+    }
+    Ok(found)
+}
+
+/// Parses `raw` and applies every pending migration in memory.
+pub fn upgrade(raw: &str, path: &Path) -> TideResult<(DocumentMut, u32)> {
+    upgrade_with(raw, path, &MIGRATIONS)
+}
+
+fn upgrade_with(
+    raw: &str,
+    path: &Path,
+    migrations: &[Migration],
+) -> TideResult<(DocumentMut, u32)> {
+    let mut doc = raw
+        .parse::<DocumentMut>()
+        .map_err(|err| TideError::ConfigParse {
+            path: path.to_path_buf(),
+            message: err.message().trim().to_string(),
+        })?;
+    let supported = migrations.len() as u32;
+    let from = schema_within(&doc, path, supported)?;
+    for (step, migration) in (from..).zip(&migrations[from as usize..]) {
+        migration(&mut doc);
+        set_schema(&mut doc, step + 1);
+    }
+    Ok((doc, from))
+}
+
+/// Rewrites `path` at the current schema; the file is left untouched when already current.
+///
+/// The new content goes to a temporary file next to `path` that is then renamed over it, so
+/// readers see either the old or the new file and never a partial write.
+pub fn migrate_file(path: &Path) -> TideResult<MigrationOutcome> {
+    let raw = fs::read_to_string(path).map_err(|err| io_err(path, err))?;
+    let (doc, from) = upgrade(raw.as_str(), path)?;
+    let changed = from != CURRENT_SCHEMA;
+    if changed {
+        write_atomically(path, doc.to_string().as_str())?;
+    }
+    Ok(MigrationOutcome {
+        path: path.to_path_buf(),
+        from,
+        to: CURRENT_SCHEMA,
+        changed,
+    })
+}
+
+fn write_atomically(path: &Path, contents: &str) -> TideResult<()> {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let tmp = path.with_file_name(format!(".{name}.{}.tmp", process::id()));
+    let written = fs::write(&tmp, contents)
+        .and_then(|()| fs::metadata(path))
+        .and_then(|meta| fs::set_permissions(&tmp, meta.permissions()))
+        .and_then(|()| fs::rename(&tmp, path));
+    if let Err(err) = written {
+        let _ = fs::remove_file(&tmp);
+        return Err(io_err(path, err));
+    }
+    Ok(())
+}
+
+/// Schema 1 introduced the `schema` key itself, which `upgrade` writes; the layout is unchanged.
+fn v0_to_v1(_doc: &mut DocumentMut) {}
+
+/// Moves the item at dotted key `from` to dotted key `to`, keeping the comments attached to it.
+/// Nothing happens when `from` is absent; when `to` is already set it wins and `from` is dropped.
+// No shipped step renames a key yet.
+#[cfg_attr(not(test), allow(dead_code))]
+fn rename_key(doc: &mut DocumentMut, from: &str, to: &str) {
+    let Some((key, item)) = take_key(doc, from) else {
+        return;
+    };
+    let mut segments: Vec<&str> = to.split('.').collect();
+    let Some(leaf) = segments.pop() else {
+        return;
+    };
+    let mut table: &mut dyn TableLike = doc.as_table_mut();
+    for segment in segments {
+        let parent = table.entry(segment).or_insert_with(|| {
+            let mut implicit = Table::new();
+            implicit.set_implicit(true);
+            Item::Table(implicit)
+        });
+        let Some(child) = parent.as_table_like_mut() else {
+            return;
+        };
+        table = child;
+    }
+    if table.contains_key(leaf) {
+        return;
+    }
+    table.insert(leaf, item);
+    if let Some(mut moved) = table.key_mut(leaf) {
+        *moved.leaf_decor_mut() = key.leaf_decor().clone();
+    }
+}
+
+/// Drops the item at dotted key `key`, with the comments attached to it.
+#[cfg_attr(not(test), allow(dead_code))]
+fn remove_key(doc: &mut DocumentMut, key: &str) {
+    take_key(doc, key);
+}
+
+fn take_key(doc: &mut DocumentMut, key: &str) -> Option<(Key, Item)> {
+    let mut segments: Vec<&str> = key.split('.').collect();
+    let leaf = segments.pop()?;
+    let mut table: &mut dyn TableLike = doc.as_table_mut();
+    for segment in segments {
+        table = table.get_mut(segment)?.as_table_like_mut()?;
+    }
+    let key = table.key(leaf)?.clone();
+    Some((key, table.remove(leaf)?))
+}
+
+fn set_schema(doc: &mut DocumentMut, version: u32) {
+    match doc.get_mut("schema") {
+        Some(item) => *item = value(i64::from(version)),
+        None => {
+            doc.insert("schema", value(i64::from(version)));
+            if let Some(mut key) = doc.key_mut("schema") {
+                key.leaf_decor_mut().set_prefix("");
+            }
+        }
+    }
+}
+
+fn item_repr(item: &Item) -> String {
+    item.to_string().trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unversioned_config_gains_schema_and_keeps_comments() {
+        let raw = "# team config\n[release]\n# our prefix\ntag_prefix = \"rel-\" # trailing\n";
+        let (doc, from) = upgrade(raw, Path::new("cfg.toml")).expect("upgrade");
+        assert_eq!(from, 0);
+        let out = doc.to_string();
+        assert!(out.starts_with(&format!("schema = {CURRENT_SCHEMA}\n")));
+        assert!(out.contains(
+            "# team config\n[release]\n# our prefix\ntag_prefix = \"rel-\" # trailing\n"
+        ));
+    }
+
+    #[test]
+    fn current_schema_is_unchanged() {
+        let raw = format!("schema = {CURRENT_SCHEMA}\n\n[time]\ntimezone = \"UTC\"\n");
+        let (doc, from) = upgrade(raw.as_str(), Path::new("cfg.toml")).expect("upgrade");
+        assert_eq!(from, CURRENT_SCHEMA);
+        assert_eq!(doc.to_string(), raw);
+    }
+
+    /// A later step that renames a key in place, moves one to a new section, and drops one.
+    fn v1_to_v2(doc: &mut DocumentMut) {
+        rename_key(doc, "remote.name", "remote.primary");
+        rename_key(doc, "output.style", "display.style");
+        remove_key(doc, "cache.legacy");
+    }
+
+    #[test]
+    fn steps_rename_and_remove_keys_keeping_comments() {
+        let raw = "# team config\n[remote]\n# where releases live\nname = \"upstream\" # mirror\n\n[cache]\nenabled = true\n# old knob\nlegacy = 3\n\n[output]\nstyle = \"plain\"\n";
+        let (doc, from) =
+            upgrade_with(raw, Path::new("cfg.toml"), &[v0_to_v1, v1_to_v2]).expect("upgrade");
+        assert_eq!(from, 0);
+        assert_eq!(
+            doc.to_string(),
+            "schema = 2\n# team config\n[remote]\n# where releases live\nprimary = \"upstream\" # mirror\n\n[cache]\nenabled = true\n\n[output]\n\n[display]\nstyle = \"plain\"\n"
+        );
+
+        // A file already at schema 1 only takes the second step; a set target key wins.
+        let raw = "schema = 1\n[remote]\nname = \"old\"\nprimary = \"new\"\n";
+        let (doc, from) =
+            upgrade_with(raw, Path::new("cfg.toml"), &[v0_to_v1, v1_to_v2]).expect("upgrade");
+        assert_eq!(from, 1);
+        assert_eq!(doc.to_string(), "schema = 2\n[remote]\nprimary = \"new\"\n");
+    }
+
+    #[test]
+    fn migrate_file_replaces_the_file_without_leftovers() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "[time]\ntimezone = \"UTC\"\n").expect("write config");
+        let outcome = migrate_file(&path).expect("migrate");
+        assert!(outcome.changed);
+        assert_eq!(
+            fs::read_to_string(&path).expect("read config"),
+            format!("schema = {CURRENT_SCHEMA}\n[time]\ntimezone = \"UTC\"\n")
+        );
+        let entries: Vec<_> = fs::read_dir(dir.path())
+            .expect("list dir")
+            .map(|entry| entry.expect("entry").file_name())
+            .collect();
+        assert_eq!(entries, ["config.toml"]);
+    }
+
+    #[test]
+    fn newer_schema_is_rejected() {
+        let raw = format!("schema = {}\n", CURRENT_SCHEMA + 1);
+        let err = upgrade(raw.as_str(), Path::new("cfg.toml")).expect_err("newer schema");
+        assert!(matches!(
+            err,
+            TideError::UnsupportedConfigSchema { found, supported, .. }
+                if found == CURRENT_SCHEMA + 1 && supported == CURRENT_SCHEMA
+        ));
+        assert!(upgrade("schema = \"one\"\n", Path::new("cfg.toml")).is_err());
+    }
+}
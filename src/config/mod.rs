@@ -0,0 +1,424 @@
+//! TideMark
+//! ========
+//!
+//! File: src/config/mod.rs
+//! Description: Configuration schema, defaults, layered loading, and initialization utilities.
+//!
+//! Responsibility:
+//! - Provide deterministic runtime configuration with validated defaults and explicit file loading behavior.
+//!
+//! Architectural Position:
+//! - Cross-cutting configuration boundary consumed by application, core, and operations flows.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{TideError, TideResult, io_err};
+
+pub mod layer;
+pub mod migrate;
+pub mod validate;
+
+pub const CONFIG_FILE_NAME: &str = ".tidemark.toml";
+/// Schema version written by `tide config init` and `tide config migrate`.
+pub const CURRENT_SCHEMA: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[non_exhaustive]
+pub struct TideConfig {
+    /// Files without `schema` are the pre-versioning shape (schema 0) and are migrated on load.
+    #[serde(default = "current_schema")]
+    pub schema: u32,
+    #[serde(default)]
+    pub release: ReleaseConfig,
+    #[serde(default)]
+    pub time: TimeConfig,
+    #[serde(default)]
+    pub coordinate: CoordinateConfig,
+    #[serde(default)]
+    pub branch: BranchConfig,
+    #[serde(default)]
+    pub remote: RemoteConfig,
+    #[serde(default)]
+    pub cache: CacheConfig,
+    #[serde(default)]
+    pub output: OutputConfig,
+}
+
+impl Default for TideConfig {
+    fn default() -> Self {
+        Self {
+            schema: CURRENT_SCHEMA,
+            release: ReleaseConfig::default(),
+            time: TimeConfig::default(),
+            coordinate: CoordinateConfig::default(),
+            branch: BranchConfig::default(),
+            remote: RemoteConfig::default(),
+            cache: CacheConfig::default(),
+            output: OutputConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[non_exhaustive]
+pub struct ReleaseConfig {
+    #[serde(default = "default_tag_prefix")]
+    pub tag_prefix: String,
+    #[serde(default = "default_true")]
+    pub require_annotated_tags: bool,
+    #[serde(default)]
+    pub on_missing_anchor: MissingAnchorPolicy,
+}
+
+impl Default for ReleaseConfig {
+    fn default() -> Self {
+        Self {
+            tag_prefix: default_tag_prefix(),
+            require_annotated_tags: true,
+            on_missing_anchor: MissingAnchorPolicy::Root,
+        }
+    }
+}
+
+/// Behavior when no release tag is reachable from the target commit.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum MissingAnchorPolicy {
+    /// Anchor at the oldest reachable root commit (ties broken by commit id).
+    #[default]
+    Root,
+    /// Fail with `NoReleaseAnchor`.
+    Error,
+    /// Anchor at the root of the target's first-parent chain.
+    FirstParentRoot,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[non_exhaustive]
+pub struct TimeConfig {
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+}
+
+impl Default for TimeConfig {
+    fn default() -> Self {
+        Self {
+            timezone: default_timezone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[non_exhaustive]
+pub struct CoordinateConfig {
+    #[serde(default)]
+    pub history: HistoryMode,
+}
+
+/// Commit set used for anchor distance and the same-day commit index.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum HistoryMode {
+    /// Every commit on an ancestry path from the anchor, including merged feature branches.
+    #[default]
+    AncestryPath,
+    /// Mainline commits only, so each merge advances the index by exactly one.
+    FirstParent,
+}
+
+impl fmt::Display for HistoryMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AncestryPath => write!(f, "ancestry-path"),
+            Self::FirstParent => write!(f, "first-parent"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[non_exhaustive]
+pub struct BranchConfig {
+    /// Branch names (or `prefix/*` patterns) that always get bare coordinates.
+    #[serde(default = "default_protected_branches")]
+    pub protected: Vec<String>,
+    #[serde(default)]
+    pub unprotected: BranchPolicy,
+    /// Environment variables consulted, in order, for the branch name when HEAD is detached.
+    #[serde(default = "default_ci_branch_env")]
+    pub ci_env: Vec<String>,
+}
+
+impl Default for BranchConfig {
+    fn default() -> Self {
+        Self {
+            protected: default_protected_branches(),
+            unprotected: BranchPolicy::Bare,
+            ci_env: default_ci_branch_env(),
+        }
+    }
+}
+
+/// Coordinate policy for branches that are not protected.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum BranchPolicy {
+    #[default]
+    Bare,
+    /// Append `-<sanitized-branch>-<short-hash>` to the coordinate.
+    Channel,
+    /// Fail with `BranchRefused`.
+    Refuse,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[non_exhaustive]
+pub struct RemoteConfig {
+    #[serde(default)]
+    pub strategy: RemoteStrategy,
+    #[serde(default = "default_remote_name")]
+    pub name: String,
+    /// Remotes in priority order; when non-empty this replaces `name`.
+    #[serde(default)]
+    pub names: Vec<String>,
+    #[serde(default = "default_true")]
+    pub fallback_to_local: bool,
+    #[serde(default)]
+    pub on_conflict: ConflictPolicy,
+    #[serde(default)]
+    pub overrides: BTreeMap<String, RemoteOverride>,
+    /// Progressively deepen shallow clones until the anchor and its ancestry path are present.
+    #[serde(default)]
+    pub deepen: bool,
+    #[serde(default = "default_deepen_step")]
+    pub deepen_step: u32,
+    #[serde(default = "default_deepen_max_rounds")]
+    pub deepen_max_rounds: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[non_exhaustive]
+pub struct RemoteOverride {
+    #[serde(default)]
+    pub fallback_to_local: Option<bool>,
+}
+
+impl RemoteConfig {
+    pub fn effective_names(&self) -> Vec<String> {
+        if self.names.is_empty() {
+            return vec![self.name.clone()];
+        }
+        let mut names: Vec<String> = Vec::with_capacity(self.names.len());
+        for name in &self.names {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+        names
+    }
+
+    pub fn fallback_for(&self, remote: &str) -> bool {
+        self.overrides
+            .get(remote)
+            .and_then(|entry| entry.fallback_to_local)
+            .unwrap_or(self.fallback_to_local)
+    }
+}
+
+impl Default for RemoteConfig {
+    fn default() -> Self {
+        Self {
+            strategy: RemoteStrategy::LsRemote,
+            name: default_remote_name(),
+            names: Vec::new(),
+            fallback_to_local: true,
+            on_conflict: ConflictPolicy::RemoteWins,
+            overrides: BTreeMap::new(),
+            deepen: false,
+            deepen_step: default_deepen_step(),
+            deepen_max_rounds: default_deepen_max_rounds(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RemoteStrategy {
+    #[default]
+    LsRemote,
+    Fetch,
+    LocalOnly,
+}
+
+/// Policy applied when a local and a remote release tag share a name but peel to different commits.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConflictPolicy {
+    #[default]
+    RemoteWins,
+    LocalWins,
+    Error,
+    Warn,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[non_exhaustive]
+pub struct CacheConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Evict oldest entries on write once the cache exceeds this many bytes.
+    #[serde(default)]
+    pub max_bytes: Option<u64>,
+    /// Evict entries written more than this many days ago on write.
+    #[serde(default)]
+    pub max_age_days: Option<u32>,
+    /// Shared tier: an absolute directory or an `http://` base URL; read-through, write-behind.
+    #[serde(default)]
+    pub remote: Option<String>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_bytes: None,
+            max_age_days: None,
+            remote: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[non_exhaustive]
+pub struct OutputConfig {
+    #[serde(default)]
+    pub metadata_suffix: Option<String>,
+    #[serde(default = "default_true")]
+    pub follow_renames: bool,
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self {
+            metadata_suffix: None,
+            follow_renames: true,
+        }
+    }
+}
+
+const fn current_schema() -> u32 {
+    CURRENT_SCHEMA
+}
+
+fn default_tag_prefix() -> String {
+    "v".to_string()
+}
+
+fn default_protected_branches() -> Vec<String> {
+    vec!["main".to_string(), "master".to_string()]
+}
+
+fn default_ci_branch_env() -> Vec<String> {
+    [
+        "GITHUB_HEAD_REF",
+        "GITHUB_REF_NAME",
+        "CI_COMMIT_BRANCH",
+        "BUILDKITE_BRANCH",
+        "BRANCH_NAME",
+    ]
+    .iter()
+    .map(|name| name.to_string())
+    .collect()
+}
+
+fn default_timezone() -> String {
+    "local".to_string()
+}
+
+fn default_remote_name() -> String {
+    "origin".to_string()
+}
+
+const fn default_deepen_step() -> u32 {
+    100
+}
+
+const fn default_deepen_max_rounds() -> u32 {
+    8
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+/// Effective config for `repo_root` after merging every layer except CLI overrides.
+pub fn load_or_default(repo_root: &Path) -> TideResult<TideConfig> {
+    Ok(layer::load_layered(repo_root, &[])?.config)
+}
+
+pub fn init_default(repo_root: &Path) -> TideResult<PathBuf> {
+    let path = repo_root.join(CONFIG_FILE_NAME);
+    if path.exists() {
+        return Err(TideError::ConfigExists { path });
+    }
+
+    fs::write(&path, default_config_toml()).map_err(|err| io_err(&path, err))?;
+    Ok(path)
+}
+
+pub fn default_config_toml() -> &'static str {
+    "# TideMark configuration\n\nschema = 1\n\n[release]\ntag_prefix = \"v\"\nrequire_annotated_tags = true\n# root | error | first-parent-root\non_missing_anchor = \"root\"\n\n[time]\ntimezone = \"local\"\n\n[coordinate]\n# ancestry-path | first-parent\nhistory = \"ancestry-path\"\n\n[branch]\nprotected = [\"main\", \"master\"]\n# bare | channel | refuse\nunprotected = \"bare\"\n\n[remote]\n# ls-remote (read-only) | fetch (mirrors into refs/tidemark/remote-tags) | local-only\nstrategy = \"ls-remote\"\nname = \"origin\"\n# names = [\"upstream\", \"origin\"] # priority order; overrides `name`\nfallback_to_local = true\n# remote-wins | local-wins | error | warn\non_conflict = \"remote-wins\"\n# Deepen shallow clones until the release anchor is reachable\ndeepen = false\ndeepen_step = 100\ndeepen_max_rounds = 8\n\n[cache]\nenabled = true\n# Limits enforced by cache writes; unset means unbounded\n# max_bytes = 52428800\n# max_age_days = 30\n# Shared cache for CI fleets: absolute directory or http://host:port/prefix\n# remote = \"/mnt/ci-cache/tidemark\"\n\n[output]\n# Optional suffix appended as x.y.z.<suffix>; does not change coordinates\n# Placeholders: {short_sha} {branch} {anchor_tag} {date} {dirty} {env:NAME}\nmetadata_suffix = \"\"\nfollow_renames = true\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_default_remote_strategy() {
+        let cfg: TideConfig = toml::from_str("[remote]\n").expect("parse config");
+        assert_eq!(cfg.remote.strategy, RemoteStrategy::LsRemote);
+        assert_eq!(cfg.remote.on_conflict, ConflictPolicy::RemoteWins);
+    }
+
+    #[test]
+    fn parse_conflict_policy_variants() {
+        let cfg: TideConfig =
+            toml::from_str("[remote]\non_conflict = \"local-wins\"\n").expect("parse config");
+        assert_eq!(cfg.remote.on_conflict, ConflictPolicy::LocalWins);
+        assert!(toml::from_str::<TideConfig>("[remote]\non_conflict = \"newest\"\n").is_err());
+    }
+
+    #[test]
+    fn remote_names_and_overrides() {
+        let cfg: TideConfig = toml::from_str(
+            "[remote]\nnames = [\"upstream\", \"origin\", \"upstream\"]\n\n[remote.overrides.upstream]\nfallback_to_local = false\n",
+        )
+        .expect("parse config");
+        assert_eq!(cfg.remote.effective_names(), vec!["upstream", "origin"]);
+        assert!(!cfg.remote.fallback_for("upstream"));
+        assert!(cfg.remote.fallback_for("origin"));
+        assert_eq!(
+            TideConfig::default().remote.effective_names(),
+            vec!["origin"]
+        );
+    }
+}
@@ -0,0 +1,438 @@
+//! TideMark
+//! ========
+//!
+//! File: src/config/validate.rs
+//! Description: Whole-config validation pass reporting every problem with file positions.
+//!
+//! Responsibility:
+//! - Collect syntax errors, unknown keys, invalid values, unmatched tag prefixes, and unreachable remotes in one run.
+//!
+//! Architectural Position:
+//! - Configuration diagnostics boundary behind `tide config validate`.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+use std::{fmt, fs, ops::Range, path::PathBuf};
+
+use serde::Serialize;
+use toml_edit::{ImDocument, TableLike};
+
+use super::{
+    CONFIG_FILE_NAME, CURRENT_SCHEMA, RemoteStrategy,
+    layer::{self, ConfigOrigin, LayeredConfig},
+};
+use crate::{
+    error::{TideError, TideResult, io_err},
+    infra::git::GitProvider,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IssueSeverity {
+    Error,
+    /// Reported but does not fail validation.
+    Warning,
+}
+
+impl fmt::Display for IssueSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error => write!(f, "error"),
+            Self::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IssueKind {
+    Syntax,
+    UnknownKey,
+    InvalidValue,
+    /// No local or remote tag starts with `release.tag_prefix`.
+    UnmatchedPrefix,
+    UnreachableRemote,
+    /// Declared schema is older than this build's; `tide config migrate` upgrades it.
+    OutdatedSchema,
+}
+
+impl fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax => write!(f, "syntax"),
+            Self::UnknownKey => write!(f, "unknown-key"),
+            Self::InvalidValue => write!(f, "invalid-value"),
+            Self::UnmatchedPrefix => write!(f, "unmatched-prefix"),
+            Self::UnreachableRemote => write!(f, "unreachable-remote"),
+            Self::OutdatedSchema => write!(f, "outdated-schema"),
+        }
+    }
+}
+
+/// One validation finding; `source` is a file path, an env var name, `-c key=value`, or `default`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigIssue {
+    pub severity: IssueSeverity,
+    pub kind: IssueKind,
+    pub source: String,
+    pub key: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationReport {
+    pub issues: Vec<ConfigIssue>,
+}
+
+impl ValidationReport {
+    pub fn error_count(&self) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == IssueSeverity::Error)
+            .count()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.error_count() == 0
+    }
+}
+
+struct ParsedFile {
+    path: PathBuf,
+    raw: String,
+    doc: ImDocument<String>,
+}
+
+/// Validates every config layer for the repository behind `git`. Remote reachability is
+/// skipped when `local_only` is set or the effective strategy is `local-only`.
+pub fn validate(
+    git: &dyn GitProvider,
+    overrides: &[(String, String)],
+    local_only: bool,
+) -> TideResult<ValidationReport> {
+    let mut issues = Vec::new();
+    let mut files = Vec::new();
+    let paths = [
+        layer::global_config_path(),
+        Some(git.repo_root().join(CONFIG_FILE_NAME)),
+    ];
+    for path in paths.into_iter().flatten() {
+        if path.exists() {
+            files.extend(check_file(path, &mut issues)?);
+        }
+    }
+    // Broken layers are left out so the remaining ones still reach the repository checks.
+    let (layered, rejected) = layer::load_layered_skipping_invalid(git.repo_root(), overrides)?;
+    for (origin, message) in rejected {
+        issues.push(ConfigIssue {
+            severity: IssueSeverity::Error,
+            kind: issue_kind(message.as_str()),
+            source: origin,
+            key: None,
+            line: None,
+            column: None,
+            message,
+        });
+    }
+
+    check_repository(git, &layered, &files, local_only, &mut issues)?;
+    Ok(ValidationReport { issues })
+}
+
+fn check_file(path: PathBuf, issues: &mut Vec<ConfigIssue>) -> TideResult<Option<ParsedFile>> {
+    let raw = fs::read_to_string(&path).map_err(|err| io_err(&path, err))?;
+    let source = path.display().to_string();
+    let doc = match ImDocument::parse(raw.clone()) {
+        Ok(doc) => doc,
+        Err(err) => {
+            let (line, column) = position(raw.as_str(), err.span());
+            issues.push(ConfigIssue {
+                severity: IssueSeverity::Error,
+                kind: IssueKind::Syntax,
+                source,
+                key: None,
+                line,
+                column,
+                message: err.message().trim().to_string(),
+            });
+            return Ok(None);
+        }
+    };
+    let values = raw
+        .parse::<toml::Table>()
+        .map_err(|err| TideError::ConfigParse {
+            path: path.clone(),
+            message: err.message().trim().to_string(),
+        })?;
+
+    let schema = match values.get("schema") {
+        Some(value) => value.as_integer(),
+        None => Some(0),
+    };
+    if let Some(found) = schema.filter(|found| *found != i64::from(CURRENT_SCHEMA)) {
+        let newer = found > i64::from(CURRENT_SCHEMA);
+        let span = doc.as_table().key("schema").and_then(|key| key.span());
+        let (line, column) = position(raw.as_str(), span);
+        issues.push(ConfigIssue {
+            severity: if newer {
+                IssueSeverity::Error
+            } else {
+                IssueSeverity::Warning
+            },
+            kind: if newer {
+                IssueKind::InvalidValue
+            } else {
+                IssueKind::OutdatedSchema
+            },
+            source: source.clone(),
+            key: Some("schema".to_string()),
+            line,
+            column,
+            message: if newer {
+                format!("schema {found} is newer than supported schema {CURRENT_SCHEMA}")
+            } else {
+                format!("schema {found} is older than {CURRENT_SCHEMA}; run `tide config migrate`")
+            },
+        });
+        if newer {
+            return Ok(None);
+        }
+    }
+
+    let mut walker = Walker {
+        raw: raw.as_str(),
+        source: source.as_str(),
+        values: &values,
+        path: Vec::new(),
+        issues,
+    };
+    walker.walk(doc.as_table());
+    Ok(Some(ParsedFile { path, raw, doc }))
+}
+
+struct Walker<'a> {
+    raw: &'a str,
+    source: &'a str,
+    values: &'a toml::Table,
+    path: Vec<String>,
+    issues: &'a mut Vec<ConfigIssue>,
+}
+
+impl Walker<'_> {
+    /// Checks each key as a single-key layer; a failing key is reported once and not descended.
+    fn walk(&mut self, table: &dyn TableLike) {
+        for (key, item) in table.iter() {
+            self.path.push(key.to_string());
+            let Some(value) = lookup(self.values, &self.path) else {
+                self.path.pop();
+                continue;
+            };
+            // Tables are checked empty so a bad child is reported at the child, not its section.
+            let value = match item.as_table_like() {
+                Some(_) => toml::Value::Table(toml::Table::new()),
+                None => value.clone(),
+            };
+            let layer = layer::nest(&self.path, value);
+            match layer::validate_layer(&layer) {
+                Err(message) => {
+                    let span = table.key(key).and_then(|key| key.span());
+                    let (line, column) = position(self.raw, span);
+                    self.issues.push(ConfigIssue {
+                        severity: IssueSeverity::Error,
+                        kind: issue_kind(message.as_str()),
+                        source: self.source.to_string(),
+                        key: Some(self.path.join(".")),
+                        line,
+                        column,
+                        message,
+                    });
+                }
+                Ok(()) => {
+                    if let Some(child) = item.as_table_like() {
+                        self.walk(child);
+                    }
+                }
+            }
+            self.path.pop();
+        }
+    }
+}
+
+fn check_repository(
+    git: &dyn GitProvider,
+    layered: &LayeredConfig,
+    files: &[ParsedFile],
+    local_only: bool,
+    issues: &mut Vec<ConfigIssue>,
+) -> TideResult<()> {
+    let cfg = &layered.config;
+    let prefix = cfg.release.tag_prefix.as_str();
+    let mut tag_count = git.list_local_tags(prefix)?.len();
+
+    if !local_only && cfg.remote.strategy != RemoteStrategy::LocalOnly {
+        let remote_key = if cfg.remote.names.is_empty() {
+            "remote.name"
+        } else {
+            "remote.names"
+        };
+        for remote in cfg.remote.effective_names() {
+            match git.list_remote_tags(remote.as_str(), prefix) {
+                Ok(tags) => tag_count += tags.len(),
+                Err(err) => issues.push(located(
+                    layered,
+                    files,
+                    remote_key,
+                    IssueSeverity::Error,
+                    IssueKind::UnreachableRemote,
+                    format!("remote `{remote}` is unreachable: {err}"),
+                )),
+            }
+        }
+    }
+
+    if tag_count == 0 {
+        issues.push(located(
+            layered,
+            files,
+            "release.tag_prefix",
+            IssueSeverity::Warning,
+            IssueKind::UnmatchedPrefix,
+            format!("no tags match prefix `{prefix}`"),
+        ));
+    }
+    Ok(())
+}
+
+/// Builds an issue positioned at `key` in whichever layer set it.
+fn located(
+    layered: &LayeredConfig,
+    files: &[ParsedFile],
+    key: &str,
+    severity: IssueSeverity,
+    kind: IssueKind,
+    message: String,
+) -> ConfigIssue {
+    let origin = layered.origin(key);
+    let (source, file) = match &origin {
+        ConfigOrigin::Global(path) | ConfigOrigin::Repo(path) => (
+            path.display().to_string(),
+            files.iter().find(|file| file.path == *path),
+        ),
+        ConfigOrigin::Env(name) => (name.clone(), None),
+        ConfigOrigin::Cli => ("cli".to_string(), None),
+        ConfigOrigin::Default => ("default".to_string(), None),
+    };
+    let (line, column) = file
+        .map(|file| position(file.raw.as_str(), key_span(&file.doc, key)))
+        .unwrap_or((None, None));
+    ConfigIssue {
+        severity,
+        kind,
+        source,
+        key: Some(key.to_string()),
+        line,
+        column,
+        message,
+    }
+}
+
+fn key_span(doc: &ImDocument<String>, key: &str) -> Option<Range<usize>> {
+    let mut table: &dyn TableLike = doc.as_table();
+    let mut segments = key.split('.').peekable();
+    while let Some(segment) = segments.next() {
+        if segments.peek().is_none() {
+            return table.key(segment).and_then(|key| key.span());
+        }
+        table = table.get(segment)?.as_table_like()?;
+    }
+    None
+}
+
+fn lookup<'a>(table: &'a toml::Table, path: &[String]) -> Option<&'a toml::Value> {
+    let (first, rest) = path.split_first()?;
+    rest.iter()
+        .try_fold(table.get(first.as_str())?, |value, segment| {
+            value.as_table()?.get(segment.as_str())
+        })
+}
+
+fn issue_kind(message: &str) -> IssueKind {
+    if message.contains("unknown field") {
+        IssueKind::UnknownKey
+    } else {
+        IssueKind::InvalidValue
+    }
+}
+
+/// 1-based line and column of the start of `span`.
+fn position(raw: &str, span: Option<Range<usize>>) -> (Option<usize>, Option<usize>) {
+    let Some(span) = span else {
+        return (None, None);
+    };
+    let before = &raw[..span.start.min(raw.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map(|tail| tail.chars().count() + 1)
+        .unwrap_or(1);
+    (Some(line), Some(column))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(raw: &str) -> Vec<ConfigIssue> {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        fs::write(&path, raw).expect("write config");
+        let mut issues = Vec::new();
+        check_file(path, &mut issues).expect("check file");
+        issues
+    }
+
+    #[test]
+    fn reports_every_problem_with_positions() {
+        let issues = check(
+            "schema = 1\n\n[release]\ntag_prefix = \"v\"\ntag_prefx = \"x\"\n\n[time]\ntimezone = \"Mars/Base\"\n\n[colour]\nmode = 1\n",
+        );
+        let summary: Vec<String> = issues
+            .iter()
+            .map(|issue| {
+                format!(
+                    "{} {} {}:{}",
+                    issue.kind,
+                    issue.key.as_deref().unwrap_or("-"),
+                    issue.line.unwrap_or(0),
+                    issue.column.unwrap_or(0)
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                "unknown-key release.tag_prefx 5:1",
+                "invalid-value time.timezone 8:1",
+                "unknown-key colour 10:2",
+            ]
+        );
+    }
+
+    #[test]
+    fn syntax_errors_carry_a_position() {
+        let issues = check("[remote]\nstrategy = \n");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, IssueKind::Syntax);
+        assert_eq!(issues[0].line, Some(2));
+    }
+
+    #[test]
+    fn valid_config_has_no_issues() {
+        assert!(check(crate::config::default_config_toml()).is_empty());
+    }
+}
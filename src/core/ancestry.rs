@@ -0,0 +1,271 @@
+//! TideMark
+//! ========
+//!
+//! File: src/core/ancestry.rs
+//! Description: Persistent per-anchor ancestry index for same-day commit ordinals.
+//!
+//! Responsibility:
+//! - Record each ancestry-path commit's day bucket and same-day ordinal, extending the index with only the commits git has not shown it yet.
+//!
+//! Architectural Position:
+//! - Core acceleration layer between the mark resolver, the Git provider, and the cache store.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::{
+        model::{CommitInfo, IndexStatus},
+        time::TimezonePolicy,
+    },
+    error::{TideError, TideResult},
+    infra::{
+        cache::{CacheLookup, CacheStore},
+        git::{CommitNode, GitProvider},
+    },
+};
+
+pub const ANCESTRY_CACHE_NAMESPACE: &str = "ancestry";
+
+/// Indexed tips passed to git as exclusions; passing fewer only lengthens the walk.
+const MAX_EXCLUDED_TIPS: usize = 64;
+
+#[derive(Serialize)]
+struct AncestryCacheKey<'a> {
+    anchor_commit: &'a str,
+    timezone: &'a str,
+    canonical_timezone: String,
+}
+
+/// Every ancestry-path commit below some indexed tip, parents before children.
+///
+/// Closed under path ancestry: whenever a commit is indexed, so is every commit between it and
+/// the anchor, which is what lets git exclude indexed tips from later walks.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AncestryIndex {
+    commits: Vec<IndexedCommit>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedCommit {
+    id: String,
+    timestamp: i64,
+    /// Positions of parents that are themselves on the ancestry path.
+    parents: Vec<u32>,
+    /// Day bucket (days from CE) in the index's timezone.
+    day: i32,
+    /// Latest day bucket among this commit and its path ancestors; bounds same-day searches.
+    max_day: i32,
+    /// Same-day commit index when this commit is the target.
+    ordinal: u32,
+}
+
+impl AncestryIndex {
+    pub fn ordinal(&self, id: &str) -> Option<u32> {
+        self.commits
+            .iter()
+            .find(|commit| commit.id == id)
+            .map(|commit| commit.ordinal)
+    }
+
+    /// Most recent indexed commits without indexed children.
+    fn tips(&self) -> Vec<String> {
+        let mut has_child = vec![false; self.commits.len()];
+        for commit in &self.commits {
+            for parent in &commit.parents {
+                has_child[*parent as usize] = true;
+            }
+        }
+        let mut tips: Vec<&IndexedCommit> = self
+            .commits
+            .iter()
+            .zip(has_child)
+            .filter(|(_, has_child)| !has_child)
+            .map(|(commit, _)| commit)
+            .collect();
+        tips.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then_with(|| a.id.cmp(&b.id)));
+        tips.into_iter()
+            .take(MAX_EXCLUDED_TIPS)
+            .map(|commit| commit.id.clone())
+            .collect()
+    }
+
+    /// Append `nodes` (parents before children), skipping commits already indexed.
+    fn extend(&mut self, nodes: Vec<CommitNode>, timezone: &TimezonePolicy) -> TideResult<u32> {
+        let mut positions: HashMap<String, u32> = self
+            .commits
+            .iter()
+            .enumerate()
+            .map(|(pos, commit)| (commit.id.clone(), pos as u32))
+            .collect();
+        let mut added = 0;
+        for node in nodes {
+            if positions.contains_key(&node.commit.id) {
+                continue;
+            }
+            let parents: Vec<u32> = node
+                .parents
+                .iter()
+                .filter_map(|parent| positions.get(parent).copied())
+                .collect();
+            let day = timezone
+                .date_for_timestamp(node.commit.timestamp)?
+                .num_days_from_ce();
+            let max_day = parents
+                .iter()
+                .map(|parent| self.commits[*parent as usize].max_day)
+                .fold(day, i32::max);
+            let ordinal = self.earlier_same_day(&parents, day, &node.commit) + 1;
+
+            positions.insert(node.commit.id.clone(), self.commits.len() as u32);
+            self.commits.push(IndexedCommit {
+                id: node.commit.id,
+                timestamp: node.commit.timestamp,
+                parents,
+                day,
+                max_day,
+                ordinal,
+            });
+            added += 1;
+        }
+        Ok(added)
+    }
+
+    /// Path ancestors on `day` that sort before `commit` by `(timestamp, id)`.
+    fn earlier_same_day(&self, parents: &[u32], day: i32, commit: &CommitInfo) -> u32 {
+        let mut seen = HashSet::new();
+        let mut stack = parents.to_vec();
+        let mut count = 0;
+        while let Some(pos) = stack.pop() {
+            if !seen.insert(pos) {
+                continue;
+            }
+            let ancestor = &self.commits[pos as usize];
+            if ancestor.max_day < day {
+                continue;
+            }
+            if ancestor.day == day
+                && (ancestor.timestamp, ancestor.id.as_str())
+                    < (commit.timestamp, commit.id.as_str())
+            {
+                count += 1;
+            }
+            stack.extend(ancestor.parents.iter().copied());
+        }
+        count
+    }
+}
+
+/// Same-day commit index of `target` on the ancestry path from `anchor`, served from and
+/// persisted to the per-anchor index.
+pub fn indexed_commit_index(
+    git: &dyn GitProvider,
+    cache: &CacheStore,
+    timezone: &TimezonePolicy,
+    timezone_setting: &str,
+    anchor: &CommitInfo,
+    target: &CommitInfo,
+) -> TideResult<(u32, IndexStatus)> {
+    let key = CacheStore::key_from_serializable(
+        ANCESTRY_CACHE_NAMESPACE,
+        &AncestryCacheKey {
+            anchor_commit: anchor.id.as_str(),
+            timezone: timezone_setting,
+            canonical_timezone: timezone.canonical_name(),
+        },
+    )?;
+    let mut index = match cache.get::<AncestryIndex>(ANCESTRY_CACHE_NAMESPACE, key.as_str())? {
+        CacheLookup::Hit(index) | CacheLookup::RemoteHit(index) => index,
+        CacheLookup::Miss | CacheLookup::Invalid(_) => AncestryIndex::default(),
+    };
+    if let Some(ordinal) = index.ordinal(target.id.as_str()) {
+        return Ok((ordinal, IndexStatus::Hit));
+    }
+
+    let nodes = git.ancestry_path_graph(anchor.id.as_str(), target.id.as_str(), &index.tips())?;
+    let new_commits = index.extend(nodes, timezone)?;
+    let ordinal = index
+        .ordinal(target.id.as_str())
+        .ok_or_else(|| TideError::Internal {
+            message: format!(
+                "target commit {} not found on the ancestry path from {}",
+                target.id, anchor.id
+            ),
+        })?;
+    cache.put(ANCESTRY_CACHE_NAMESPACE, key.as_str(), &index)?;
+    Ok((ordinal, IndexStatus::Extended { new_commits }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, timestamp: i64, parents: &[&str]) -> CommitNode {
+        CommitNode {
+            commit: CommitInfo {
+                id: id.to_string(),
+                timestamp,
+            },
+            parents: parents.iter().map(|parent| parent.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn ordinals_count_only_same_day_path_ancestors_across_extensions() {
+        let utc = TimezonePolicy::parse("UTC").unwrap();
+        let day = 86_400;
+        let mut index = AncestryIndex::default();
+
+        // anchor -> a (day 1) -> b, c (day 2, siblings) -> m (merge, day 2)
+        let added = index
+            .extend(
+                vec![
+                    node("a", day + 10, &["anchor"]),
+                    node("b", 2 * day + 20, &["a"]),
+                    node("c", 2 * day + 10, &["a"]),
+                ],
+                &utc,
+            )
+            .unwrap();
+        assert_eq!(added, 3);
+        assert_eq!(index.ordinal("a"), Some(1));
+        assert_eq!(index.ordinal("c"), Some(1));
+        // `c` is earlier on the same day but not an ancestor of `b`.
+        assert_eq!(index.ordinal("b"), Some(1));
+        assert_eq!(index.tips(), vec!["b".to_string(), "c".to_string()]);
+
+        let added = index
+            .extend(
+                vec![
+                    node("b", 2 * day + 20, &["a"]),
+                    node("m", 2 * day + 30, &["b", "c"]),
+                ],
+                &utc,
+            )
+            .unwrap();
+        assert_eq!(added, 1);
+        assert_eq!(index.ordinal("m"), Some(3));
+
+        // Clock skew: `s` is dated the day before its parent yet still sees same-day ancestors.
+        index
+            .extend(
+                vec![
+                    node("d", 3 * day + 5, &["m"]),
+                    node("s", 2 * day + 1, &["d"]),
+                ],
+                &utc,
+            )
+            .unwrap();
+        assert_eq!(index.ordinal("s"), Some(1));
+        index
+            .extend(vec![node("t", 2 * day + 40, &["s"])], &utc)
+            .unwrap();
+        assert_eq!(index.ordinal("t"), Some(5));
+    }
+}
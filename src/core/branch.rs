@@ -0,0 +1,142 @@
+//! TideMark
+//! ========
+//!
+//! File: src/core/branch.rs
+//! Description: Branch detection and branch-policy channel derivation for version coordinates.
+//!
+//! Responsibility:
+//! - Resolve the build branch (Git or CI-provided) and map it to a bare coordinate, a channel suffix, or a refusal.
+//!
+//! Architectural Position:
+//! - Core policy layer applied by the mark resolver before coordinate emission.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+use std::env;
+
+use sha2::{Digest, Sha256};
+
+use crate::{
+    config::{BranchConfig, BranchPolicy},
+    error::{TideError, TideResult},
+    infra::git::GitProvider,
+};
+
+const MAX_CHANNEL_NAME_LEN: usize = 32;
+const CHANNEL_HASH_LEN: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchContext {
+    pub name: Option<String>,
+    pub channel: Option<String>,
+}
+
+/// Resolve the branch for HEAD, consulting `branch.ci_env` only when HEAD is detached.
+///
+/// A detached HEAD without a CI-provided name (for example a tag checkout) is treated like a
+/// protected branch and gets a bare coordinate.
+pub fn resolve_branch(git: &dyn GitProvider, config: &BranchConfig) -> TideResult<BranchContext> {
+    let name = match git.current_branch()? {
+        Some(name) => Some(name),
+        None => ci_branch(config),
+    };
+
+    let Some(branch) = name.as_deref() else {
+        return Ok(BranchContext {
+            name: None,
+            channel: None,
+        });
+    };
+    if is_protected(config, branch) {
+        return Ok(BranchContext {
+            name,
+            channel: None,
+        });
+    }
+
+    let channel = match config.unprotected {
+        BranchPolicy::Bare => None,
+        BranchPolicy::Channel => Some(channel_for(branch)),
+        BranchPolicy::Refuse => {
+            return Err(TideError::BranchRefused {
+                branch: branch.to_string(),
+            });
+        }
+    };
+    Ok(BranchContext { name, channel })
+}
+
+fn ci_branch(config: &BranchConfig) -> Option<String> {
+    // GitHub reports the tag name in GITHUB_REF_NAME for tag builds; that is not a branch.
+    let github_tag_build = env::var("GITHUB_REF_TYPE").is_ok_and(|value| value == "tag");
+    config
+        .ci_env
+        .iter()
+        .filter(|key| !(github_tag_build && key.as_str() == "GITHUB_REF_NAME"))
+        .filter_map(|key| env::var(key).ok())
+        .map(|value| value.trim().trim_start_matches("refs/heads/").to_string())
+        .find(|value| !value.is_empty())
+}
+
+fn is_protected(config: &BranchConfig, branch: &str) -> bool {
+    config
+        .protected
+        .iter()
+        .any(|pattern| match pattern.strip_suffix('*') {
+            Some(prefix) => branch.starts_with(prefix),
+            None => pattern == branch,
+        })
+}
+
+/// `<sanitized-branch>-<hash>`: the hash of the raw name keeps branches that sanitize to the
+/// same text apart.
+pub fn channel_for(branch: &str) -> String {
+    let mut sanitized = String::with_capacity(branch.len());
+    for ch in branch.chars() {
+        if ch.is_ascii_alphanumeric() {
+            sanitized.push(ch.to_ascii_lowercase());
+        } else if !sanitized.ends_with('-') {
+            sanitized.push('-');
+        }
+    }
+    let mut sanitized = sanitized.trim_matches('-').to_string();
+    sanitized.truncate(MAX_CHANNEL_NAME_LEN);
+    let sanitized = sanitized.trim_end_matches('-');
+
+    let digest = hex::encode(Sha256::digest(branch.as_bytes()));
+    let short = &digest[..CHANNEL_HASH_LEN];
+    if sanitized.is_empty() {
+        short.to_string()
+    } else {
+        format!("{sanitized}-{short}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn channel_is_sanitized_and_disambiguated() {
+        let a = channel_for("feature/X_y");
+        let b = channel_for("feature-x-y");
+        assert!(a.starts_with("feature-x-y-"));
+        assert!(b.starts_with("feature-x-y-"));
+        assert_ne!(a, b);
+        assert_eq!(a, channel_for("feature/X_y"));
+        assert_eq!(channel_for("///").len(), CHANNEL_HASH_LEN);
+    }
+
+    #[test]
+    fn protected_patterns_support_prefix_globs() {
+        let config = BranchConfig {
+            protected: vec!["main".to_string(), "release/*".to_string()],
+            ..BranchConfig::default()
+        };
+        assert!(is_protected(&config, "main"));
+        assert!(is_protected(&config, "release/1.x"));
+        assert!(!is_protected(&config, "mainline"));
+    }
+}
@@ -0,0 +1,186 @@
+//! TideMark
+//! ========
+//!
+//! File: src/core/history.rs
+//! Description: History-walk selection, shallow-history detection, and progressive deepening for anchor discovery.
+//!
+//! Responsibility:
+//! - Route distance and commit-index walks through the configured history mode.
+//! - Guarantee that the selected anchor and its ancestry path are fully present before a coordinate is emitted.
+//!
+//! Architectural Position:
+//! - Core guard between release-anchor selection and coordinate computation in shallow clones.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+use crate::{
+    config::{HistoryMode, TideConfig},
+    core::{
+        model::{AnchorSelection, CommitInfo, ReleaseTag},
+        release,
+    },
+    error::{TideError, TideResult},
+    infra::git::{DeepenStep, GitProvider},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryStatus {
+    pub shallow: bool,
+    pub deepen_rounds: u32,
+}
+
+/// Commits between `ancestor` and `descendant` counted under the configured history mode.
+pub fn distance(
+    git: &dyn GitProvider,
+    mode: HistoryMode,
+    ancestor: &str,
+    descendant: &str,
+) -> TideResult<u32> {
+    match mode {
+        HistoryMode::AncestryPath => git.commit_distance(ancestor, descendant),
+        HistoryMode::FirstParent => git.first_parent_distance(ancestor, descendant),
+    }
+}
+
+/// Commits in `(ancestor, descendant]` that participate in the same-day commit index.
+pub fn path_commits(
+    git: &dyn GitProvider,
+    mode: HistoryMode,
+    ancestor: &str,
+    descendant: &str,
+) -> TideResult<Vec<CommitInfo>> {
+    match mode {
+        HistoryMode::AncestryPath => git.ancestry_path_commits(ancestor, descendant),
+        HistoryMode::FirstParent => git.first_parent_commits(ancestor, descendant),
+    }
+}
+
+/// Select the release anchor, deepening a shallow clone when allowed.
+///
+/// Returns `Ok((None, _))` only for complete histories without a reachable release tag; a
+/// shallow clone that cannot prove its anchor or ancestry path fails with `ShallowHistory`.
+pub fn select_anchor_with_history(
+    git: &dyn GitProvider,
+    config: &TideConfig,
+    releases: &[ReleaseTag],
+    target: &CommitInfo,
+    allow_deepen: bool,
+) -> TideResult<(Option<AnchorSelection>, HistoryStatus)> {
+    let allow_deepen = allow_deepen && config.remote.deepen;
+    let mut deepen_rounds = 0;
+
+    loop {
+        let boundaries = git.shallow_boundaries()?;
+        // In a complete clone a commit missing locally cannot be an ancestor of the target, so
+        // remote tag objects are only worth fetching behind a shallow boundary.
+        if deepen_rounds == 0 && !boundaries.is_empty() {
+            release::fetch_missing_commits(git, releases)?;
+        }
+        let selected = match release::select_anchor(
+            git,
+            releases,
+            target,
+            config.release.tag_prefix.as_str(),
+            config.coordinate.history,
+        ) {
+            Ok(anchor) => Some(anchor),
+            Err(TideError::NoReleaseAnchor { .. }) => None,
+            Err(other) => return Err(other),
+        };
+        let status = HistoryStatus {
+            shallow: !boundaries.is_empty(),
+            deepen_rounds,
+        };
+        if boundaries.is_empty() {
+            return Ok((selected, status));
+        }
+
+        let reason = match &selected {
+            Some(anchor) => match crossed_boundary(git, &boundaries, anchor, target)? {
+                None => return Ok((selected, status)),
+                Some(boundary) => format!(
+                    "ancestry path from `{}` crosses shallow boundary {boundary}",
+                    anchor.release.tag.name
+                ),
+            },
+            None => "no release anchor is reachable above the shallow boundary".to_string(),
+        };
+
+        if !allow_deepen || deepen_rounds >= config.remote.deepen_max_rounds {
+            return Err(TideError::ShallowHistory {
+                reason,
+                deepen_rounds,
+            });
+        }
+
+        let step = next_deepen_step(git, config, &boundaries, releases, target, deepen_rounds)?;
+        let remote = config
+            .remote
+            .effective_names()
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+        git.deepen(remote.as_str(), &step)?;
+        deepen_rounds += 1;
+    }
+}
+
+/// A shallow boundary commit that descends from the target but not from the anchor means
+/// part of `(anchor, target]` is missing, so distance and same-day ordering are unreliable.
+fn crossed_boundary(
+    git: &dyn GitProvider,
+    boundaries: &[String],
+    anchor: &AnchorSelection,
+    target: &CommitInfo,
+) -> TideResult<Option<String>> {
+    for boundary in boundaries {
+        if boundary == &anchor.anchor_commit.id {
+            continue;
+        }
+        if git.is_ancestor(boundary, target.id.as_str())?
+            && !git.is_ancestor(boundary, anchor.anchor_commit.id.as_str())?
+        {
+            return Ok(Some(boundary.clone()));
+        }
+    }
+    Ok(None)
+}
+
+/// The first round jumps straight to the newest plausible anchor date; later rounds (or when
+/// no tag date is usable) deepen by a fixed commit count.
+fn next_deepen_step(
+    git: &dyn GitProvider,
+    config: &TideConfig,
+    boundaries: &[String],
+    releases: &[ReleaseTag],
+    target: &CommitInfo,
+    deepen_rounds: u32,
+) -> TideResult<DeepenStep> {
+    let fallback = DeepenStep::Commits(config.remote.deepen_step.max(1));
+    if deepen_rounds > 0 {
+        return Ok(fallback);
+    }
+
+    let mut oldest_boundary = i64::MAX;
+    for boundary in boundaries {
+        oldest_boundary = oldest_boundary.min(git.resolve_commit(boundary)?.timestamp);
+    }
+
+    let mut newest_candidate: Option<i64> = None;
+    for release in releases {
+        if !git.commit_exists(release.tag.commit_id.as_str())? {
+            continue;
+        }
+        let ts = git
+            .resolve_commit(release.tag.commit_id.as_str())?
+            .timestamp;
+        // `--shallow-since` may also shorten history, so only move the boundary backwards.
+        if ts <= target.timestamp && ts < oldest_boundary {
+            newest_candidate = Some(newest_candidate.map_or(ts, |current| current.max(ts)));
+        }
+    }
+
+    Ok(newest_candidate.map_or(fallback, DeepenStep::Since))
+}
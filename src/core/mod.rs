@@ -5,7 +5,7 @@
 //! Description: Core domain module index for deterministic version-coordinate semantics.
 //!
 //! Responsibility:
-//! - Expose domain model, time policy, release-anchor selection, and resolver algorithms.
+//! - Expose domain model, time policy, branch policy, release-anchor selection, history completeness, suffix templates, and resolver algorithms.
 //!
 //! Architectural Position:
 //! - Pure domain layer isolated from CLI rendering and operational side effects.
@@ -14,7 +14,11 @@
 //! Email: silan.hu@u.nus.edu
 //! Copyright (c) 2026-2027 easynet. All rights reserved.
 
+pub mod ancestry;
+pub mod branch;
+pub mod history;
 pub mod model;
 pub mod release;
 pub mod resolver;
+pub mod suffix;
 pub mod time;
@@ -18,22 +18,30 @@ use std::fmt;
 
 use serde::{Deserialize, Serialize};
 
+use crate::config::HistoryMode;
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct VersionCoordinate {
     pub x: u64,
     pub y: u32,
     pub z: u32,
+    /// Branch channel for unprotected branches; rendered as `x.y.z-<channel>`.
+    #[serde(default)]
+    pub channel: Option<String>,
     pub metadata: Option<String>,
 }
 
 impl fmt::Display for VersionCoordinate {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match &self.metadata {
-            Some(meta) if !meta.is_empty() => {
-                write!(f, "{}.{}.{}.{}", self.x, self.y, self.z, meta)
-            }
-            _ => write!(f, "{}.{}.{}", self.x, self.y, self.z),
+        write!(f, "{}.{}.{}", self.x, self.y, self.z)?;
+        if let Some(channel) = self.channel.as_deref().filter(|v| !v.is_empty()) {
+            write!(f, "-{channel}")?;
+        }
+        if let Some(meta) = self.metadata.as_deref().filter(|v| !v.is_empty()) {
+            write!(f, ".{meta}")?;
         }
+        Ok(())
     }
 }
 
@@ -44,16 +52,18 @@ pub struct CommitInfo {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum TagSource {
     Local,
-    Remote,
+    /// Tag listed from the named remote.
+    Remote(String),
 }
 
 impl fmt::Display for TagSource {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Local => write!(f, "local"),
-            Self::Remote => write!(f, "remote"),
+            Self::Remote(name) => write!(f, "remote:{name}"),
         }
     }
 }
@@ -73,6 +83,7 @@ pub struct ReleaseTag {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum RemoteLoadStatus {
     NotAttempted,
     UsedRemote,
@@ -89,6 +100,72 @@ impl fmt::Display for RemoteLoadStatus {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum RemoteFailureKind {
+    Auth,
+    Network,
+    MissingRemote,
+    MissingRefspec,
+    Other,
+}
+
+impl fmt::Display for RemoteFailureKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Auth => write!(f, "auth"),
+            Self::Network => write!(f, "network"),
+            Self::MissingRemote => write!(f, "missing-remote"),
+            Self::MissingRefspec => write!(f, "missing-refspec"),
+            Self::Other => write!(f, "other"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct RemoteFailure {
+    pub kind: RemoteFailureKind,
+    pub stderr: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct RemoteOutcome {
+    pub name: String,
+    pub status: RemoteLoadStatus,
+    pub failure: Option<RemoteFailure>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct TagConflict {
+    pub name: String,
+    pub local_commit: String,
+    pub remote_commit: String,
+    pub kept: TagSource,
+}
+
+impl fmt::Display for TagConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} local={} remote={} kept={}",
+            self.name, self.local_commit, self.remote_commit, self.kept
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ReleaseInventory {
+    pub releases: Vec<ReleaseTag>,
+    pub remote_status: RemoteLoadStatus,
+    pub remote_failure: Option<RemoteFailure>,
+    pub remotes: Vec<RemoteOutcome>,
+    pub conflicts: Vec<TagConflict>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AnchorSelection {
     pub release: ReleaseTag,
@@ -96,28 +173,156 @@ pub struct AnchorSelection {
     pub anchor_commit: CommitInfo,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[non_exhaustive]
+pub enum AnchorKind {
+    #[default]
+    Tag,
+    RootFallback,
+    FirstParentRootFallback,
+}
+
+impl fmt::Display for AnchorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tag => write!(f, "tag"),
+            Self::RootFallback => write!(f, "root-fallback"),
+            Self::FirstParentRootFallback => write!(f, "first-parent-root-fallback"),
+        }
+    }
+}
+
+/// Why a cache entry was discarded and rewritten.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum CacheInvalidKind {
+    /// Missing or unparsable header, or a payload that does not deserialize.
+    Corrupt,
+    /// Written with a different cache format version.
+    FormatVersion,
+    /// Written by a different tidemark version.
+    ToolVersion,
+    /// Payload does not match the header checksum (e.g. a truncated write).
+    Checksum,
+}
+
+impl fmt::Display for CacheInvalidKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Corrupt => write!(f, "corrupt"),
+            Self::FormatVersion => write!(f, "format-version"),
+            Self::ToolVersion => write!(f, "tool-version"),
+            Self::Checksum => write!(f, "checksum"),
+        }
+    }
+}
+
+/// How the result cache was used for one resolution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[non_exhaustive]
+pub enum CacheStatus {
+    /// Remote refresh was required, so the cache was not consulted.
+    #[default]
+    Bypassed,
+    Disabled,
+    Hit,
+    /// Served by the shared `cache.remote` tier after a local miss.
+    RemoteHit,
+    Miss,
+    /// An unusable entry was treated as a miss and replaced.
+    Rewritten(CacheInvalidKind),
+}
+
+impl fmt::Display for CacheStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bypassed => write!(f, "bypassed"),
+            Self::Disabled => write!(f, "disabled"),
+            Self::Hit => write!(f, "hit"),
+            Self::RemoteHit => write!(f, "remote-hit"),
+            Self::Miss => write!(f, "miss"),
+            Self::Rewritten(_) => write!(f, "rewritten"),
+        }
+    }
+}
+
+/// How the persistent ancestry index produced the same-day commit index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[non_exhaustive]
+pub enum IndexStatus {
+    /// Anchor is the target, first-parent history, or the cache is disabled.
+    #[default]
+    Unused,
+    /// Target was already indexed; no history walk.
+    Hit,
+    /// Only commits newer than the index were walked.
+    Extended { new_commits: u32 },
+}
+
+impl fmt::Display for IndexStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unused => write!(f, "unused"),
+            Self::Hit => write!(f, "hit"),
+            Self::Extended { .. } => write!(f, "extended"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct MarkExplain {
     pub version: VersionCoordinate,
     pub target_commit: CommitInfo,
     pub anchor_tag: String,
+    #[serde(default)]
+    pub anchor_kind: AnchorKind,
     pub anchor_commit: CommitInfo,
     pub day_delta: u32,
     pub commit_index: u32,
+    #[serde(default)]
+    pub history: HistoryMode,
     pub timezone: String,
     pub remote_status: RemoteLoadStatus,
+    #[serde(default)]
+    pub remote_failure: Option<RemoteFailure>,
+    #[serde(default)]
+    pub remotes: Vec<RemoteOutcome>,
+    #[serde(default)]
+    pub tag_conflicts: Vec<TagConflict>,
+    #[serde(default)]
+    pub shallow: bool,
+    #[serde(default)]
+    pub deepen_rounds: u32,
     pub branch: Option<String>,
+    #[serde(default)]
+    pub cache: CacheStatus,
+    #[serde(default)]
+    pub ancestry_index: IndexStatus,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct MarkResult {
     pub coordinate: VersionCoordinate,
     pub explain: MarkExplain,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct FileResult {
     pub path: String,
     pub last_commit: CommitInfo,
     pub mark: MarkResult,
 }
+
+/// Commits that resolve to one `x.y.z` coordinate, oldest first; several when sibling branches
+/// share a day and ordinal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct LocateResult {
+    /// Normalized `x.y.z` that was searched for.
+    pub coordinate: String,
+    pub anchor_tag: String,
+    pub commits: Vec<CommitInfo>,
+}
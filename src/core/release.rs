@@ -14,13 +14,21 @@
 //! Email: silan.hu@u.nus.edu
 //! Copyright (c) 2026-2027 easynet. All rights reserved.
 
-use std::{cmp::Ordering, collections::BTreeMap};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+};
+
+use sha2::{Digest, Sha256};
 
 use crate::{
-    config::{RemoteStrategy, TideConfig},
-    core::model::{AnchorSelection, CommitInfo, ReleaseTag, RemoteLoadStatus, TagRef},
+    config::{ConflictPolicy, HistoryMode, RemoteStrategy, TideConfig},
+    core::model::{
+        AnchorSelection, CommitInfo, ReleaseInventory, ReleaseTag, RemoteFailure,
+        RemoteFailureKind, RemoteLoadStatus, RemoteOutcome, TagConflict, TagRef, TagSource,
+    },
     error::{TideError, TideResult},
-    infra::git::GitProvider,
+    infra::git::{GitProvider, ReachableCommit},
 };
 
 /// Extract a sortable semver key from a tag name (e.g. "v0.1.0" → (0,1,0), "v3" → (3,0,0)).
@@ -43,8 +51,8 @@ pub fn parse_sort_key(tag_name: &str, prefix: &str) -> TideResult<(u64, u64, u64
         }
     };
 
-    let major = parse_part(parts.first().unwrap_or(&""))
-        .ok_or_else(|| TideError::InvalidReleaseTag {
+    let major =
+        parse_part(parts.first().unwrap_or(&"")).ok_or_else(|| TideError::InvalidReleaseTag {
             tag: tag_name.to_string(),
             prefix: prefix.to_string(),
         })?;
@@ -58,53 +66,70 @@ pub fn load_release_tags(
     git: &dyn GitProvider,
     config: &TideConfig,
     local_only: bool,
-) -> TideResult<(Vec<ReleaseTag>, RemoteLoadStatus)> {
+    require_remote: bool,
+) -> TideResult<ReleaseInventory> {
     let mut by_name: BTreeMap<String, TagRef> = BTreeMap::new();
     for tag in git.list_local_tags(config.release.tag_prefix.as_str())? {
         by_name.insert(tag.name.clone(), tag);
     }
 
-    let mut remote_status = RemoteLoadStatus::NotAttempted;
-    let should_attempt_remote = !local_only && config.remote.strategy == RemoteStrategy::LsRemote;
+    let mut remotes = Vec::new();
+    let mut conflicts = Vec::new();
+    let should_attempt_remote = !local_only && config.remote.strategy != RemoteStrategy::LocalOnly;
     if should_attempt_remote {
-        match git.list_remote_tags(
-            config.remote.name.as_str(),
-            config.release.tag_prefix.as_str(),
-        ) {
-            Ok(remote_tags) => {
-                remote_status = RemoteLoadStatus::UsedRemote;
-                for tag in remote_tags {
-                    // In remote mode, same-name remote tags override local tags so the
-                    // coordinate reflects the latest remote definition.
-                    by_name.insert(tag.name.clone(), tag);
+        let prefix = config.release.tag_prefix.as_str();
+        // Remotes are listed in priority order: the first remote to define a tag name wins.
+        let mut remote_tags: BTreeMap<String, TagRef> = BTreeMap::new();
+        for remote_name in config.remote.effective_names() {
+            let listed = if config.remote.strategy == RemoteStrategy::Fetch {
+                git.fetch_remote_tags(remote_name.as_str(), prefix)
+            } else {
+                git.list_remote_tags(remote_name.as_str(), prefix)
+            };
+            match listed {
+                Ok(tags) => {
+                    for tag in tags {
+                        remote_tags.entry(tag.name.clone()).or_insert(tag);
+                    }
+                    remotes.push(RemoteOutcome {
+                        name: remote_name,
+                        status: RemoteLoadStatus::UsedRemote,
+                        failure: None,
+                    });
                 }
-            }
-            Err(err) => {
-                if config.remote.fallback_to_local {
-                    remote_status = RemoteLoadStatus::FallbackLocal;
-                    let _ = err;
-                } else {
-                    return Err(err);
+                Err(err) => {
+                    if config.remote.fallback_for(remote_name.as_str()) && !require_remote {
+                        remotes.push(RemoteOutcome {
+                            name: remote_name,
+                            status: RemoteLoadStatus::FallbackLocal,
+                            failure: Some(remote_failure_from_error(err)),
+                        });
+                    } else {
+                        return Err(err);
+                    }
                 }
             }
         }
+
+        for tag in remote_tags.into_values() {
+            merge_remote_tag(&mut by_name, &mut conflicts, tag, config.remote.on_conflict)?;
+        }
     }
 
+    let remote_status = summarize_remote_status(remotes.as_slice());
+    let remote_failure = remotes.iter().find_map(|remote| remote.failure.clone());
+
     let mut keyed: Vec<((u64, u64, u64), TagRef)> = Vec::new();
     for tag in by_name.into_values() {
         if config.release.require_annotated_tags && !tag.is_annotated {
             continue;
         }
 
-        let sort_key =
-            parse_sort_key(tag.name.as_str(), config.release.tag_prefix.as_str())?;
+        let sort_key = parse_sort_key(tag.name.as_str(), config.release.tag_prefix.as_str())?;
         keyed.push((sort_key, tag));
     }
 
-    keyed.sort_by(|a, b| {
-        a.0.cmp(&b.0)
-            .then_with(|| a.1.name.cmp(&b.1.name))
-    });
+    keyed.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.name.cmp(&b.1.name)));
 
     let releases: Vec<ReleaseTag> = keyed
         .into_iter()
@@ -115,32 +140,156 @@ pub fn load_release_tags(
         })
         .collect();
 
-    Ok((releases, remote_status))
+    Ok(ReleaseInventory {
+        releases,
+        remote_status,
+        remote_failure,
+        remotes,
+        conflicts,
+    })
+}
+
+/// Order-independent digest of eligible release tag names and peeled commits. Cache keys
+/// include it so adding, deleting, or moving a tag invalidates cached coordinates.
+pub fn release_set_digest(releases: &[ReleaseTag]) -> String {
+    let mut pairs: Vec<(&str, &str)> = releases
+        .iter()
+        .map(|release| (release.tag.name.as_str(), release.tag.commit_id.as_str()))
+        .collect();
+    pairs.sort_unstable();
+
+    let mut hasher = Sha256::new();
+    for (name, commit) in pairs {
+        hasher.update(name.as_bytes());
+        hasher.update([0u8]);
+        hasher.update(commit.as_bytes());
+        hasher.update([b'\n']);
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Overall status: any per-remote fallback degrades the whole inventory to `fallback-local`.
+fn summarize_remote_status(remotes: &[RemoteOutcome]) -> RemoteLoadStatus {
+    if remotes.is_empty() {
+        RemoteLoadStatus::NotAttempted
+    } else if remotes
+        .iter()
+        .any(|remote| remote.status == RemoteLoadStatus::FallbackLocal)
+    {
+        RemoteLoadStatus::FallbackLocal
+    } else {
+        RemoteLoadStatus::UsedRemote
+    }
+}
+
+fn remote_failure_from_error(err: TideError) -> RemoteFailure {
+    match err {
+        TideError::RemoteRefresh { kind, stderr, .. } => RemoteFailure { kind, stderr },
+        other => RemoteFailure {
+            kind: RemoteFailureKind::Other,
+            stderr: other.to_string(),
+        },
+    }
+}
+
+/// Merge one remote tag into the name-keyed inventory, recording a conflict whenever a
+/// same-name local tag peels to a different commit.
+fn merge_remote_tag(
+    by_name: &mut BTreeMap<String, TagRef>,
+    conflicts: &mut Vec<TagConflict>,
+    tag: TagRef,
+    policy: ConflictPolicy,
+) -> TideResult<()> {
+    let Some(local) = by_name.get(tag.name.as_str()) else {
+        by_name.insert(tag.name.clone(), tag);
+        return Ok(());
+    };
+    if local.commit_id == tag.commit_id {
+        // Same target: the remote definition wins so annotation status reflects upstream.
+        by_name.insert(tag.name.clone(), tag);
+        return Ok(());
+    }
+
+    if policy == ConflictPolicy::Error {
+        return Err(TideError::ReleaseTagConflict {
+            tag: tag.name,
+            local_commit: local.commit_id.clone(),
+            remote_commit: tag.commit_id,
+        });
+    }
+
+    let keep_local = policy == ConflictPolicy::LocalWins;
+    conflicts.push(TagConflict {
+        name: tag.name.clone(),
+        local_commit: local.commit_id.clone(),
+        remote_commit: tag.commit_id.clone(),
+        kept: if keep_local {
+            local.source.clone()
+        } else {
+            tag.source.clone()
+        },
+    });
+    if !keep_local {
+        by_name.insert(tag.name.clone(), tag);
+    }
+    Ok(())
 }
 
+/// Fetch the tag objects of remote `releases` whose commits are missing locally, one fetch per
+/// remote. Listing remote tags never fetches, so callers do this only where ancestry is checked.
+pub fn fetch_missing_commits<'a>(
+    git: &dyn GitProvider,
+    releases: impl IntoIterator<Item = &'a ReleaseTag>,
+) -> TideResult<()> {
+    let mut missing: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for release in releases {
+        let TagSource::Remote(remote) = &release.tag.source else {
+            continue;
+        };
+        if !git.commit_exists(release.tag.commit_id.as_str())? {
+            missing
+                .entry(remote.as_str())
+                .or_default()
+                .push(release.tag.name.clone());
+        }
+    }
+    for (remote, tags) in missing {
+        git.fetch_tag_objects(remote, &tags)?;
+    }
+    Ok(())
+}
+
+/// Nearest reachable release tag to `target`; every tag's reachability and distance comes from
+/// one bulk provider query.
 pub fn select_anchor(
     git: &dyn GitProvider,
     releases: &[ReleaseTag],
     target: &CommitInfo,
     prefix: &str,
+    mode: HistoryMode,
 ) -> TideResult<AnchorSelection> {
-    let mut selected: Option<AnchorSelection> = None;
+    let candidates: Vec<String> = releases
+        .iter()
+        .map(|release| release.tag.commit_id.clone())
+        .collect();
+    let reachable: HashMap<String, ReachableCommit> = git
+        .reachable_with_distances(target.id.as_str(), &candidates)?
+        .into_iter()
+        .map(|hit| (hit.commit.id.clone(), hit))
+        .collect();
 
+    let mut selected: Option<AnchorSelection> = None;
     for release in releases {
-        if !git.commit_exists(release.tag.commit_id.as_str())? {
+        let Some(hit) = reachable.get(release.tag.commit_id.as_str()) else {
             continue;
-        }
-
-        if !git.is_ancestor(release.tag.commit_id.as_str(), target.id.as_str())? {
-            continue;
-        }
-
-        let distance = git.commit_distance(release.tag.commit_id.as_str(), target.id.as_str())?;
-        let anchor_commit = git.resolve_commit(release.tag.commit_id.as_str())?;
+        };
         let candidate = AnchorSelection {
             release: release.clone(),
-            distance,
-            anchor_commit,
+            distance: match mode {
+                HistoryMode::AncestryPath => hit.distance,
+                HistoryMode::FirstParent => hit.first_parent_distance,
+            },
+            anchor_commit: hit.commit.clone(),
         };
 
         if is_better_anchor(selected.as_ref(), &candidate) {
@@ -172,6 +321,27 @@ fn compare_anchor(left: &AnchorSelection, right: &AnchorSelection) -> Ordering {
 mod tests {
     use super::*;
 
+    #[test]
+    fn remote_status_summary_degrades_on_any_fallback() {
+        let outcome = |status| RemoteOutcome {
+            name: "origin".to_string(),
+            status,
+            failure: None,
+        };
+        assert_eq!(summarize_remote_status(&[]), RemoteLoadStatus::NotAttempted);
+        assert_eq!(
+            summarize_remote_status(&[outcome(RemoteLoadStatus::UsedRemote)]),
+            RemoteLoadStatus::UsedRemote
+        );
+        assert_eq!(
+            summarize_remote_status(&[
+                outcome(RemoteLoadStatus::UsedRemote),
+                outcome(RemoteLoadStatus::FallbackLocal),
+            ]),
+            RemoteLoadStatus::FallbackLocal
+        );
+    }
+
     #[test]
     fn parse_sort_key_from_default_prefix() {
         assert_eq!(parse_sort_key("v1", "v").unwrap(), (1, 0, 0));
@@ -179,4 +349,64 @@ mod tests {
         assert_eq!(parse_sort_key("v12.3.4", "v").unwrap(), (12, 3, 4));
         assert!(parse_sort_key("v", "v").is_err());
     }
+
+    fn tag(name: &str, commit: &str, source: TagSource) -> TagRef {
+        TagRef {
+            name: name.to_string(),
+            commit_id: commit.to_string(),
+            is_annotated: true,
+            source,
+        }
+    }
+
+    #[test]
+    fn conflict_policy_controls_merge_winner() {
+        for (policy, expected) in [
+            (ConflictPolicy::RemoteWins, "b"),
+            (ConflictPolicy::Warn, "b"),
+            (ConflictPolicy::LocalWins, "a"),
+        ] {
+            let mut by_name = BTreeMap::new();
+            by_name.insert("v1".to_string(), tag("v1", "a", TagSource::Local));
+            let mut conflicts = Vec::new();
+            merge_remote_tag(
+                &mut by_name,
+                &mut conflicts,
+                tag("v1", "b", TagSource::Remote("origin".to_string())),
+                policy,
+            )
+            .unwrap();
+            assert_eq!(by_name["v1"].commit_id, expected);
+            assert_eq!(conflicts.len(), 1);
+        }
+
+        let mut by_name = BTreeMap::new();
+        by_name.insert("v1".to_string(), tag("v1", "a", TagSource::Local));
+        let err = merge_remote_tag(
+            &mut by_name,
+            &mut Vec::new(),
+            tag("v1", "b", TagSource::Remote("origin".to_string())),
+            ConflictPolicy::Error,
+        )
+        .unwrap_err();
+        assert!(matches!(err, TideError::ReleaseTagConflict { .. }));
+    }
+
+    #[test]
+    fn release_set_digest_tracks_names_and_commits_only() {
+        let release = |name: &str, commit: &str| ReleaseTag {
+            anchor_value: 1,
+            tag: tag(name, commit, TagSource::Local),
+        };
+        let base = release_set_digest(&[release("v1", "a"), release("v2", "b")]);
+        assert_eq!(
+            base,
+            release_set_digest(&[release("v2", "b"), release("v1", "a")])
+        );
+        assert_ne!(
+            base,
+            release_set_digest(&[release("v1", "c"), release("v2", "b")])
+        );
+        assert_ne!(base, release_set_digest(&[release("v1", "a")]));
+    }
 }
@@ -6,6 +6,7 @@
 //!
 //! Responsibility:
 //! - Map a tracked path to its last modifying commit and then compute its version coordinate.
+//! - Expand directories and glob patterns and resolve many files from one history walk.
 //!
 //! Architectural Position:
 //! - Core resolver adapter that composes file history lookup with mark semantics.
@@ -14,26 +15,40 @@
 //! Email: silan.hu@u.nus.edu
 //! Copyright (c) 2026-2027 easynet. All rights reserved.
 
-use std::path::PathBuf;
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    path::{Component, Path, PathBuf},
+};
 
 use serde::Serialize;
 
 use crate::{
-    config::{RemoteStrategy, TideConfig},
+    config::{HistoryMode, MissingAnchorPolicy, RemoteStrategy, TideConfig},
     core::{
-        model::FileResult,
-        resolver::mark::{MarkRequest, resolve_mark},
+        branch::{self, BranchContext},
+        model::{CacheStatus, CommitInfo, FileResult, MarkResult, ReleaseInventory, TagConflict},
+        release,
+        resolver::mark::{
+            apply_metadata_suffix, initial_cache_status, metadata_template, resolve_target,
+        },
+        suffix::SuffixTemplate,
+        time::TimezonePolicy,
+    },
+    error::{TideError, TideResult},
+    infra::{
+        cache::{CacheLookup, CacheStore},
+        git::GitProvider,
     },
-    error::TideResult,
-    infra::{cache::CacheStore, git::GitProvider},
 };
 
-const FILE_CACHE_NAMESPACE: &str = "file";
+pub const FILE_CACHE_NAMESPACE: &str = "file";
 
 #[derive(Debug, Clone)]
 pub struct FileRequest {
     pub path: PathBuf,
     pub local_only: bool,
+    pub require_remote: bool,
     pub metadata_suffix: Option<String>,
 }
 
@@ -42,11 +57,15 @@ struct FileCacheKey<'a> {
     head_commit: &'a str,
     path: &'a str,
     local_only: bool,
-    metadata_suffix: Option<&'a str>,
     follow_renames: bool,
     timezone: &'a str,
     tag_prefix: &'a str,
     require_annotated_tags: bool,
+    on_missing_anchor: MissingAnchorPolicy,
+    history: HistoryMode,
+    branch: Option<&'a str>,
+    channel: Option<&'a str>,
+    release_tags: &'a str,
 }
 
 pub fn resolve_file(
@@ -55,70 +74,296 @@ pub fn resolve_file(
     cache: &CacheStore,
     req: FileRequest,
 ) -> TideResult<FileResult> {
-    let head_commit = git.head_commit()?;
-    let normalized_path = if req.path.is_absolute() {
-        req.path
-            .strip_prefix(git.repo_root())
-            .unwrap_or(req.path.as_path())
-            .to_path_buf()
-    } else {
-        req.path.clone()
+    let scope = FileScope::open(
+        git,
+        config,
+        cache,
+        req.local_only,
+        req.require_remote,
+        req.metadata_suffix,
+    )?;
+    let path_text = repo_relative(git, req.path.as_path())
+        .to_string_lossy()
+        .to_string();
+    let (cache_key, cache_status) = match scope.lookup(path_text.as_str())? {
+        Lookup::Cached(result) => return Ok(*result),
+        Lookup::Miss { key, status } => (key, status),
     };
-    let path_text = normalized_path.to_string_lossy().to_string();
-
-    let metadata = req
-        .metadata_suffix
-        .clone()
-        .or_else(|| config.output.metadata_suffix.clone())
-        .map(|v| v.trim().to_string())
-        .filter(|v| !v.is_empty());
-    let bypass_cache = requires_remote_refresh(config, req.local_only);
-
-    let mut cache_key = None;
-    if !bypass_cache {
+    let last_commit =
+        git.last_modifying_commit(Path::new(path_text.as_str()), config.output.follow_renames)?;
+    scope.finish(path_text, last_commit, cache_key, cache_status)
+}
+
+/// Files, directories, and glob patterns to resolve together.
+#[derive(Debug, Clone)]
+pub struct FilesRequest {
+    /// Repository-relative (or absolute) paths. Directories recurse into their tracked files;
+    /// patterns containing `*`, `?`, or `[` match tracked files as git glob pathspecs (`*` stays
+    /// within one directory, `**` crosses directories).
+    pub patterns: Vec<String>,
+    pub local_only: bool,
+    pub require_remote: bool,
+    pub metadata_suffix: Option<String>,
+}
+
+/// Resolve every file matched by `req.patterns`, sorted by path. Release tags are listed once;
+/// cache misses share one history walk for their last-modifying commits and one mark resolution
+/// per distinct commit.
+///
+/// A plain file path without history fails with `FileHistoryNotFound`; files reached through a
+/// directory or pattern that have never been committed are skipped.
+pub fn resolve_files(
+    git: &dyn GitProvider,
+    config: &TideConfig,
+    cache: &CacheStore,
+    req: FilesRequest,
+) -> TideResult<Vec<FileResult>> {
+    let targets = expand_patterns(git, &req.patterns)?;
+    let scope = FileScope::open(
+        git,
+        config,
+        cache,
+        req.local_only,
+        req.require_remote,
+        req.metadata_suffix,
+    )?;
+
+    let mut results = Vec::with_capacity(targets.len());
+    let mut misses = Vec::new();
+    for (path, required) in targets {
+        match scope.lookup(path.to_string_lossy().as_ref())? {
+            Lookup::Cached(result) => results.push(*result),
+            Lookup::Miss { key, status } => misses.push((path, required, key, status)),
+        }
+    }
+
+    if !misses.is_empty() {
+        let paths: Vec<PathBuf> = misses.iter().map(|(path, ..)| path.clone()).collect();
+        let mut last_commits = git.last_modifying_commits(&paths, config.output.follow_renames)?;
+        for (path, required, key, status) in misses {
+            let Some(last_commit) = last_commits.remove(&path) else {
+                if required {
+                    return Err(TideError::FileHistoryNotFound { path });
+                }
+                continue;
+            };
+            let path_text = path.to_string_lossy().to_string();
+            results.push(scope.finish(path_text, last_commit, key, status)?);
+        }
+    }
+    results.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(results)
+}
+
+/// Tag conflicts reported by any of `files`, each once, in first-seen order.
+pub fn tag_conflicts(files: &[FileResult]) -> Vec<TagConflict> {
+    let mut conflicts: Vec<TagConflict> = Vec::new();
+    for conflict in files
+        .iter()
+        .flat_map(|file| &file.mark.explain.tag_conflicts)
+    {
+        if !conflicts.contains(conflict) {
+            conflicts.push(conflict.clone());
+        }
+    }
+    conflicts
+}
+
+/// Per-invocation state shared by every path: HEAD, branch, release set, and suffix template.
+struct FileScope<'a> {
+    git: &'a dyn GitProvider,
+    config: &'a TideConfig,
+    cache: &'a CacheStore,
+    local_only: bool,
+    template: Option<SuffixTemplate>,
+    head_commit: CommitInfo,
+    branch: BranchContext,
+    /// Listed once per scope and shared by every mark resolved in it.
+    inventory: ReleaseInventory,
+    /// Canonical offset of the configured timezone, so `local` keys differ per zone.
+    timezone: String,
+    /// `None` when a remote refresh is required, which bypasses the cache.
+    release_tags: Option<String>,
+    /// Marks already resolved in this scope, by target commit.
+    marks: RefCell<HashMap<String, MarkResult>>,
+}
+
+enum Lookup {
+    Cached(Box<FileResult>),
+    Miss {
+        key: Option<String>,
+        status: CacheStatus,
+    },
+}
+
+impl<'a> FileScope<'a> {
+    fn open(
+        git: &'a dyn GitProvider,
+        config: &'a TideConfig,
+        cache: &'a CacheStore,
+        local_only: bool,
+        require_remote: bool,
+        metadata_suffix: Option<String>,
+    ) -> TideResult<Self> {
+        let head_commit = git.head_commit()?;
+        let template = metadata_template(metadata_suffix, config)?;
+        let branch = branch::resolve_branch(git, &config.branch)?;
+        let timezone = TimezonePolicy::parse(config.time.timezone.as_str())?.canonical_name();
+        let inventory = release::load_release_tags(git, config, local_only, require_remote)?;
+        let release_tags = (!requires_remote_refresh(config, local_only))
+            .then(|| release::release_set_digest(&inventory.releases));
+        Ok(Self {
+            git,
+            config,
+            cache,
+            local_only,
+            template,
+            head_commit,
+            branch,
+            inventory,
+            timezone,
+            release_tags,
+            marks: RefCell::default(),
+        })
+    }
+
+    fn lookup(&self, path_text: &str) -> TideResult<Lookup> {
+        let mut status = initial_cache_status(self.cache, self.release_tags.is_none());
+        let Some(release_tags) = self.release_tags.as_deref() else {
+            return Ok(Lookup::Miss { key: None, status });
+        };
+        let config = self.config;
         let key_payload = FileCacheKey {
-            head_commit: head_commit.id.as_str(),
-            path: path_text.as_str(),
-            local_only: req.local_only,
-            metadata_suffix: metadata.as_deref(),
+            head_commit: self.head_commit.id.as_str(),
+            path: path_text,
+            local_only: self.local_only,
             follow_renames: config.output.follow_renames,
-            timezone: config.time.timezone.as_str(),
+            timezone: self.timezone.as_str(),
             tag_prefix: config.release.tag_prefix.as_str(),
             require_annotated_tags: config.release.require_annotated_tags,
+            on_missing_anchor: config.release.on_missing_anchor,
+            history: config.coordinate.history,
+            branch: self.branch.name.as_deref(),
+            channel: self.branch.channel.as_deref(),
+            release_tags,
         };
 
-        let resolved_key = CacheStore::key_from_serializable(FILE_CACHE_NAMESPACE, &key_payload)?;
-        if let Some(cached) =
-            cache.get::<FileResult>(FILE_CACHE_NAMESPACE, resolved_key.as_str())?
+        let key = CacheStore::key_from_serializable(FILE_CACHE_NAMESPACE, &key_payload)?;
+        let (mut cached, hit) = match self
+            .cache
+            .get::<FileResult>(FILE_CACHE_NAMESPACE, key.as_str())?
         {
-            return Ok(cached);
+            CacheLookup::Hit(cached) => (cached, CacheStatus::Hit),
+            CacheLookup::RemoteHit(cached) => (cached, CacheStatus::RemoteHit),
+            CacheLookup::Miss => {
+                return Ok(Lookup::Miss {
+                    key: Some(key),
+                    status,
+                });
+            }
+            CacheLookup::Invalid(kind) => {
+                status = CacheStatus::Rewritten(kind);
+                return Ok(Lookup::Miss {
+                    key: Some(key),
+                    status,
+                });
+            }
+        };
+        cached.mark.explain.cache = hit;
+        apply_metadata_suffix(self.git, config, self.template.as_ref(), &mut cached.mark)?;
+        Ok(Lookup::Cached(Box::new(cached)))
+    }
+
+    /// Resolve the mark of `last_commit`, store the entry under `key`, and render the suffix.
+    fn finish(
+        &self,
+        path_text: String,
+        last_commit: CommitInfo,
+        key: Option<String>,
+        status: CacheStatus,
+    ) -> TideResult<FileResult> {
+        let known = self.marks.borrow().get(&last_commit.id).cloned();
+        let mark = match known {
+            Some(mark) => mark,
+            None => {
+                let mark = resolve_target(
+                    self.git,
+                    self.config,
+                    self.cache,
+                    last_commit.clone(),
+                    self.local_only,
+                    &self.branch,
+                    &self.inventory,
+                )?;
+                self.marks
+                    .borrow_mut()
+                    .insert(last_commit.id.clone(), mark.clone());
+                mark
+            }
+        };
+
+        let mut result = FileResult {
+            path: path_text,
+            last_commit,
+            mark,
+        };
+        result.mark.explain.cache = status;
+        if let Some(key) = key.as_deref() {
+            self.cache.put(FILE_CACHE_NAMESPACE, key, &result)?;
         }
-        cache_key = Some(resolved_key);
+        apply_metadata_suffix(
+            self.git,
+            self.config,
+            self.template.as_ref(),
+            &mut result.mark,
+        )?;
+        Ok(result)
     }
+}
 
-    let last_commit = git.last_modifying_commit(&normalized_path, config.output.follow_renames)?;
-    let mark = resolve_mark(
-        git,
-        config,
-        cache,
-        MarkRequest {
-            target_rev: Some(last_commit.id.clone()),
-            local_only: req.local_only,
-            metadata_suffix: metadata,
-        },
-    )?;
+/// Expand patterns into repository-relative files, each flagged `true` when it was named
+/// directly (and so must have history).
+fn expand_patterns(
+    git: &dyn GitProvider,
+    patterns: &[String],
+) -> TideResult<BTreeMap<PathBuf, bool>> {
+    let mut targets = BTreeMap::new();
+    let mut pathspecs = Vec::new();
+    for pattern in patterns {
+        let path = repo_relative(git, Path::new(pattern.as_str()));
+        let text = path.to_string_lossy().to_string();
+        if pattern.contains(['*', '?', '[']) {
+            pathspecs.push(format!(":(glob){text}"));
+        } else if text.is_empty() || git.repo_root().join(&path).is_dir() {
+            pathspecs.push(format!(
+                ":(literal){}",
+                if text.is_empty() { "." } else { text.as_str() }
+            ));
+        } else {
+            targets.insert(path, true);
+        }
+    }
+    if !pathspecs.is_empty() {
+        for path in git.tracked_files(&pathspecs)? {
+            targets.entry(path).or_insert(false);
+        }
+    }
+    Ok(targets)
+}
 
-    let result = FileResult {
-        path: path_text,
-        last_commit,
-        mark,
+/// `path` relative to the repository root, without `.` components.
+fn repo_relative(git: &dyn GitProvider, path: &Path) -> PathBuf {
+    let relative = if path.is_absolute() {
+        path.strip_prefix(git.repo_root()).unwrap_or(path)
+    } else {
+        path
     };
-    if let Some(key) = cache_key.as_deref() {
-        cache.put(FILE_CACHE_NAMESPACE, key, &result)?;
-    }
-    Ok(result)
+    relative
+        .components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect()
 }
 
 fn requires_remote_refresh(config: &TideConfig, local_only: bool) -> bool {
-    !local_only && config.remote.strategy == RemoteStrategy::LsRemote
+    !local_only && config.remote.strategy != RemoteStrategy::LocalOnly
 }
@@ -0,0 +1,152 @@
+//! TideMark
+//! ========
+//!
+//! File: src/core/resolver/locate.rs
+//! Description: Reverse lookup from a version coordinate to the commits that resolve to it.
+//!
+//! Responsibility:
+//! - Find the anchor tag for `x`, narrow its descendants to day `y`, and keep the candidates whose own mark is `x.y.z`.
+//!
+//! Architectural Position:
+//! - Core resolver layer; inverse of the mark resolver and verified through it.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+use crate::{
+    config::TideConfig,
+    core::{
+        branch, history,
+        model::{CommitInfo, LocateResult},
+        release,
+        resolver::mark::resolve_target,
+        time::TimezonePolicy,
+    },
+    error::{TideError, TideResult},
+    infra::{cache::CacheStore, git::GitProvider},
+};
+
+#[derive(Debug, Clone)]
+pub struct LocateRequest {
+    /// `x.y.z`, optionally followed by a `-<channel>` or `.<metadata>` suffix, which is ignored.
+    pub coordinate: String,
+    /// Search commits reachable from this revision (default: `HEAD`).
+    pub from_rev: Option<String>,
+    pub local_only: bool,
+    pub require_remote: bool,
+}
+
+pub fn resolve_locate(
+    git: &dyn GitProvider,
+    config: &TideConfig,
+    cache: &CacheStore,
+    req: LocateRequest,
+) -> TideResult<LocateResult> {
+    let (x, y, z) = parse_coordinate(req.coordinate.as_str())?;
+    let coordinate = format!("{x}.{y}.{z}");
+    let from_rev = req.from_rev.clone().unwrap_or_else(|| "HEAD".to_string());
+    let not_found = || TideError::CoordinateNotFound {
+        coordinate: coordinate.clone(),
+        rev: from_rev.clone(),
+    };
+
+    let inventory = release::load_release_tags(git, config, req.local_only, req.require_remote)?;
+    let release = inventory
+        .releases
+        .iter()
+        .find(|release| release.anchor_value == x)
+        .ok_or_else(not_found)?;
+    if !git.commit_exists(release.tag.commit_id.as_str())? {
+        // Only a shallow clone can be missing an ancestor of the tip.
+        if !git.shallow_boundaries()?.is_empty() {
+            release::fetch_missing_commits(git, [release])?;
+        }
+        if !git.commit_exists(release.tag.commit_id.as_str())? {
+            return Err(not_found());
+        }
+    }
+    let anchor = git.resolve_commit(release.tag.commit_id.as_str())?;
+    let tip = git.resolve_commit(from_rev.as_str())?;
+    if !git.is_ancestor(anchor.id.as_str(), tip.id.as_str())? {
+        return Err(not_found());
+    }
+
+    // Only commits on day `y` can match; each is confirmed by resolving its own mark against the
+    // inventory loaded above, which also rejects commits that a later tag anchors instead.
+    let branch = branch::resolve_branch(git, &config.branch)?;
+    let timezone = TimezonePolicy::parse(config.time.timezone.as_str())?;
+    let mut candidates = vec![anchor.clone()];
+    candidates.extend(history::path_commits(
+        git,
+        config.coordinate.history,
+        anchor.id.as_str(),
+        tip.id.as_str(),
+    )?);
+    let mut commits: Vec<CommitInfo> = Vec::new();
+    for candidate in candidates {
+        if timezone.day_delta(anchor.timestamp, candidate.timestamp)? != i64::from(y) {
+            continue;
+        }
+        let mark = resolve_target(
+            git,
+            config,
+            cache,
+            candidate.clone(),
+            req.local_only,
+            &branch,
+            &inventory,
+        )?;
+        let found = &mark.coordinate;
+        if (found.x, found.y, found.z) == (x, y, z) {
+            commits.push(candidate);
+        }
+    }
+    if commits.is_empty() {
+        return Err(not_found());
+    }
+    commits.sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then_with(|| a.id.cmp(&b.id)));
+
+    Ok(LocateResult {
+        coordinate,
+        anchor_tag: release.tag.name.clone(),
+        commits,
+    })
+}
+
+/// Leading `x.y.z` of a rendered coordinate.
+fn parse_coordinate(raw: &str) -> TideResult<(u64, u32, u32)> {
+    let invalid = || TideError::InvalidCoordinate {
+        value: raw.to_string(),
+    };
+    let core = raw.trim().split('-').next().unwrap_or_default();
+    let mut parts = core.split('.');
+    let x = parts.next().and_then(|part| part.parse().ok());
+    let y = parts.next().and_then(|part| part.parse().ok());
+    let z = parts.next().and_then(|part| part.parse().ok());
+    match (x, y, z) {
+        (Some(x), Some(y), Some(z)) => Ok((x, y, z)),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coordinates_parse_with_channel_and_metadata_suffixes() {
+        assert_eq!(parse_coordinate("3.12.2").unwrap(), (3, 12, 2));
+        assert_eq!(parse_coordinate("3.12.2-feature-x").unwrap(), (3, 12, 2));
+        assert_eq!(parse_coordinate("3.12.2.build7").unwrap(), (3, 12, 2));
+        for bad in ["", "3.12", "v3.12.2", "3.x.2"] {
+            assert!(
+                matches!(
+                    parse_coordinate(bad),
+                    Err(TideError::InvalidCoordinate { .. })
+                ),
+                "{bad}"
+            );
+        }
+    }
+}
@@ -17,25 +17,33 @@
 use serde::Serialize;
 
 use crate::{
-    config::{RemoteStrategy, TideConfig},
+    config::{HistoryMode, MissingAnchorPolicy, RemoteStrategy, TideConfig},
     core::{
+        ancestry,
+        branch::{self, BranchContext},
+        history,
         model::{
-            AnchorSelection, CommitInfo, MarkExplain, MarkResult, ReleaseTag, TagRef, TagSource,
-            VersionCoordinate,
+            AnchorKind, AnchorSelection, CacheStatus, CommitInfo, IndexStatus, MarkExplain,
+            MarkResult, ReleaseInventory, ReleaseTag, TagRef, TagSource, VersionCoordinate,
         },
         release,
+        suffix::{SuffixContext, SuffixTemplate},
         time::TimezonePolicy,
     },
     error::{TideError, TideResult},
-    infra::{cache::CacheStore, git::GitProvider},
+    infra::{
+        cache::{CacheLookup, CacheStore},
+        git::GitProvider,
+    },
 };
 
-const MARK_CACHE_NAMESPACE: &str = "mark";
+pub const MARK_CACHE_NAMESPACE: &str = "mark";
 
 #[derive(Debug, Clone)]
 pub struct MarkRequest {
     pub target_rev: Option<String>,
     pub local_only: bool,
+    pub require_remote: bool,
     pub metadata_suffix: Option<String>,
 }
 
@@ -45,10 +53,15 @@ struct MarkCacheKey<'a> {
     local_only: bool,
     tag_prefix: &'a str,
     require_annotated_tags: bool,
-    timezone: &'a str,
+    on_missing_anchor: MissingAnchorPolicy,
+    history: HistoryMode,
+    /// Resolved offset rather than the configured name, so `local` keys differ per zone.
+    timezone: String,
     remote_strategy: &'a str,
-    remote_name: &'a str,
-    metadata_suffix: Option<&'a str>,
+    remote_names: Vec<String>,
+    branch: Option<&'a str>,
+    channel: Option<&'a str>,
+    release_tags: &'a str,
 }
 
 pub fn resolve_mark(
@@ -57,62 +70,135 @@ pub fn resolve_mark(
     cache: &CacheStore,
     req: MarkRequest,
 ) -> TideResult<MarkResult> {
-    let timezone = TimezonePolicy::parse(config.time.timezone.as_str())?;
+    let template = metadata_template(req.metadata_suffix.clone(), config)?;
+    let mut result = resolve_coordinate(git, config, cache, req)?;
+    apply_metadata_suffix(git, config, template.as_ref(), &mut result)?;
+    Ok(result)
+}
+
+/// Metadata template chosen by CLI argument over `output.metadata_suffix`; blank means none.
+pub fn metadata_template(
+    arg_suffix: Option<String>,
+    config: &TideConfig,
+) -> TideResult<Option<SuffixTemplate>> {
+    normalize_metadata_suffix(arg_suffix, &config.output.metadata_suffix)
+        .map(|raw| SuffixTemplate::parse(raw.as_str()))
+        .transpose()
+}
+
+/// Render the suffix onto a resolved (possibly cached) result. Rendering happens outside the
+/// cache because `{dirty}` and `{env:NAME}` can change without any ref moving.
+pub fn apply_metadata_suffix(
+    git: &dyn GitProvider,
+    config: &TideConfig,
+    template: Option<&SuffixTemplate>,
+    result: &mut MarkResult,
+) -> TideResult<()> {
+    let metadata = match template {
+        Some(template) => {
+            let timezone = TimezonePolicy::parse(config.time.timezone.as_str())?;
+            let explain = &result.explain;
+            let ctx = SuffixContext {
+                commit_id: explain.target_commit.id.as_str(),
+                branch: explain.branch.as_deref(),
+                anchor_tag: explain.anchor_tag.as_str(),
+                date: timezone
+                    .date_for_timestamp(explain.target_commit.timestamp)?
+                    .format("%Y%m%d")
+                    .to_string(),
+                dirty: template.uses_dirty() && git.is_dirty()?,
+            };
+            template.render(&ctx)
+        }
+        None => None,
+    };
+    result.coordinate.metadata = metadata.clone();
+    result.explain.version.metadata = metadata;
+    Ok(())
+}
+
+fn resolve_coordinate(
+    git: &dyn GitProvider,
+    config: &TideConfig,
+    cache: &CacheStore,
+    req: MarkRequest,
+) -> TideResult<MarkResult> {
     let target = match req.target_rev.as_deref() {
         Some(rev) => git.resolve_commit(rev)?,
         None => git.head_commit()?,
     };
+    let branch = branch::resolve_branch(git, &config.branch)?;
+    // Cached resolution is local-only, so loading the inventory up front costs one ref listing.
+    let inventory = release::load_release_tags(git, config, req.local_only, req.require_remote)?;
+    resolve_target(
+        git,
+        config,
+        cache,
+        target,
+        req.local_only,
+        &branch,
+        &inventory,
+    )
+}
 
-    let metadata = normalize_metadata_suffix(req.metadata_suffix, &config.output.metadata_suffix);
-    let bypass_cache = requires_remote_refresh(config, req.local_only);
+/// Coordinate of `target` (without metadata suffix) against an already loaded tag inventory
+/// and branch context, so callers resolving many commits list tags once.
+pub fn resolve_target(
+    git: &dyn GitProvider,
+    config: &TideConfig,
+    cache: &CacheStore,
+    target: CommitInfo,
+    local_only: bool,
+    branch: &BranchContext,
+    inventory: &ReleaseInventory,
+) -> TideResult<MarkResult> {
+    let timezone = TimezonePolicy::parse(config.time.timezone.as_str())?;
+    let bypass_cache = requires_remote_refresh(config, local_only);
 
     let mut cache_key = None;
+    let mut cache_status = initial_cache_status(cache, bypass_cache);
     if !bypass_cache {
+        let release_tags = release::release_set_digest(&inventory.releases);
         let key_payload = MarkCacheKey {
             target_commit: target.id.as_str(),
-            local_only: req.local_only,
+            local_only,
             tag_prefix: config.release.tag_prefix.as_str(),
             require_annotated_tags: config.release.require_annotated_tags,
-            timezone: config.time.timezone.as_str(),
+            on_missing_anchor: config.release.on_missing_anchor,
+            history: config.coordinate.history,
+            timezone: timezone.canonical_name(),
             remote_strategy: remote_strategy_label(&config.remote.strategy),
-            remote_name: config.remote.name.as_str(),
-            metadata_suffix: metadata.as_deref(),
+            remote_names: config.remote.effective_names(),
+            branch: branch.name.as_deref(),
+            channel: branch.channel.as_deref(),
+            release_tags: release_tags.as_str(),
         };
         let resolved_key = CacheStore::key_from_serializable(MARK_CACHE_NAMESPACE, &key_payload)?;
-        if let Some(cached) =
-            cache.get::<MarkResult>(MARK_CACHE_NAMESPACE, resolved_key.as_str())?
-        {
-            return Ok(cached);
+        match cache.get::<MarkResult>(MARK_CACHE_NAMESPACE, resolved_key.as_str())? {
+            CacheLookup::Hit(mut cached) => {
+                cached.explain.cache = CacheStatus::Hit;
+                return Ok(cached);
+            }
+            CacheLookup::RemoteHit(mut cached) => {
+                cached.explain.cache = CacheStatus::RemoteHit;
+                return Ok(cached);
+            }
+            CacheLookup::Miss => {}
+            CacheLookup::Invalid(kind) => cache_status = CacheStatus::Rewritten(kind),
         }
         cache_key = Some(resolved_key);
     }
 
-    let (releases, remote_status) = release::load_release_tags(git, config, req.local_only)?;
-    let anchor = match release::select_anchor(
+    let (selected, history) = history::select_anchor_with_history(
         git,
-        releases.as_slice(),
+        config,
+        inventory.releases.as_slice(),
         &target,
-        config.release.tag_prefix.as_str(),
-    ) {
-        Ok(a) => a,
-        Err(TideError::NoReleaseAnchor { .. }) => {
-            let root = git.root_commit()?;
-            let distance = git.commit_distance(root.id.as_str(), target.id.as_str())?;
-            AnchorSelection {
-                release: ReleaseTag {
-                    anchor_value: 0,
-                    tag: TagRef {
-                        name: "(none)".to_string(),
-                        commit_id: root.id.clone(),
-                        is_annotated: false,
-                        source: TagSource::Local,
-                    },
-                },
-                distance,
-                anchor_commit: root,
-            }
-        }
-        Err(other) => return Err(other),
+        !local_only,
+    )?;
+    let (anchor, anchor_kind) = match selected {
+        Some(a) => (a, AnchorKind::Tag),
+        None => fallback_anchor(git, config, &target)?,
     };
 
     let day_delta_i64 = timezone.day_delta(anchor.anchor_commit.timestamp, target.timestamp)?;
@@ -126,25 +212,42 @@ pub fn resolve_mark(
         message: format!("day delta overflow: {day_delta_i64}"),
     })?;
 
-    let commit_index = resolve_commit_index(git, &timezone, &anchor.anchor_commit, &target)?;
+    let (commit_index, ancestry_index) = resolve_commit_index(
+        git,
+        cache,
+        config,
+        &timezone,
+        &anchor.anchor_commit,
+        &target,
+    )?;
 
     let coordinate = VersionCoordinate {
         x: anchor.release.anchor_value,
         y: day_delta,
         z: commit_index,
-        metadata,
+        channel: branch.channel.clone(),
+        metadata: None,
     };
 
     let explain = MarkExplain {
         version: coordinate.clone(),
         target_commit: target,
         anchor_tag: anchor.release.tag.name,
+        anchor_kind,
         anchor_commit: anchor.anchor_commit,
         day_delta,
         commit_index,
+        history: config.coordinate.history,
         timezone: timezone.canonical_name(),
-        remote_status,
-        branch: git.current_branch()?,
+        remote_status: inventory.remote_status.clone(),
+        remote_failure: inventory.remote_failure.clone(),
+        remotes: inventory.remotes.clone(),
+        tag_conflicts: inventory.conflicts.clone(),
+        shallow: history.shallow,
+        deepen_rounds: history.deepen_rounds,
+        branch: branch.name.clone(),
+        cache: cache_status,
+        ancestry_index,
     };
 
     let result = MarkResult {
@@ -158,6 +261,72 @@ pub fn resolve_mark(
     Ok(result)
 }
 
+/// Cache status before lookup: `Bypassed` for remote refreshes, `Disabled` when turned off.
+pub fn initial_cache_status(cache: &CacheStore, bypass_cache: bool) -> CacheStatus {
+    if bypass_cache {
+        CacheStatus::Bypassed
+    } else if cache.is_enabled() {
+        CacheStatus::Miss
+    } else {
+        CacheStatus::Disabled
+    }
+}
+
+/// Anchor used when no release tag is reachable, as chosen by `release.on_missing_anchor`.
+fn fallback_anchor(
+    git: &dyn GitProvider,
+    config: &TideConfig,
+    target: &CommitInfo,
+) -> TideResult<(AnchorSelection, AnchorKind)> {
+    let (root, kind) = match config.release.on_missing_anchor {
+        MissingAnchorPolicy::Error => {
+            return Err(TideError::NoReleaseAnchor {
+                prefix: config.release.tag_prefix.clone(),
+            });
+        }
+        MissingAnchorPolicy::Root => {
+            let roots = git.root_commits(target.id.as_str())?;
+            let root = oldest_root(roots).ok_or_else(|| TideError::Internal {
+                message: "no root commit found".to_string(),
+            })?;
+            (root, AnchorKind::RootFallback)
+        }
+        MissingAnchorPolicy::FirstParentRoot => (
+            git.first_parent_root(target.id.as_str())?,
+            AnchorKind::FirstParentRootFallback,
+        ),
+    };
+
+    let distance = history::distance(
+        git,
+        config.coordinate.history,
+        root.id.as_str(),
+        target.id.as_str(),
+    )?;
+    let anchor = AnchorSelection {
+        release: ReleaseTag {
+            anchor_value: 0,
+            tag: TagRef {
+                name: "(none)".to_string(),
+                commit_id: root.id.clone(),
+                is_annotated: false,
+                source: TagSource::Local,
+            },
+        },
+        distance,
+        anchor_commit: root,
+    };
+    Ok((anchor, kind))
+}
+
+/// Oldest root by timestamp, then commit id, so multi-root histories do not depend on
+/// `rev-list` output order.
+fn oldest_root(roots: Vec<CommitInfo>) -> Option<CommitInfo> {
+    roots
+        .into_iter()
+        .min_by(|a, b| a.timestamp.cmp(&b.timestamp).then_with(|| a.id.cmp(&b.id)))
+}
+
 fn normalize_metadata_suffix(
     arg_suffix: Option<String>,
     config_suffix: &Option<String>,
@@ -174,32 +343,52 @@ fn normalize_metadata_suffix(
 fn remote_strategy_label(strategy: &RemoteStrategy) -> &'static str {
     match strategy {
         RemoteStrategy::LsRemote => "ls-remote",
+        RemoteStrategy::Fetch => "fetch",
         RemoteStrategy::LocalOnly => "local-only",
     }
 }
 
 fn requires_remote_refresh(config: &TideConfig, local_only: bool) -> bool {
-    !local_only && config.remote.strategy == RemoteStrategy::LsRemote
+    !local_only && config.remote.strategy != RemoteStrategy::LocalOnly
 }
 
+/// Ancestry-path indexes come from the persistent index when the cache is enabled; first-parent
+/// walks and disabled caches fall back to a full path walk.
 fn resolve_commit_index(
     git: &dyn GitProvider,
+    cache: &CacheStore,
+    config: &TideConfig,
     timezone: &TimezonePolicy,
     anchor_commit: &CommitInfo,
     target: &CommitInfo,
-) -> TideResult<u32> {
+) -> TideResult<(u32, IndexStatus)> {
     if anchor_commit.id == target.id {
-        return Ok(0);
+        return Ok((0, IndexStatus::Unused));
     }
 
-    let commits = git.ancestry_path_commits(anchor_commit.id.as_str(), target.id.as_str())?;
-    commit_index_on_day(commits.as_slice(), target, timezone)
+    let mode = config.coordinate.history;
+    if mode == HistoryMode::AncestryPath && cache.is_enabled() {
+        return ancestry::indexed_commit_index(
+            git,
+            cache,
+            timezone,
+            config.time.timezone.as_str(),
+            anchor_commit,
+            target,
+        );
+    }
+    let commits = history::path_commits(git, mode, anchor_commit.id.as_str(), target.id.as_str())?;
+    let index = commit_index_on_day(commits.as_slice(), target, timezone, mode)?;
+    Ok((index, IndexStatus::Unused))
 }
 
+/// In ancestry-path mode same-day commits are ordered by `(timestamp, id)`; in first-parent
+/// mode the mainline order returned by the walk is kept, so each merge is exactly one step.
 fn commit_index_on_day(
     path_commits: &[CommitInfo],
     target: &CommitInfo,
     timezone: &TimezonePolicy,
+    mode: HistoryMode,
 ) -> TideResult<u32> {
     let target_day = timezone.date_for_timestamp(target.timestamp)?;
 
@@ -209,7 +398,9 @@ fn commit_index_on_day(
         .cloned()
         .collect();
 
-    commits_on_day.sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then_with(|| a.id.cmp(&b.id)));
+    if mode == HistoryMode::AncestryPath {
+        commits_on_day.sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then_with(|| a.id.cmp(&b.id)));
+    }
 
     let idx = commits_on_day
         .iter()
@@ -250,7 +441,25 @@ mod tests {
             },
         ];
 
-        let idx = commit_index_on_day(commits.as_slice(), &target, &tz).unwrap();
+        let idx = commit_index_on_day(commits.as_slice(), &target, &tz, HistoryMode::AncestryPath)
+            .unwrap();
+        assert_eq!(idx, 2);
+
+        let idx = commit_index_on_day(commits.as_slice(), &target, &tz, HistoryMode::FirstParent)
+            .unwrap();
         assert_eq!(idx, 2);
     }
+
+    #[test]
+    fn oldest_root_is_order_independent() {
+        let commit = |id: &str, timestamp| CommitInfo {
+            id: id.to_string(),
+            timestamp,
+        };
+        let forward = vec![commit("b", 10), commit("a", 10), commit("c", 20)];
+        let mut reversed = forward.clone();
+        reversed.reverse();
+        assert_eq!(oldest_root(forward).unwrap().id, "a");
+        assert_eq!(oldest_root(reversed).unwrap().id, "a");
+    }
 }
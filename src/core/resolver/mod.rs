@@ -15,4 +15,5 @@
 //! Copyright (c) 2026-2027 easynet. All rights reserved.
 
 pub mod file;
+pub mod locate;
 pub mod mark;
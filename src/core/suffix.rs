@@ -0,0 +1,241 @@
+//! TideMark
+//! ========
+//!
+//! File: src/core/suffix.rs
+//! Description: Metadata suffix template parsing, validation, and sanitized rendering.
+//!
+//! Responsibility:
+//! - Expand `{placeholder}` templates into suffix text that stays valid in `x.y.z.<suffix>` output.
+//!
+//! Architectural Position:
+//! - Core formatting policy applied after coordinate resolution; never influences `(x, y, z)`.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+use std::env;
+
+use crate::error::{TideError, TideResult};
+
+const SHORT_SHA_LEN: usize = 7;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    ShortSha,
+    Branch,
+    AnchorTag,
+    Date,
+    Dirty,
+    Env(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuffixTemplate {
+    segments: Vec<Segment>,
+}
+
+/// Values available to placeholders for one resolved coordinate.
+#[derive(Debug, Clone)]
+pub struct SuffixContext<'a> {
+    pub commit_id: &'a str,
+    pub branch: Option<&'a str>,
+    pub anchor_tag: &'a str,
+    /// Target commit date in the configured timezone, formatted `YYYYMMDD`.
+    pub date: String,
+    pub dirty: bool,
+}
+
+impl SuffixTemplate {
+    /// Parse `{short_sha}`, `{branch}`, `{anchor_tag}`, `{date}`, `{dirty}` and `{env:NAME}`
+    /// placeholders; any other text is literal.
+    pub fn parse(raw: &str) -> TideResult<Self> {
+        let invalid = |message: String| TideError::InvalidSuffixTemplate {
+            template: raw.to_string(),
+            message,
+        };
+
+        let mut segments = Vec::new();
+        let mut rest = raw;
+        while let Some(open) = rest.find(['{', '}']) {
+            if rest[open..].starts_with('}') {
+                return Err(invalid(format!(
+                    "unmatched `}}` at byte {}",
+                    raw.len() - rest.len() + open
+                )));
+            }
+            if open > 0 {
+                segments.push(Segment::Literal(rest[..open].to_string()));
+            }
+            let after = &rest[open + 1..];
+            let close = after
+                .find('}')
+                .ok_or_else(|| invalid("unterminated `{`".to_string()))?;
+            let name = &after[..close];
+            segments.push(match name {
+                "short_sha" => Segment::ShortSha,
+                "branch" => Segment::Branch,
+                "anchor_tag" => Segment::AnchorTag,
+                "date" => Segment::Date,
+                "dirty" => Segment::Dirty,
+                _ => match name.strip_prefix("env:") {
+                    Some(var) if is_env_name(var) => Segment::Env(var.to_string()),
+                    _ => return Err(invalid(format!("unknown placeholder `{{{name}}}`"))),
+                },
+            });
+            rest = &after[close + 1..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Literal(rest.to_string()));
+        }
+        Ok(Self { segments })
+    }
+
+    pub fn uses_dirty(&self) -> bool {
+        self.segments.contains(&Segment::Dirty)
+    }
+
+    /// Variables read by `{env:NAME}` placeholders.
+    pub fn env_names(&self) -> impl Iterator<Item = &str> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Env(var) => Some(var.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Render; `None` when nothing printable remains. Literal text is kept as written, so a
+    /// template without placeholders renders exactly as configured; only placeholder values
+    /// are sanitized, and empty identifiers they leave behind are dropped.
+    pub fn render(&self, ctx: &SuffixContext<'_>) -> Option<String> {
+        let mut rendered = String::new();
+        let mut expanded = false;
+        for segment in &self.segments {
+            let value = match segment {
+                Segment::Literal(text) => {
+                    rendered.push_str(text);
+                    continue;
+                }
+                Segment::ShortSha => {
+                    ctx.commit_id[..ctx.commit_id.len().min(SHORT_SHA_LEN)].to_string()
+                }
+                Segment::Branch => ctx.branch.unwrap_or("detached").to_string(),
+                Segment::AnchorTag => ctx.anchor_tag.to_string(),
+                Segment::Date => ctx.date.clone(),
+                Segment::Dirty => {
+                    if ctx.dirty {
+                        "dirty".to_string()
+                    } else {
+                        String::new()
+                    }
+                }
+                Segment::Env(var) => env::var(var).unwrap_or_default(),
+            };
+            expanded = true;
+            rendered.push_str(&sanitize(value.as_str()));
+        }
+        if !expanded {
+            return (!rendered.is_empty()).then_some(rendered);
+        }
+        let identifiers: Vec<&str> = rendered
+            .split('.')
+            .filter(|part| !part.is_empty())
+            .collect();
+        if identifiers.is_empty() {
+            None
+        } else {
+            Some(identifiers.join("."))
+        }
+    }
+}
+
+fn is_env_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
+/// Reduce a placeholder value to dot-separated identifiers of `[0-9A-Za-z-]`, the charset
+/// shared by semver build metadata and the `x.y.z.<suffix>` coordinate format.
+fn sanitize(value: &str) -> String {
+    let mapped: String = value
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '-' || ch == '.' {
+                ch
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    mapped
+        .split('.')
+        .map(|part| part.trim_matches('-'))
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(dirty: bool) -> SuffixContext<'static> {
+        SuffixContext {
+            commit_id: "0123456789abcdef",
+            branch: Some("feature/a b"),
+            anchor_tag: "v1.2",
+            date: "20240102".to_string(),
+            dirty,
+        }
+    }
+
+    #[test]
+    fn renders_placeholders_and_sanitizes() {
+        let template = SuffixTemplate::parse("{branch}.{short_sha}.{dirty}").unwrap();
+        assert_eq!(
+            template.render(&ctx(false)).as_deref(),
+            Some("feature-a-b.0123456")
+        );
+        assert_eq!(
+            template.render(&ctx(true)).as_deref(),
+            Some("feature-a-b.0123456.dirty")
+        );
+
+        let template = SuffixTemplate::parse("rc_{anchor_tag}+{branch}").unwrap();
+        assert_eq!(
+            template.render(&ctx(false)).as_deref(),
+            Some("rc_v1.2+feature-a-b")
+        );
+        assert_eq!(
+            SuffixTemplate::parse("{dirty}")
+                .unwrap()
+                .render(&ctx(false)),
+            None
+        );
+    }
+
+    #[test]
+    fn static_suffixes_render_verbatim() {
+        for raw in ["build_7", "rc+1", "nightly..2"] {
+            assert_eq!(
+                SuffixTemplate::parse(raw)
+                    .unwrap()
+                    .render(&ctx(true))
+                    .as_deref(),
+                Some(raw)
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_templates() {
+        assert!(SuffixTemplate::parse("{nope}").is_err());
+        assert!(SuffixTemplate::parse("{branch").is_err());
+        assert!(SuffixTemplate::parse("branch}").is_err());
+        assert!(SuffixTemplate::parse("{env:}").is_err());
+        assert!(SuffixTemplate::parse("{env:BUILD_ID}").is_ok());
+        assert!(SuffixTemplate::parse("plain-text").is_ok());
+    }
+}
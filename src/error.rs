@@ -18,9 +18,12 @@ use std::{io, path::PathBuf, process::ExitCode};
 
 use thiserror::Error;
 
+use crate::core::model::RemoteFailureKind;
+
 pub type TideResult<T> = Result<T, TideError>;
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum TideError {
     #[error("not a git repository: {path}")]
     NotGitRepository { path: PathBuf },
@@ -32,15 +35,39 @@ pub enum TideError {
         code: Option<i32>,
     },
 
+    #[error("remote tag refresh from `{remote}` failed ({kind}): {stderr}")]
+    RemoteRefresh {
+        remote: String,
+        kind: RemoteFailureKind,
+        stderr: String,
+    },
+
     #[error("invalid UTF-8 from git command")]
     InvalidUtf8,
 
     #[error("invalid release tag `{tag}` for prefix `{prefix}`")]
     InvalidReleaseTag { tag: String, prefix: String },
 
+    #[error(
+        "release tag `{tag}` conflicts: local points to {local_commit}, remote points to {remote_commit}"
+    )]
+    ReleaseTagConflict {
+        tag: String,
+        local_commit: String,
+        remote_commit: String,
+    },
+
     #[error("no release anchor found for prefix `{prefix}`")]
     NoReleaseAnchor { prefix: String },
 
+    #[error(
+        "shallow clone lacks history for a correct coordinate after {deepen_rounds} deepen round(s): {reason}; set `[remote] deepen = true` or fetch full history"
+    )]
+    ShallowHistory { reason: String, deepen_rounds: u32 },
+
+    #[error("branch `{branch}` is not protected and `[branch] unprotected = \"refuse\"`")]
+    BranchRefused { branch: String },
+
     #[error(
         "timestamp anomaly: anchor timestamp {anchor_ts} is later than target timestamp {target_ts}"
     )]
@@ -49,18 +76,57 @@ pub enum TideError {
     #[error("invalid timezone value `{value}`; expected `UTC` or +/-HH:MM")]
     InvalidTimezone { value: String },
 
+    #[error("invalid metadata suffix template `{template}`: {message}")]
+    InvalidSuffixTemplate { template: String, message: String },
+
     #[error("config parse failed at {path}: {message}")]
     ConfigParse { path: PathBuf, message: String },
 
+    #[error("invalid config override `{origin}`: {message}")]
+    InvalidConfigOverride { origin: String, message: String },
+
+    #[error(
+        "config at {path} declares schema {found}, but this build supports up to schema {supported}"
+    )]
+    UnsupportedConfigSchema {
+        path: PathBuf,
+        found: u32,
+        supported: u32,
+    },
+
+    #[error("config validation failed with {errors} error(s)")]
+    ConfigInvalid { errors: usize },
+
     #[error("config file already exists at {path}")]
     ConfigExists { path: PathBuf },
 
+    #[error("invalid coordinate `{value}`; expected x.y.z")]
+    InvalidCoordinate { value: String },
+
+    #[error("no commit reachable from {rev} resolves to {coordinate}")]
+    CoordinateNotFound { coordinate: String, rev: String },
+
     #[error("file has no tracked git history: {path}")]
     FileHistoryNotFound { path: PathBuf },
 
     #[error("cache format error: {message}")]
     CacheFormat { message: String },
 
+    #[error("invalid cache remote `{value}`: {message}")]
+    InvalidCacheRemote { value: String, message: String },
+
+    #[error("remote cache {location} failed: {message}")]
+    CacheRemote { location: String, message: String },
+
+    #[error("cache verification failed: {entries} unreadable entries")]
+    CacheCorrupt { entries: usize },
+
+    #[error("cannot stamp {path}: {message}")]
+    ManifestStamp { path: PathBuf, message: String },
+
+    #[error("manifest(s) out of date: {}", paths.join(", "))]
+    ManifestOutOfDate { paths: Vec<String> },
+
     #[error("service interval must be >= 1 minute; got {minutes}")]
     InvalidServiceInterval { minutes: u32 },
 
@@ -78,6 +144,10 @@ pub enum TideError {
         code: Option<i32>,
     },
 
+    /// A failure reported by `tide serve`, carrying the daemon-side message and exit code.
+    #[error("{message}")]
+    Daemon { message: String, exit_code: u8 },
+
     #[error("I/O error at {path}: {source}")]
     Io { path: PathBuf, source: io::Error },
 
@@ -87,27 +157,49 @@ pub enum TideError {
 
 impl TideError {
     pub fn exit_code(&self) -> ExitCode {
+        ExitCode::from(self.exit_status())
+    }
+
+    /// Numeric process exit status; `exit_code` wraps it for `main`.
+    pub fn exit_status(&self) -> u8 {
         match self {
+            Self::ManifestOutOfDate { .. } | Self::CacheCorrupt { .. } => 1,
+
             Self::ConfigParse { .. }
+            | Self::InvalidConfigOverride { .. }
+            | Self::ConfigInvalid { .. }
+            | Self::UnsupportedConfigSchema { .. }
             | Self::InvalidTimezone { .. }
+            | Self::InvalidSuffixTemplate { .. }
+            | Self::InvalidCacheRemote { .. }
+            | Self::InvalidCoordinate { .. }
             | Self::ConfigExists { .. }
             | Self::InvalidReleaseTag { .. }
-            | Self::InvalidServiceInterval { .. } => ExitCode::from(2),
+            | Self::InvalidServiceInterval { .. }
+            | Self::ManifestStamp { .. } => 2,
 
             Self::NotGitRepository { .. }
             | Self::GitCommand { .. }
-            | Self::SystemCommand { .. } => ExitCode::from(3),
+            | Self::RemoteRefresh { .. }
+            | Self::SystemCommand { .. } => 3,
 
             Self::NoReleaseAnchor { .. }
+            | Self::ReleaseTagConflict { .. }
+            | Self::ShallowHistory { .. }
+            | Self::BranchRefused { .. }
             | Self::TimestampAnomaly { .. }
-            | Self::FileHistoryNotFound { .. } => ExitCode::from(4),
+            | Self::CoordinateNotFound { .. }
+            | Self::FileHistoryNotFound { .. } => 4,
+
+            Self::Daemon { exit_code, .. } => *exit_code,
 
             Self::InvalidUtf8
             | Self::CacheFormat { .. }
+            | Self::CacheRemote { .. }
             | Self::UnsupportedPlatform { .. }
             | Self::MissingHomeDirectory
             | Self::Io { .. }
-            | Self::Internal { .. } => ExitCode::from(5),
+            | Self::Internal { .. } => 5,
         }
     }
 }
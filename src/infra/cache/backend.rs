@@ -0,0 +1,164 @@
+//! TideMark
+//! ========
+//!
+//! File: src/infra/cache/backend.rs
+//! Description: Byte-level cache backend trait, remote spec parsing, and the directory backend.
+//!
+//! Responsibility:
+//! - Store and load encoded cache entries by namespace and SHA-256 key in a directory tree.
+//!
+//! Architectural Position:
+//! - Storage seam under `CacheStore`; shared by the local `.git/tidemark-cache` tier and shared-directory remotes.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use super::http::HttpBackend;
+use crate::error::{TideError, TideResult, io_err};
+
+static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Content-addressed blob store for encoded entries; keys are the resolver's SHA-256 cache keys.
+///
+/// Backends move bytes only: entries are self-validating, so callers decode and check them.
+pub trait CacheBackend: fmt::Debug + Send + Sync {
+    /// Path or URL shown in diagnostics.
+    fn location(&self) -> String;
+
+    fn load(&self, namespace: &str, key: &str) -> TideResult<Option<Vec<u8>>>;
+
+    fn store(&self, namespace: &str, key: &str, bytes: &[u8]) -> TideResult<()>;
+}
+
+/// `cache.remote`: an absolute directory (NFS mount, CI cache path) or an `http://` base URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteSpec {
+    Directory(PathBuf),
+    Http(HttpBackend),
+}
+
+impl RemoteSpec {
+    pub fn parse(raw: &str) -> TideResult<Self> {
+        let trimmed = raw.trim();
+        let invalid = |message: &str| TideError::InvalidCacheRemote {
+            value: raw.to_string(),
+            message: message.to_string(),
+        };
+        if trimmed.starts_with("http://") {
+            return HttpBackend::parse(trimmed)
+                .map(Self::Http)
+                .map_err(|message| invalid(message.as_str()));
+        }
+        if trimmed.contains("://") {
+            return Err(invalid(
+                "only `http://` URLs and directory paths are supported",
+            ));
+        }
+        let path = Path::new(trimmed.strip_prefix("file:").unwrap_or(trimmed));
+        if !path.is_absolute() {
+            return Err(invalid("directory must be an absolute path"));
+        }
+        Ok(Self::Directory(path.to_path_buf()))
+    }
+
+    pub fn into_backend(self) -> Box<dyn CacheBackend> {
+        match self {
+            Self::Directory(root) => Box::new(DirectoryBackend::new(root)),
+            Self::Http(http) => Box::new(http),
+        }
+    }
+}
+
+/// `<root>/<namespace>/<key>.json`, written through a per-writer temp file and an atomic rename.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectoryBackend {
+    root: PathBuf,
+}
+
+impl DirectoryBackend {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn path_for(&self, namespace: &str, key: &str) -> PathBuf {
+        self.root.join(namespace).join(format!("{key}.json"))
+    }
+}
+
+impl CacheBackend for DirectoryBackend {
+    fn location(&self) -> String {
+        self.root.display().to_string()
+    }
+
+    fn load(&self, namespace: &str, key: &str) -> TideResult<Option<Vec<u8>>> {
+        let path = self.path_for(namespace, key);
+        match fs::read(&path) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(io_err(&path, err)),
+        }
+    }
+
+    fn store(&self, namespace: &str, key: &str, bytes: &[u8]) -> TideResult<()> {
+        let dir = self.root.join(namespace);
+        fs::create_dir_all(&dir).map_err(|err| io_err(&dir, err))?;
+
+        let path = self.path_for(namespace, key);
+        // Unique per process and write, so concurrent writers never share a temp file.
+        let tmp = dir.join(format!(
+            ".{key}.{}-{}.tmp",
+            process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&tmp, bytes).map_err(|err| io_err(&tmp, err))?;
+        if let Err(err) = fs::rename(&tmp, &path) {
+            let _ = fs::remove_file(&tmp);
+            return Err(io_err(&path, err));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remote_spec_accepts_absolute_directories_and_http_urls() {
+        let dir = RemoteSpec::parse("/mnt/ci-cache/tidemark").expect("directory");
+        assert_eq!(
+            dir,
+            RemoteSpec::Directory(PathBuf::from("/mnt/ci-cache/tidemark"))
+        );
+        assert_eq!(
+            RemoteSpec::parse("file:/mnt/ci-cache").expect("file prefix"),
+            RemoteSpec::Directory(PathBuf::from("/mnt/ci-cache"))
+        );
+        assert!(matches!(
+            RemoteSpec::parse("http://cache.internal:8080/tide").expect("http"),
+            RemoteSpec::Http(_)
+        ));
+
+        for bad in ["relative/dir", "https://cache.internal", "http://"] {
+            assert!(
+                matches!(
+                    RemoteSpec::parse(bad),
+                    Err(TideError::InvalidCacheRemote { .. })
+                ),
+                "{bad}"
+            );
+        }
+    }
+}
@@ -0,0 +1,207 @@
+//! TideMark
+//! ========
+//!
+//! File: src/infra/cache/http.rs
+//! Description: Minimal HTTP/1.1 key/value cache backend.
+//!
+//! Responsibility:
+//! - Speak the `GET`/`PUT <prefix>/<namespace>/<key>` cache protocol over plain TCP with bounded timeouts.
+//!
+//! Architectural Position:
+//! - Remote `CacheBackend` for CI fleets; any blob store or the test stand-in server can sit behind it.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+use std::{
+    io::{Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    time::Duration,
+};
+
+use super::backend::CacheBackend;
+use crate::error::{TideError, TideResult};
+
+/// Applied to connect, read, and write so an unreachable cache costs a bounded delay.
+const TIMEOUT: Duration = Duration::from_secs(2);
+
+/// `http://host[:port][/prefix]`; entries live at `<prefix>/<namespace>/<key>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpBackend {
+    host: String,
+    port: u16,
+    prefix: String,
+}
+
+struct Response {
+    status: u16,
+    body: Vec<u8>,
+}
+
+impl HttpBackend {
+    pub(super) fn parse(url: &str) -> Result<Self, String> {
+        let rest = url
+            .strip_prefix("http://")
+            .ok_or_else(|| "expected an `http://` URL".to_string())?;
+        let (authority, path) = match rest.find('/') {
+            Some(index) => rest.split_at(index),
+            None => (rest, ""),
+        };
+        if authority.is_empty() {
+            return Err("missing host".to_string());
+        }
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse::<u16>()
+                    .map_err(|_| format!("invalid port `{port}`"))?,
+            ),
+            None => (authority, 80),
+        };
+        if host.is_empty() {
+            return Err("missing host".to_string());
+        }
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            prefix: path.trim_end_matches('/').to_string(),
+        })
+    }
+
+    fn request(&self, method: &str, target: &str, body: &[u8]) -> TideResult<Response> {
+        let err = |message: String| TideError::CacheRemote {
+            location: self.location(),
+            message,
+        };
+        let addr = (self.host.as_str(), self.port)
+            .to_socket_addrs()
+            .map_err(|e| err(format!("resolve {}: {e}", self.host)))?
+            .next()
+            .ok_or_else(|| err(format!("no address for {}", self.host)))?;
+        let mut stream =
+            TcpStream::connect_timeout(&addr, TIMEOUT).map_err(|e| err(format!("connect: {e}")))?;
+        stream
+            .set_read_timeout(Some(TIMEOUT))
+            .and_then(|()| stream.set_write_timeout(Some(TIMEOUT)))
+            .map_err(|e| err(e.to_string()))?;
+
+        let head = format!(
+            "{method} {target} HTTP/1.1\r\nHost: {}:{}\r\nConnection: close\r\nContent-Type: application/octet-stream\r\nContent-Length: {}\r\n\r\n",
+            self.host,
+            self.port,
+            body.len()
+        );
+        stream
+            .write_all(head.as_bytes())
+            .and_then(|()| stream.write_all(body))
+            .map_err(|e| err(format!("send: {e}")))?;
+
+        let mut raw = Vec::new();
+        stream
+            .read_to_end(&mut raw)
+            .map_err(|e| err(format!("receive: {e}")))?;
+        parse_response(&raw).map_err(err)
+    }
+
+    fn target(&self, namespace: &str, key: &str) -> String {
+        format!("{}/{namespace}/{key}", self.prefix)
+    }
+}
+
+impl CacheBackend for HttpBackend {
+    fn location(&self) -> String {
+        format!("http://{}:{}{}", self.host, self.port, self.prefix)
+    }
+
+    fn load(&self, namespace: &str, key: &str) -> TideResult<Option<Vec<u8>>> {
+        let response = self.request("GET", self.target(namespace, key).as_str(), &[])?;
+        match response.status {
+            200 => Ok(Some(response.body)),
+            404 => Ok(None),
+            status => Err(TideError::CacheRemote {
+                location: self.location(),
+                message: format!("GET returned status {status}"),
+            }),
+        }
+    }
+
+    fn store(&self, namespace: &str, key: &str, bytes: &[u8]) -> TideResult<()> {
+        let response = self.request("PUT", self.target(namespace, key).as_str(), bytes)?;
+        if (200..300).contains(&response.status) {
+            Ok(())
+        } else {
+            Err(TideError::CacheRemote {
+                location: self.location(),
+                message: format!("PUT returned status {}", response.status),
+            })
+        }
+    }
+}
+
+fn parse_response(raw: &[u8]) -> Result<Response, String> {
+    let split = raw
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or_else(|| "truncated response head".to_string())?;
+    let head =
+        std::str::from_utf8(&raw[..split]).map_err(|_| "non-UTF-8 response head".to_string())?;
+    let mut lines = head.split("\r\n");
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or_else(|| "malformed status line".to_string())?;
+
+    let mut body = raw[split + 4..].to_vec();
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("transfer-encoding") && !value.eq_ignore_ascii_case("identity")
+        {
+            return Err(format!("unsupported transfer-encoding `{value}`"));
+        }
+        if name.eq_ignore_ascii_case("content-length") {
+            let length = value
+                .parse::<usize>()
+                .map_err(|_| format!("invalid content-length `{value}`"))?;
+            if body.len() < length {
+                return Err("truncated response body".to_string());
+            }
+            body.truncate(length);
+        }
+    }
+    Ok(Response { status, body })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_base_urls_and_responses() {
+        let backend = HttpBackend::parse("http://cache.internal:8080/ci/tide/").expect("url");
+        assert_eq!(backend.location(), "http://cache.internal:8080/ci/tide");
+        assert_eq!(backend.target("mark", "ab12"), "/ci/tide/mark/ab12");
+        assert_eq!(
+            HttpBackend::parse("http://cache.internal")
+                .expect("default port")
+                .location(),
+            "http://cache.internal:80"
+        );
+        assert!(HttpBackend::parse("http://cache.internal:http").is_err());
+
+        let ok =
+            parse_response(b"HTTP/1.1 200 OK\r\nContent-Length: 3\r\n\r\nabcdef").expect("200");
+        assert_eq!((ok.status, ok.body.as_slice()), (200, b"abc".as_slice()));
+        let missing = parse_response(b"HTTP/1.1 404 Not Found\r\n\r\n").expect("404");
+        assert_eq!(missing.status, 404);
+        assert!(parse_response(b"HTTP/1.1 200 OK\r\nContent-Length: 9\r\n\r\nabc").is_err());
+        assert!(
+            parse_response(b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc")
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,710 @@
+//! TideMark
+//! ========
+//!
+//! File: src/infra/cache/mod.rs
+//! Description: Tiered cache store: `.git/tidemark-cache` plus an optional shared remote tier.
+//!
+//! Responsibility:
+//! - Provide namespaced cache keying, checksummed entries with atomic per-writer writes, entry enumeration, and size/age limits for deterministic command acceleration.
+//!
+//! Architectural Position:
+//! - Infrastructure persistence adapter used by resolver workflows.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+pub mod backend;
+pub mod http;
+
+use std::{
+    collections::HashMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex,
+        mpsc::{self, SyncSender},
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant, SystemTime},
+};
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use sha2::{Digest, Sha256};
+
+use self::backend::{CacheBackend, DirectoryBackend, RemoteSpec};
+use crate::{
+    config::CacheConfig,
+    core::model::CacheInvalidKind,
+    error::{TideError, TideResult, io_err},
+};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+/// Entries held by the in-memory tier before it starts over.
+const MEMORY_TIER_MAX_ENTRIES: usize = 4096;
+/// Bumped whenever the on-disk entry layout changes.
+pub const CACHE_FORMAT_VERSION: u32 = 1;
+const TOOL_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Uploads waiting for the write-behind worker; further writes are dropped until it catches up.
+const UPLOAD_QUEUE_DEPTH: usize = 64;
+/// Longest gap between `max_age` scans in one process, so long-lived resolvers keep expiring.
+const AGE_SCAN_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// How long the remote tier is skipped after a failed request.
+const REMOTE_BACKOFF: Duration = Duration::from_secs(60);
+
+/// First line of every entry; the payload JSON follows on the next line.
+#[derive(Debug, Serialize, Deserialize)]
+struct EntryHeader {
+    format: u32,
+    tidemark: String,
+    sha256: String,
+}
+
+#[derive(Debug)]
+pub enum CacheLookup<T> {
+    Hit(T),
+    /// Missing or unusable locally, served by the remote tier and copied into the local cache.
+    RemoteHit(T),
+    Miss,
+    /// An entry exists but cannot be used; callers treat it as a miss and overwrite it.
+    Invalid(CacheInvalidKind),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheInvalid {
+    pub kind: CacheInvalidKind,
+    pub message: String,
+}
+
+impl fmt::Display for CacheInvalid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.kind, self.message)
+    }
+}
+
+/// Local `.git/tidemark-cache` tier plus an optional read-through, write-behind remote tier.
+#[derive(Debug, Clone)]
+pub struct CacheStore {
+    local: DirectoryBackend,
+    enabled: bool,
+    max_bytes: Option<u64>,
+    max_age: Option<Duration>,
+    remote: Option<Arc<RemoteTier>>,
+    memory: Option<Arc<MemoryTier>>,
+    /// Shared by clones so a long-lived process scans the directory as rarely as possible.
+    limits: Arc<Mutex<LimitScan>>,
+}
+
+/// Bookkeeping between limit scans of the local tier.
+#[derive(Debug, Default)]
+struct LimitScan {
+    /// Local tier size as of the last scan plus every write since; `None` until the first write
+    /// of the process scans the directory.
+    estimated_bytes: Option<u64>,
+    scanned_at: Option<Instant>,
+}
+
+type MemoryKey = (String, String);
+
+/// Encoded entries kept in process for long-lived resolvers such as `tide serve`.
+///
+/// Keys address immutable inputs, so a held entry stays correct even after the disk copy is
+/// pruned; the tier simply starts over once it holds `MEMORY_TIER_MAX_ENTRIES`.
+#[derive(Debug, Default)]
+struct MemoryTier {
+    entries: Mutex<HashMap<MemoryKey, Arc<Vec<u8>>>>,
+}
+
+impl MemoryTier {
+    fn load(&self, namespace: &str, key: &str) -> Option<Arc<Vec<u8>>> {
+        let entries = self.entries.lock().ok()?;
+        entries
+            .get(&(namespace.to_string(), key.to_string()))
+            .cloned()
+    }
+
+    fn store(&self, namespace: &str, key: &str, bytes: Vec<u8>) {
+        if let Ok(mut entries) = self.entries.lock() {
+            if entries.len() >= MEMORY_TIER_MAX_ENTRIES {
+                entries.clear();
+            }
+            entries.insert((namespace.to_string(), key.to_string()), Arc::new(bytes));
+        }
+    }
+}
+
+/// Remote backend plus its write-behind worker, joined when the last store clone is dropped.
+///
+/// The worker is fed by a bounded queue, so a large batch never holds more than
+/// `UPLOAD_QUEUE_DEPTH` uploads; a dropped upload only costs another job a remote miss.
+#[derive(Debug)]
+struct RemoteTier {
+    backend: Arc<dyn CacheBackend>,
+    health: Arc<RemoteHealth>,
+    uploader: Mutex<Option<Uploader>>,
+}
+
+#[derive(Debug)]
+struct Upload {
+    namespace: String,
+    key: String,
+    bytes: Vec<u8>,
+}
+
+#[derive(Debug)]
+struct Uploader {
+    queue: SyncSender<Upload>,
+    worker: JoinHandle<()>,
+}
+
+/// Circuit breaker: a failed request skips the remote tier for `REMOTE_BACKOFF`, so an
+/// unreachable server costs one timeout per window instead of one per key.
+#[derive(Debug, Default)]
+struct RemoteHealth {
+    retry_at: Mutex<Option<Instant>>,
+}
+
+impl RemoteHealth {
+    fn available(&self) -> bool {
+        match self.retry_at.lock() {
+            Ok(retry_at) => retry_at.is_none_or(|at| Instant::now() >= at),
+            Err(_) => false,
+        }
+    }
+
+    fn record<T>(&self, result: TideResult<T>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(_) => {
+                if let Ok(mut retry_at) = self.retry_at.lock() {
+                    *retry_at = Some(Instant::now() + REMOTE_BACKOFF);
+                }
+                None
+            }
+        }
+    }
+}
+
+impl RemoteTier {
+    fn new(backend: Arc<dyn CacheBackend>) -> Self {
+        Self {
+            backend,
+            health: Arc::default(),
+            uploader: Mutex::new(None),
+        }
+    }
+
+    /// Remote failures and unusable remote entries degrade to a miss.
+    fn fetch<T: DeserializeOwned>(&self, namespace: &str, key: &str) -> Option<(T, Vec<u8>)> {
+        if !self.health.available() {
+            return None;
+        }
+        let bytes = self.health.record(self.backend.load(namespace, key))??;
+        let value = CacheStore::decode::<T>(&bytes).ok()?;
+        Some((value, bytes))
+    }
+
+    /// Queue `bytes` for the worker, starting it on first use; dropped when the queue is full.
+    fn upload(&self, namespace: &str, key: &str, bytes: Vec<u8>) {
+        if !self.health.available() {
+            return;
+        }
+        let Ok(mut uploader) = self.uploader.lock() else {
+            return;
+        };
+        let uploader = uploader.get_or_insert_with(|| self.spawn_uploader());
+        let _ = uploader.queue.try_send(Upload {
+            namespace: namespace.to_string(),
+            key: key.to_string(),
+            bytes,
+        });
+    }
+
+    fn spawn_uploader(&self) -> Uploader {
+        let (queue, uploads) = mpsc::sync_channel::<Upload>(UPLOAD_QUEUE_DEPTH);
+        let backend = Arc::clone(&self.backend);
+        let health = Arc::clone(&self.health);
+        let worker = thread::spawn(move || {
+            for upload in uploads {
+                if health.available() {
+                    health.record(backend.store(
+                        upload.namespace.as_str(),
+                        upload.key.as_str(),
+                        &upload.bytes,
+                    ));
+                }
+            }
+        });
+        Uploader { queue, worker }
+    }
+
+    /// Close the queue and wait for the worker to drain it; the next upload starts a new one.
+    fn flush(&self) {
+        let uploader = match self.uploader.lock() {
+            Ok(mut uploader) => uploader.take(),
+            Err(_) => return,
+        };
+        if let Some(Uploader { queue, worker }) = uploader {
+            drop(queue);
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Drop for RemoteTier {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+/// One cache file as found on disk.
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    pub namespace: String,
+    pub key: String,
+    pub path: PathBuf,
+    pub bytes: u64,
+    pub modified: SystemTime,
+}
+
+impl CacheEntry {
+    pub fn age(&self, now: SystemTime) -> Duration {
+        now.duration_since(self.modified).unwrap_or_default()
+    }
+}
+
+impl CacheStore {
+    pub fn new(git_dir: &Path, enabled: bool) -> Self {
+        Self {
+            local: DirectoryBackend::new(git_dir.join("tidemark-cache")),
+            enabled,
+            max_bytes: None,
+            max_age: None,
+            remote: None,
+            memory: None,
+            limits: Arc::default(),
+        }
+    }
+
+    pub fn from_config(git_dir: &Path, config: &CacheConfig) -> TideResult<Self> {
+        let store = Self {
+            max_bytes: config.max_bytes,
+            max_age: config
+                .max_age_days
+                .map(|days| Duration::from_secs(u64::from(days) * SECONDS_PER_DAY)),
+            ..Self::new(git_dir, config.enabled)
+        };
+        Ok(
+            match config
+                .remote
+                .as_deref()
+                .filter(|raw| !raw.trim().is_empty())
+            {
+                Some(raw) => store.with_remote(RemoteSpec::parse(raw)?.into_backend()),
+                None => store,
+            },
+        )
+    }
+
+    /// Consult `backend` on local misses and upload local writes to it in the background.
+    pub fn with_remote(mut self, backend: Box<dyn CacheBackend>) -> Self {
+        self.remote = Some(Arc::new(RemoteTier::new(Arc::from(backend))));
+        self
+    }
+
+    /// Serve repeated lookups from process memory in front of the local tier.
+    pub fn with_memory(mut self) -> Self {
+        self.memory = Some(Arc::default());
+        self
+    }
+
+    pub fn remote_location(&self) -> Option<String> {
+        self.remote.as_ref().map(|remote| remote.backend.location())
+    }
+
+    /// Wait for background uploads; also happens when the last clone of the store is dropped.
+    pub fn flush(&self) {
+        if let Some(remote) = &self.remote {
+            remote.flush();
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        self.local.root()
+    }
+
+    pub fn key_from_serializable<T: Serialize>(namespace: &str, value: &T) -> TideResult<String> {
+        let payload = serde_json::to_vec(value).map_err(|err| TideError::CacheFormat {
+            message: err.to_string(),
+        })?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(namespace.as_bytes());
+        hasher.update([0u8]);
+        hasher.update(payload);
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn get<T: DeserializeOwned>(
+        &self,
+        namespace: &str,
+        key: &str,
+    ) -> TideResult<CacheLookup<T>> {
+        if !self.enabled {
+            return Ok(CacheLookup::Miss);
+        }
+        if let Some(value) = self
+            .memory
+            .as_ref()
+            .and_then(|memory| memory.load(namespace, key))
+            .and_then(|bytes| Self::decode::<T>(&bytes).ok())
+        {
+            return Ok(CacheLookup::Hit(value));
+        }
+        let local = match self.local.load(namespace, key)? {
+            Some(bytes) => match Self::decode::<T>(&bytes) {
+                Ok(value) => {
+                    self.remember(namespace, key, bytes);
+                    return Ok(CacheLookup::Hit(value));
+                }
+                Err(invalid) => CacheLookup::Invalid(invalid.kind),
+            },
+            None => CacheLookup::Miss,
+        };
+        if let Some((value, bytes)) = self
+            .remote
+            .as_ref()
+            .and_then(|remote| remote.fetch::<T>(namespace, key))
+        {
+            self.local.store(namespace, key, &bytes)?;
+            self.enforce_limits(self.local.path_for(namespace, key).as_path(), bytes.len())?;
+            self.remember(namespace, key, bytes);
+            return Ok(CacheLookup::RemoteHit(value));
+        }
+        Ok(local)
+    }
+
+    pub fn put<T: Serialize>(&self, namespace: &str, key: &str, value: &T) -> TideResult<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+        let bytes = Self::encode(value)?;
+        self.local.store(namespace, key, &bytes)?;
+        self.enforce_limits(self.local.path_for(namespace, key).as_path(), bytes.len())?;
+        if let Some(remote) = &self.remote {
+            remote.upload(namespace, key, bytes.clone());
+        }
+        self.remember(namespace, key, bytes);
+        Ok(())
+    }
+
+    fn remember(&self, namespace: &str, key: &str, bytes: Vec<u8>) {
+        if let Some(memory) = &self.memory {
+            memory.store(namespace, key, bytes);
+        }
+    }
+
+    /// Header line plus payload, as written to disk.
+    pub fn encode<T: Serialize>(value: &T) -> TideResult<Vec<u8>> {
+        let payload = serde_json::to_vec(value).map_err(|err| TideError::CacheFormat {
+            message: err.to_string(),
+        })?;
+        let header = EntryHeader {
+            format: CACHE_FORMAT_VERSION,
+            tidemark: TOOL_VERSION.to_string(),
+            sha256: hex::encode(Sha256::digest(&payload)),
+        };
+        let mut out = serde_json::to_vec(&header).map_err(|err| TideError::CacheFormat {
+            message: err.to_string(),
+        })?;
+        out.push(b'\n');
+        out.extend_from_slice(&payload);
+        Ok(out)
+    }
+
+    /// Check header, versions, and checksum, then deserialize the payload.
+    pub fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, CacheInvalid> {
+        let invalid = |kind, message: String| CacheInvalid { kind, message };
+        let split = bytes
+            .iter()
+            .position(|byte| *byte == b'\n')
+            .ok_or_else(|| invalid(CacheInvalidKind::Corrupt, "missing header".to_string()))?;
+        let (header, payload) = (&bytes[..split], &bytes[split + 1..]);
+        let header = serde_json::from_slice::<EntryHeader>(header)
+            .map_err(|err| invalid(CacheInvalidKind::Corrupt, format!("header: {err}")))?;
+        if header.format != CACHE_FORMAT_VERSION {
+            return Err(invalid(
+                CacheInvalidKind::FormatVersion,
+                format!("format {} != {CACHE_FORMAT_VERSION}", header.format),
+            ));
+        }
+        if header.tidemark != TOOL_VERSION {
+            return Err(invalid(
+                CacheInvalidKind::ToolVersion,
+                format!("written by tidemark {}", header.tidemark),
+            ));
+        }
+        if hex::encode(Sha256::digest(payload)) != header.sha256 {
+            return Err(invalid(
+                CacheInvalidKind::Checksum,
+                "payload checksum mismatch".to_string(),
+            ));
+        }
+        serde_json::from_slice::<T>(payload)
+            .map_err(|err| invalid(CacheInvalidKind::Corrupt, format!("payload: {err}")))
+    }
+
+    /// Every entry in every namespace, sorted by namespace then key. Ignores the `enabled` flag.
+    pub fn entries(&self) -> TideResult<Vec<CacheEntry>> {
+        let mut entries = Vec::new();
+        for namespace in read_dir_sorted(self.local.root())? {
+            if !namespace.is_dir() {
+                continue;
+            }
+            let Some(name) = namespace.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            for path in read_dir_sorted(&namespace)? {
+                if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                    continue;
+                }
+                let Some(key) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                    continue;
+                };
+                let Some(meta) = metadata_if_present(&path)? else {
+                    continue;
+                };
+                entries.push(CacheEntry {
+                    namespace: name.to_string(),
+                    key: key.to_string(),
+                    bytes: meta.len(),
+                    modified: meta.modified().map_err(|err| io_err(&path, err))?,
+                    path,
+                });
+            }
+        }
+        Ok(entries)
+    }
+
+    pub fn read_raw(&self, entry: &CacheEntry) -> TideResult<Vec<u8>> {
+        fs::read(&entry.path).map_err(|err| io_err(&entry.path, err))
+    }
+
+    /// Delete `entry`; an entry already removed by another process is not an error.
+    pub fn remove(&self, entry: &CacheEntry) -> TideResult<()> {
+        match fs::remove_file(&entry.path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(io_err(&entry.path, err)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Account for a write of `written_bytes` and evict when limits may be exceeded: on the first
+    /// write of the process, whenever the size estimate passes `max_bytes`, and when `max_age` is
+    /// set and the last scan is older than `max_age` or `AGE_SCAN_INTERVAL`, whichever is
+    /// shorter. Other writes skip the directory scan.
+    fn enforce_limits(&self, written: &Path, written_bytes: usize) -> TideResult<()> {
+        if self.max_bytes.is_none() && self.max_age.is_none() {
+            return Ok(());
+        }
+        let mut limits = self.limits.lock().map_err(|_| TideError::Internal {
+            message: "cache limit state lock poisoned".to_string(),
+        })?;
+        let aged = match (self.max_age, limits.scanned_at) {
+            (Some(max_age), Some(scanned_at)) => {
+                scanned_at.elapsed() >= max_age.min(AGE_SCAN_INTERVAL)
+            }
+            _ => false,
+        };
+        if let Some(bytes) = limits.estimated_bytes.as_mut() {
+            *bytes += written_bytes as u64;
+            if !aged && self.max_bytes.is_none_or(|max| *bytes <= max) {
+                return Ok(());
+            }
+        }
+        limits.estimated_bytes = Some(self.evict(written)?);
+        limits.scanned_at = Some(Instant::now());
+        Ok(())
+    }
+
+    /// Apply `max_age` then `max_bytes`, evicting oldest first but never the entry just written;
+    /// returns the bytes left in the local tier.
+    fn evict(&self, written: &Path) -> TideResult<u64> {
+        let now = SystemTime::now();
+        let mut entries = self.entries()?;
+        entries.sort_by(|a, b| a.modified.cmp(&b.modified).then(a.path.cmp(&b.path)));
+
+        let mut kept = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let expired = self.max_age.is_some_and(|max| entry.age(now) > max);
+            if expired && entry.path != written {
+                self.remove(&entry)?;
+            } else {
+                kept.push(entry);
+            }
+        }
+
+        let mut total: u64 = kept.iter().map(|entry| entry.bytes).sum();
+        if let Some(max_bytes) = self.max_bytes {
+            for entry in &kept {
+                if total <= max_bytes {
+                    break;
+                }
+                if entry.path == written {
+                    continue;
+                }
+                self.remove(entry)?;
+                total -= entry.bytes;
+            }
+        }
+        Ok(total)
+    }
+}
+
+fn read_dir_sorted(dir: &Path) -> TideResult<Vec<PathBuf>> {
+    let reader = match fs::read_dir(dir) {
+        Ok(reader) => reader,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(io_err(dir, err)),
+    };
+    let mut paths = reader
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| io_err(dir, err))?;
+    paths.sort();
+    Ok(paths)
+}
+
+fn metadata_if_present(path: &Path) -> TideResult<Option<fs::Metadata>> {
+    match fs::metadata(path) {
+        Ok(meta) => Ok(Some(meta)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(io_err(path, err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn put_evicts_oldest_entries_over_the_size_limit() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let entry_bytes = CacheStore::encode(&"0123456789012345")
+            .expect("encode")
+            .len() as u64;
+        let config = CacheConfig {
+            max_bytes: Some(entry_bytes * 2 + entry_bytes / 2),
+            ..CacheConfig::default()
+        };
+        let store = CacheStore::from_config(dir.path(), &config).expect("store");
+        for key in ["a", "b", "c"] {
+            store
+                .put("mark", key, &"0123456789012345")
+                .expect("put entry");
+            std::thread::sleep(Duration::from_millis(20));
+        }
+
+        let keys: Vec<String> = store
+            .entries()
+            .expect("entries")
+            .into_iter()
+            .map(|entry| entry.key)
+            .collect();
+        assert_eq!(keys, vec!["b", "c"]);
+    }
+
+    #[test]
+    fn put_rescans_only_when_the_size_estimate_passes_the_limit() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let entry_bytes = CacheStore::encode(&"0123456789012345")
+            .expect("encode")
+            .len() as u64;
+        let config = CacheConfig {
+            max_bytes: Some(entry_bytes * 3 + entry_bytes / 2),
+            ..CacheConfig::default()
+        };
+        let limited = CacheStore::from_config(dir.path(), &config).expect("store");
+        let other = CacheStore::new(dir.path(), true);
+        let keys = |store: &CacheStore| -> Vec<String> {
+            store
+                .entries()
+                .expect("entries")
+                .into_iter()
+                .map(|entry| entry.key)
+                .collect()
+        };
+
+        limited.put("mark", "a", &"0123456789012345").expect("put");
+        std::thread::sleep(Duration::from_millis(20));
+        // Another writer fills the cache past the limit; this store's estimate does not see it.
+        for key in ["b", "c", "d"] {
+            other.put("mark", key, &"0123456789012345").expect("put");
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        limited.put("mark", "e", &"0123456789012345").expect("put");
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(keys(&limited), vec!["a", "b", "c", "d", "e"]);
+
+        // Crossing the estimate scans and evicts the oldest entries.
+        limited.put("mark", "f", &"0123456789012345").expect("put");
+        std::thread::sleep(Duration::from_millis(20));
+        limited.put("mark", "g", &"0123456789012345").expect("put");
+        assert_eq!(keys(&limited), vec!["e", "f", "g"]);
+    }
+
+    #[test]
+    fn put_expires_entries_again_in_a_long_lived_store() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let store = CacheStore {
+            max_age: Some(Duration::from_millis(300)),
+            ..CacheStore::new(dir.path(), true)
+        };
+        let keys = || -> Vec<String> {
+            store
+                .entries()
+                .expect("entries")
+                .into_iter()
+                .map(|entry| entry.key)
+                .collect()
+        };
+
+        store.put("mark", "a", &"0123456789012345").expect("put");
+        store.put("mark", "b", &"0123456789012345").expect("put");
+        assert_eq!(keys(), vec!["a", "b"]);
+
+        // Past the expiry boundary the next write scans again, without any size pressure.
+        std::thread::sleep(Duration::from_millis(400));
+        store.put("mark", "c", &"0123456789012345").expect("put");
+        assert_eq!(keys(), vec!["c"]);
+    }
+
+    #[test]
+    fn decode_rejects_damaged_and_foreign_entries() {
+        let encoded = CacheStore::encode(&vec![1u32, 2, 3]).expect("encode");
+        assert_eq!(
+            CacheStore::decode::<Vec<u32>>(&encoded).expect("decode"),
+            vec![1, 2, 3]
+        );
+
+        let kind = |bytes: &[u8]| {
+            CacheStore::decode::<Vec<u32>>(bytes)
+                .expect_err("invalid entry")
+                .kind
+        };
+        assert_eq!(
+            kind(&encoded[..encoded.len() - 2]),
+            CacheInvalidKind::Checksum
+        );
+        assert_eq!(kind(b"[1,2,3]"), CacheInvalidKind::Corrupt);
+
+        let text = String::from_utf8(encoded).expect("utf8");
+        let older = text.replacen(TOOL_VERSION, "0.0.1", 1);
+        assert_eq!(kind(older.as_bytes()), CacheInvalidKind::ToolVersion);
+        let newer = text.replacen("\"format\":1", "\"format\":99", 1);
+        assert_eq!(kind(newer.as_bytes()), CacheInvalidKind::FormatVersion);
+    }
+}
@@ -15,16 +15,24 @@
 //! Copyright (c) 2026-2027 easynet. All rights reserved.
 
 use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    io::{BufRead, BufReader, Read},
     path::{Path, PathBuf},
-    process::{Command, Output},
+    process::{Command, Output, Stdio},
+    thread,
 };
 
 use crate::{
-    core::model::{CommitInfo, TagRef, TagSource},
+    core::model::{CommitInfo, RemoteFailureKind, TagRef, TagSource},
     error::{TideError, TideResult, io_err},
-    infra::git::GitProvider,
+    infra::git::{CommitNode, DeepenStep, GitProvider, ReachableCommit},
 };
 
+/// History under the oldest candidate beyond which a distance walk is bounded by the candidates'
+/// merge base; shallower histories cost less to walk than the merge base costs to compute.
+const DEEP_HISTORY_COMMITS: usize = 1000;
+
 #[derive(Debug, Clone)]
 pub struct GitCli {
     repo_root: PathBuf,
@@ -44,6 +52,40 @@ impl GitCli {
         })
     }
 
+    /// Parents of the merge base of `target` and every candidate. A distance walk can stop
+    /// there: older commits are ancestors of the target and of every reachable candidate alike
+    /// and cancel out of each distance. `None`, meaning the whole history is walked, when the
+    /// oldest candidate has less than `DEEP_HISTORY_COMMITS` of history below it, when a
+    /// candidate is missing locally, or when there is no common base.
+    fn distance_floor(&self, target: &str, candidates: &[String]) -> TideResult<Option<String>> {
+        // Release candidates arrive oldest first.
+        let Some(oldest) = candidates.first() else {
+            return Ok(None);
+        };
+        let max_count = format!("--max-count={}", DEEP_HISTORY_COMMITS + 1);
+        let probe = self.run_git(&["rev-list", "--count", max_count.as_str(), oldest])?;
+        let depth = stdout_trimmed(&probe)?.parse::<usize>().unwrap_or(0);
+        if !probe.status.success() || depth <= DEEP_HISTORY_COMMITS {
+            return Ok(None);
+        }
+        // Folding the octopus newest first keeps each of its pairwise steps short.
+        let mut seen = HashSet::new();
+        let mut args = vec!["merge-base", "--octopus", target];
+        args.extend(
+            candidates
+                .iter()
+                .rev()
+                .map(String::as_str)
+                .filter(|candidate| seen.insert(*candidate)),
+        );
+        let output = self.run_git(&args)?;
+        if !output.status.success() {
+            return Ok(None);
+        }
+        let base = stdout_trimmed(&output)?;
+        Ok((!base.is_empty()).then(|| format!("{base}^@")))
+    }
+
     fn run_git(&self, args: &[&str]) -> TideResult<Output> {
         run_git_at(&self.repo_root, args)
     }
@@ -78,6 +120,63 @@ impl GitCli {
             timestamp,
         })
     }
+
+    /// `%H%x09%ct%x09%P` line; root commits have an empty parent field.
+    fn parse_graph_line(&self, line: &str) -> TideResult<CommitNode> {
+        let mut fields = line.splitn(3, '\t');
+        let commit_line = format!(
+            "{}\t{}",
+            fields.next().unwrap_or_default(),
+            fields.next().unwrap_or_default()
+        );
+        Ok(CommitNode {
+            commit: self.parse_commit_line(commit_line.as_str())?,
+            parents: fields
+                .next()
+                .unwrap_or_default()
+                .split_whitespace()
+                .map(str::to_string)
+                .collect(),
+        })
+    }
+
+    /// Give `walked` one diff per parent (one for a root commit). `log -m` prints nothing for a
+    /// parent whose tree equals the merge's, so when some are missing the parents with that tree
+    /// get empty diffs and the printed ones go to the rest in order.
+    fn complete_diffs(&self, mut walked: WalkedCommit) -> TideResult<WalkedCommit> {
+        let parents = &walked.node.parents;
+        let slots = parents.len().max(1);
+        if walked.diffs.len() == slots {
+            return Ok(walked);
+        }
+        if walked.diffs.is_empty() {
+            walked.diffs = vec![HashSet::new(); slots];
+            return Ok(walked);
+        }
+        let mut revs = vec![
+            "rev-parse".to_string(),
+            format!("{}^{{tree}}", walked.node.commit.id),
+        ];
+        revs.extend(parents.iter().map(|parent| format!("{parent}^{{tree}}")));
+        let args: Vec<&str> = revs.iter().map(String::as_str).collect();
+        let out = self.run_git_checked(&args)?;
+        let mut trees = out.lines();
+        let own = trees.next().unwrap_or_default();
+        let mut printed = std::mem::take(&mut walked.diffs).into_iter();
+        for tree in trees {
+            walked.diffs.push(if tree == own {
+                HashSet::new()
+            } else {
+                printed.next().unwrap_or_default()
+            });
+        }
+        if walked.diffs.len() != slots || printed.next().is_some() {
+            return Err(TideError::Internal {
+                message: format!("unexpected merge diffs for {}", walked.node.commit.id),
+            });
+        }
+        Ok(walked)
+    }
 }
 
 impl GitProvider for GitCli {
@@ -164,22 +263,74 @@ impl GitProvider for GitCli {
     }
 
     fn list_remote_tags(&self, remote: &str, prefix: &str) -> TideResult<Vec<TagRef>> {
+        let output = self.run_git(&["ls-remote", "--tags", remote])?;
+        if !output.status.success() {
+            return Err(remote_refresh_error(remote, &output));
+        }
+
+        // `ls-remote` prints the tag object and, for annotated tags, a `^{}` line with the peeled commit.
+        let mut objects: BTreeMap<String, (String, Option<String>)> = BTreeMap::new();
+        for line in stdout_trimmed(&output)?.lines() {
+            let mut fields = line.split('\t');
+            let object = fields.next().unwrap_or_default().trim();
+            let ref_name = fields.next().unwrap_or_default().trim();
+            let Some(name) = ref_name.strip_prefix("refs/tags/") else {
+                continue;
+            };
+            if let Some(name) = name.strip_suffix("^{}") {
+                if name.starts_with(prefix) {
+                    objects.entry(name.to_string()).or_default().1 = Some(object.to_string());
+                }
+            } else if name.starts_with(prefix) {
+                objects.entry(name.to_string()).or_default().0 = object.to_string();
+            }
+        }
+
+        let mut tags = Vec::new();
+        for (name, (object, peeled)) in objects {
+            let is_annotated = peeled.is_some();
+            let commit_id = peeled.unwrap_or(object);
+            if commit_id.is_empty() {
+                continue;
+            }
+            tags.push(TagRef {
+                name,
+                commit_id,
+                is_annotated,
+                source: TagSource::Remote(remote.to_string()),
+            });
+        }
+
+        Ok(tags)
+    }
+
+    fn fetch_tag_objects(&self, remote: &str, tags: &[String]) -> TideResult<()> {
+        if tags.is_empty() {
+            return Ok(());
+        }
+        // Fetch objects only; no refspec destination and no FETCH_HEAD keeps refs untouched.
+        let refs: Vec<String> = tags.iter().map(|tag| format!("refs/tags/{tag}")).collect();
+        let mut args = vec![
+            "fetch",
+            "--quiet",
+            "--no-tags",
+            "--no-write-fetch-head",
+            remote,
+        ];
+        args.extend(refs.iter().map(String::as_str));
+        let output = self.run_git(&args)?;
+        if !output.status.success() {
+            return Err(remote_refresh_error(remote, &output));
+        }
+        Ok(())
+    }
+
+    fn fetch_remote_tags(&self, remote: &str, prefix: &str) -> TideResult<Vec<TagRef>> {
         let refspec = format!("+refs/tags/{prefix}*:refs/tidemark/remote-tags/{prefix}*");
         let output =
             self.run_git(&["fetch", "--quiet", "--prune", "--no-tags", remote, &refspec])?;
         if !output.status.success() {
-            return Err(TideError::GitCommand {
-                args: vec![
-                    "fetch".to_string(),
-                    "--quiet".to_string(),
-                    "--prune".to_string(),
-                    "--no-tags".to_string(),
-                    remote.to_string(),
-                    refspec,
-                ],
-                stderr: stderr_trimmed(&output),
-                code: output.status.code(),
-            });
+            return Err(remote_refresh_error(remote, &output));
         }
 
         let out = self.run_git_checked(&[
@@ -217,7 +368,7 @@ impl GitProvider for GitCli {
                 name: name.to_string(),
                 commit_id,
                 is_annotated,
-                source: TagSource::Remote,
+                source: TagSource::Remote(remote.to_string()),
             });
         }
 
@@ -250,6 +401,28 @@ impl GitProvider for GitCli {
         })
     }
 
+    fn reachable_with_distances(
+        &self,
+        target: &str,
+        candidates: &[String],
+    ) -> TideResult<Vec<ReachableCommit>> {
+        if candidates.is_empty() {
+            return Ok(Vec::new());
+        }
+        let floor = self.distance_floor(target, candidates)?;
+        let mut args = vec!["log", "--topo-order", "--format=%H%x09%ct%x09%P", target];
+        if let Some(floor) = floor.as_deref() {
+            args.extend(["--not", floor]);
+        }
+        let out = self.run_git_checked(&args)?;
+        let nodes = out
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| self.parse_graph_line(line))
+            .collect::<TideResult<Vec<_>>>()?;
+        Ok(reachable_distances(&nodes, candidates))
+    }
+
     fn ancestry_path_commits(
         &self,
         ancestor: &str,
@@ -273,6 +446,97 @@ impl GitProvider for GitCli {
         Ok(commits)
     }
 
+    fn ancestry_path_graph(
+        &self,
+        ancestor: &str,
+        descendant: &str,
+        known: &[String],
+    ) -> TideResult<Vec<CommitNode>> {
+        if ancestor == descendant {
+            return Ok(Vec::new());
+        }
+        // `--ancestry-path` with the known tips as extra bottoms would drop branches forking from
+        // an indexed commit below a tip, so walk the plain range and keep descendants here.
+        let mut args = vec![
+            "log",
+            "--topo-order",
+            "--reverse",
+            "--format=%H%x09%ct%x09%P",
+            descendant,
+            "--not",
+            ancestor,
+        ];
+        args.extend(known.iter().map(String::as_str));
+        let out = self.run_git_checked(&args)?;
+        let nodes = out
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| self.parse_graph_line(line))
+            .collect::<TideResult<Vec<_>>>()?;
+
+        let listed: HashSet<String> = nodes.iter().map(|node| node.commit.id.clone()).collect();
+        let mut on_path: HashSet<String> = HashSet::new();
+        // Parents outside the walk are the anchor's or a known tip's ancestors; they are on the
+        // path exactly when they descend from the anchor.
+        let mut boundary: HashMap<String, bool> = HashMap::new();
+        let mut path = Vec::new();
+        for node in nodes {
+            let mut descends = false;
+            for parent in &node.parents {
+                descends = if on_path.contains(parent) || parent == ancestor {
+                    true
+                } else if listed.contains(parent) {
+                    false
+                } else if let Some(&hit) = boundary.get(parent) {
+                    hit
+                } else {
+                    let hit = self.is_ancestor(ancestor, parent)?;
+                    boundary.insert(parent.clone(), hit);
+                    hit
+                };
+                if descends {
+                    break;
+                }
+            }
+            if descends {
+                on_path.insert(node.commit.id.clone());
+                path.push(node);
+            }
+        }
+        Ok(path)
+    }
+
+    fn first_parent_distance(&self, ancestor: &str, descendant: &str) -> TideResult<u32> {
+        let range = format!("{ancestor}..{descendant}");
+        let out = self.run_git_checked(&["rev-list", "--first-parent", "--count", &range])?;
+        out.parse::<u32>().map_err(|_| TideError::Internal {
+            message: format!("invalid commit count output: {out}"),
+        })
+    }
+
+    fn first_parent_commits(
+        &self,
+        ancestor: &str,
+        descendant: &str,
+    ) -> TideResult<Vec<CommitInfo>> {
+        if ancestor == descendant {
+            return Ok(Vec::new());
+        }
+        let range = format!("{ancestor}..{descendant}");
+        let out = self.run_git_checked(&[
+            "log",
+            "--first-parent",
+            "--reverse",
+            "--format=%H%x09%ct",
+            &range,
+        ])?;
+        let mut commits = Vec::new();
+        for line in out.lines().filter(|line| !line.trim().is_empty()) {
+            commits.push(self.parse_commit_line(line)?);
+        }
+        Ok(commits)
+    }
+
     fn last_modifying_commit(&self, path: &Path, follow_renames: bool) -> TideResult<CommitInfo> {
         let normalized_path = if path.is_absolute() {
             path.strip_prefix(&self.repo_root)
@@ -282,12 +546,18 @@ impl GitProvider for GitCli {
             path.to_path_buf()
         };
 
-        let path_arg = normalized_path.to_string_lossy().to_string();
-        let mut args = vec!["log", "-n", "1", "--format=%H%x09%ct"];
         if follow_renames {
-            args.push("--follow");
+            // `log --follow` skips merges; the batch walk counts them, so answer from it.
+            return self
+                .last_modifying_commits(std::slice::from_ref(&normalized_path), true)?
+                .remove(&normalized_path)
+                .ok_or(TideError::FileHistoryNotFound {
+                    path: normalized_path,
+                });
         }
-        args.push("--");
+
+        let path_arg = normalized_path.to_string_lossy().to_string();
+        let mut args = vec!["log", "-n", "1", "--format=%H%x09%ct", "--"];
         args.push(path_arg.as_str());
 
         let output = self.run_git(&args)?;
@@ -308,16 +578,203 @@ impl GitProvider for GitCli {
         self.parse_commit_line(out.as_str())
     }
 
-    fn root_commit(&self) -> TideResult<CommitInfo> {
-        let out = self.run_git_checked(&["rev-list", "--max-parents=0", "HEAD"])?;
-        let first_root = out
+    fn last_modifying_commits(
+        &self,
+        paths: &[PathBuf],
+        follow_renames: bool,
+    ) -> TideResult<HashMap<PathBuf, CommitInfo>> {
+        let mut walk = HistoryWalk::new(paths, follow_renames);
+        if walk.is_done() {
+            return Ok(walk.found);
+        }
+        // One unsimplified walk answers every path; `-m` diffs each merge against every parent,
+        // so merges can be judged the way each mode defines them.
+        let args = [
+            "log",
+            "-m",
+            "--format=%x1e%H%x09%ct%x09%P",
+            "--name-only",
+            "--no-renames",
+            "-z",
+            "HEAD",
+        ];
+        let mut child = Command::new("git")
+            .arg("-C")
+            .arg(&self.repo_root)
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| io_err(&self.repo_root, err))?;
+        // Drain stderr while stdout is read so git never blocks on a full pipe.
+        let mut stderr_pipe = child.stderr.take().expect("piped stderr");
+        let stderr = thread::spawn(move || {
+            let mut text = String::new();
+            let _ = stderr_pipe.read_to_string(&mut text);
+            text
+        });
+
+        let mut stdout = BufReader::new(child.stdout.take().expect("piped stdout"));
+        let mut current: Option<WalkedCommit> = None;
+        let mut record = Vec::new();
+        while !walk.is_done() {
+            record.clear();
+            let read = stdout
+                .read_until(0x1e, &mut record)
+                .map_err(|err| io_err(&self.repo_root, err))?;
+            if read == 0 {
+                break;
+            }
+            // `<id>\t<ct>\t<parents>\0` then `\n<name>\0<name>\0...`; a merge repeats the record
+            // once per parent it differs from.
+            let record = record.strip_suffix(&[0x1e]).unwrap_or(&record);
+            let mut fields = record.split(|byte| *byte == 0);
+            let header = fields.next().unwrap_or_default();
+            if header.is_empty() {
+                continue;
+            }
+            let header = std::str::from_utf8(header).map_err(|_| TideError::InvalidUtf8)?;
+            let node = self.parse_graph_line(header)?;
+            if current.as_ref().is_none_or(|walked| walked.node != node) {
+                if let Some(done) = current.take() {
+                    walk.visit(self.complete_diffs(done)?);
+                }
+                current = Some(WalkedCommit {
+                    node,
+                    diffs: Vec::new(),
+                });
+            }
+            let names: Vec<&str> = fields
+                .map(|name| name.strip_prefix(b"\n").unwrap_or(name))
+                .filter_map(|name| std::str::from_utf8(name).ok())
+                .filter(|name| !name.is_empty())
+                .collect();
+            // A header without names is git showing a commit whose diffs are all empty.
+            if !names.is_empty() {
+                let diff = walk.wanted(names);
+                current.as_mut().expect("header seen").diffs.push(diff);
+            }
+        }
+        drop(stdout);
+        if let Some(done) = current.take()
+            && !walk.is_done()
+        {
+            walk.visit(self.complete_diffs(done)?);
+        }
+
+        if walk.is_done() {
+            // Everything is answered; the rest of the walk is not needed.
+            let _ = child.kill();
+            let _ = child.wait();
+            let _ = stderr.join();
+            return Ok(walk.found);
+        }
+        let status = child.wait().map_err(|err| io_err(&self.repo_root, err))?;
+        let stderr = stderr.join().unwrap_or_default();
+        if !status.success() {
+            return Err(TideError::GitCommand {
+                args: args.iter().map(|arg| arg.to_string()).collect(),
+                stderr: stderr.trim().to_string(),
+                code: status.code(),
+            });
+        }
+        Ok(walk.found)
+    }
+
+    fn tracked_files(&self, pathspecs: &[String]) -> TideResult<Vec<PathBuf>> {
+        let mut args = vec!["ls-files", "-z", "--full-name", "--"];
+        args.extend(pathspecs.iter().map(String::as_str));
+        let output = self.run_git(&args)?;
+        if !output.status.success() {
+            return Err(TideError::GitCommand {
+                args: args.iter().map(|arg| arg.to_string()).collect(),
+                stderr: stderr_trimmed(&output),
+                code: output.status.code(),
+            });
+        }
+        let out = String::from_utf8(output.stdout).map_err(|_| TideError::InvalidUtf8)?;
+        Ok(out
+            .split('\0')
+            .filter(|name| !name.is_empty())
+            .map(PathBuf::from)
+            .collect())
+    }
+
+    fn root_commits(&self, rev: &str) -> TideResult<Vec<CommitInfo>> {
+        let out = self.run_git_checked(&["rev-list", "--max-parents=0", rev])?;
+        let mut roots = Vec::new();
+        for line in out.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            roots.push(self.resolve_commit(line)?);
+        }
+        Ok(roots)
+    }
+
+    fn first_parent_root(&self, rev: &str) -> TideResult<CommitInfo> {
+        let out = self.run_git_checked(&["rev-list", "--first-parent", "--max-parents=0", rev])?;
+        let root = out
             .lines()
             .next()
             .ok_or_else(|| TideError::Internal {
                 message: "no root commit found".to_string(),
             })?
             .trim();
-        self.resolve_commit(first_root)
+        self.resolve_commit(root)
+    }
+
+    fn is_dirty(&self) -> TideResult<bool> {
+        let out = self.run_git_checked(&["status", "--porcelain", "--untracked-files=no"])?;
+        Ok(!out.is_empty())
+    }
+
+    fn shallow_boundaries(&self) -> TideResult<Vec<String>> {
+        let out = self.run_git_checked(&["rev-parse", "--git-path", "shallow"])?;
+        let mut path = PathBuf::from(out);
+        if !path.is_absolute() {
+            path = self.repo_root.join(path);
+        }
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let raw = fs::read_to_string(&path).map_err(|err| io_err(&path, err))?;
+        Ok(raw
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    fn deepen(&self, remote: &str, step: &DeepenStep) -> TideResult<()> {
+        let depth_arg =
+            match step {
+                DeepenStep::Commits(count) => format!("--deepen={count}"),
+                DeepenStep::Since(ts) => {
+                    let since = chrono::DateTime::<chrono::Utc>::from_timestamp(*ts, 0)
+                        .ok_or_else(|| TideError::Internal {
+                            message: format!("invalid unix timestamp: {ts}"),
+                        })?;
+                    format!("--shallow-since={}", since.format("%Y-%m-%dT%H:%M:%SZ"))
+                }
+            };
+        let output = self.run_git(&["fetch", "--quiet", "--no-tags", &depth_arg, remote])?;
+        if !output.status.success() {
+            return Err(remote_refresh_error(remote, &output));
+        }
+        Ok(())
+    }
+
+    fn reachable_from_refs(&self, commits: &[String]) -> TideResult<Vec<String>> {
+        if commits.is_empty() {
+            return Ok(Vec::new());
+        }
+        let out = self.run_git_checked(&["rev-list", "--all", "HEAD"])?;
+        let reachable: HashSet<&str> = out.lines().map(str::trim).collect();
+        Ok(commits
+            .iter()
+            .filter(|commit| reachable.contains(commit.as_str()))
+            .cloned()
+            .collect())
     }
 
     fn current_branch(&self) -> TideResult<Option<String>> {
@@ -339,6 +796,104 @@ impl GitProvider for GitCli {
     }
 }
 
+/// A commit from the `log -m` walk with, per parent, the requested paths that differ from it.
+struct WalkedCommit {
+    node: CommitNode,
+    diffs: Vec<HashSet<PathBuf>>,
+}
+
+/// Per-path state of `last_modifying_commits` while the walk streams in.
+///
+/// A path's answer is a commit that differs from every parent on it (for a root commit, one that
+/// contains it). With `follow_renames` the first such commit in walk order wins, as a single
+/// `log --follow` would find it, except that merges changing the path against every parent count
+/// too. Without it each path moves from `HEAD` to the first parent it is unchanged against, which
+/// is how `log -- <path>` simplifies history; a root commit without the path means no history.
+struct HistoryWalk {
+    follow_renames: bool,
+    pending: HashSet<PathBuf>,
+    /// Without `follow_renames`: the commit each pending path has reached.
+    cursors: HashMap<PathBuf, String>,
+    /// Without `follow_renames`: every commit walked so far, as cursors may lag behind.
+    walked: HashMap<String, WalkedCommit>,
+    found: HashMap<PathBuf, CommitInfo>,
+}
+
+impl HistoryWalk {
+    fn new(paths: &[PathBuf], follow_renames: bool) -> Self {
+        Self {
+            follow_renames,
+            pending: paths.iter().cloned().collect(),
+            cursors: HashMap::new(),
+            walked: HashMap::new(),
+            found: HashMap::new(),
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// The requested paths among `names`.
+    fn wanted(&self, names: Vec<&str>) -> HashSet<PathBuf> {
+        names
+            .into_iter()
+            .map(PathBuf::from)
+            .filter(|name| self.pending.contains(name))
+            .collect()
+    }
+
+    fn visit(&mut self, walked: WalkedCommit) {
+        if self.follow_renames {
+            let pending = std::mem::take(&mut self.pending);
+            for path in pending {
+                if walked.diffs.iter().all(|diff| diff.contains(&path)) {
+                    self.found.insert(path, walked.node.commit.clone());
+                } else {
+                    self.pending.insert(path);
+                }
+            }
+            return;
+        }
+
+        if self.walked.is_empty() {
+            // The walk starts at HEAD.
+            for path in &self.pending {
+                self.cursors
+                    .insert(path.clone(), walked.node.commit.id.clone());
+            }
+        }
+        self.walked.insert(walked.node.commit.id.clone(), walked);
+        let pending: Vec<PathBuf> = self.pending.iter().cloned().collect();
+        for path in pending {
+            while let Some(commit) = self
+                .cursors
+                .get(&path)
+                .and_then(|cursor| self.walked.get(cursor))
+            {
+                let unchanged = commit.diffs.iter().position(|diff| !diff.contains(&path));
+                match (
+                    unchanged,
+                    commit.node.parents.get(unchanged.unwrap_or_default()),
+                ) {
+                    (Some(_), Some(parent)) => {
+                        self.cursors.insert(path.clone(), parent.clone());
+                    }
+                    (Some(_), None) => {
+                        self.pending.remove(&path);
+                        break;
+                    }
+                    (None, _) => {
+                        self.found.insert(path.clone(), commit.node.commit.clone());
+                        self.pending.remove(&path);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
 fn run_git_at(dir: &Path, args: &[&str]) -> TideResult<Output> {
     Command::new("git")
         .arg("-C")
@@ -356,3 +911,214 @@ fn stdout_trimmed(output: &Output) -> TideResult<String> {
 fn stderr_trimmed(output: &Output) -> String {
     String::from_utf8_lossy(&output.stderr).trim().to_string()
 }
+
+fn remote_refresh_error(remote: &str, output: &Output) -> TideError {
+    let stderr = stderr_trimmed(output);
+    TideError::RemoteRefresh {
+        remote: remote.to_string(),
+        kind: classify_remote_failure(stderr.as_str()),
+        stderr: stderr_summary(stderr.as_str()),
+    }
+}
+
+/// Collapse multi-line git stderr into a single line so it stays safe in key=value output.
+fn stderr_summary(stderr: &str) -> String {
+    stderr
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+fn classify_remote_failure(stderr: &str) -> RemoteFailureKind {
+    let lower = stderr.to_ascii_lowercase();
+    let matches_any = |needles: &[&str]| needles.iter().any(|needle| lower.contains(needle));
+
+    if matches_any(&["couldn't find remote ref", "invalid refspec"]) {
+        RemoteFailureKind::MissingRefspec
+    } else if matches_any(&[
+        "does not appear to be a git repository",
+        "no such remote",
+        "repository not found",
+    ]) {
+        RemoteFailureKind::MissingRemote
+    } else if matches_any(&[
+        "authentication failed",
+        "permission denied",
+        "could not read username",
+        "could not read password",
+        "terminal prompts disabled",
+        "host key verification failed",
+        "403",
+    ]) {
+        RemoteFailureKind::Auth
+    } else if matches_any(&[
+        "could not resolve host",
+        "connection refused",
+        "connection timed out",
+        "operation timed out",
+        "network is unreachable",
+        "unable to access",
+        "early eof",
+        "the remote end hung up",
+    ]) {
+        RemoteFailureKind::Network
+    } else {
+        RemoteFailureKind::Other
+    }
+}
+
+/// Distances from every reachable candidate to `nodes[0]`, given the target's full history
+/// with children before parents.
+///
+/// Each commit carries a bitset of the candidates it is an ancestor of, pushed from children to
+/// parents. A candidate's ancestor count gives `|target| - |candidate|`, and the first
+/// first-parent-chain commit holding its bit gives the first-parent distance.
+fn reachable_distances(nodes: &[CommitNode], candidates: &[String]) -> Vec<ReachableCommit> {
+    let position: HashMap<&str, usize> = nodes
+        .iter()
+        .enumerate()
+        .map(|(pos, node)| (node.commit.id.as_str(), pos))
+        .collect();
+    let mut wanted: Vec<usize> = Vec::new();
+    for candidate in candidates {
+        if let Some(&pos) = position.get(candidate.as_str())
+            && !wanted.contains(&pos)
+        {
+            wanted.push(pos);
+        }
+    }
+    if wanted.is_empty() {
+        return Vec::new();
+    }
+    let bit_of: HashMap<usize, usize> = wanted
+        .iter()
+        .enumerate()
+        .map(|(bit, pos)| (*pos, bit))
+        .collect();
+
+    let mut chain_index: HashMap<usize, u32> = HashMap::new();
+    let mut cursor = Some(0);
+    while let Some(pos) = cursor {
+        chain_index.insert(pos, chain_index.len() as u32);
+        cursor = nodes[pos]
+            .parents
+            .first()
+            .and_then(|parent| position.get(parent.as_str()).copied());
+    }
+
+    let words = wanted.len().div_ceil(64);
+    let mut sets: Vec<Vec<u64>> = vec![Vec::new(); nodes.len()];
+    let mut ancestor_counts = vec![0u32; wanted.len()];
+    let mut first_parent = vec![chain_index.len() as u32; wanted.len()];
+    for pos in 0..nodes.len() {
+        let mut set = std::mem::take(&mut sets[pos]);
+        set.resize(words, 0);
+        if let Some(&bit) = bit_of.get(&pos) {
+            set[bit / 64] |= 1 << (bit % 64);
+        }
+        for (word_index, word) in set.iter().enumerate() {
+            let mut bits = *word;
+            while bits != 0 {
+                let bit = word_index * 64 + bits.trailing_zeros() as usize;
+                ancestor_counts[bit] += 1;
+                if let Some(&index) = chain_index.get(&pos) {
+                    first_parent[bit] = first_parent[bit].min(index);
+                }
+                bits &= bits - 1;
+            }
+        }
+        for parent in &nodes[pos].parents {
+            if let Some(&parent_pos) = position.get(parent.as_str()) {
+                let parent_set = &mut sets[parent_pos];
+                parent_set.resize(words, 0);
+                for (into, from) in parent_set.iter_mut().zip(&set) {
+                    *into |= from;
+                }
+            }
+        }
+    }
+
+    let total = nodes.len() as u32;
+    wanted
+        .iter()
+        .enumerate()
+        .map(|(bit, pos)| ReachableCommit {
+            commit: nodes[*pos].commit.clone(),
+            distance: total - ancestor_counts[bit],
+            first_parent_distance: first_parent[bit],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bulk_distances_match_rev_list_counts() {
+        let node = |id: &str, timestamp: i64, parents: &[&str]| CommitNode {
+            commit: CommitInfo {
+                id: id.to_string(),
+                timestamp,
+            },
+            parents: parents.iter().map(|parent| parent.to_string()).collect(),
+        };
+        // root <- a <- b <- m (merge of b and s), root <- s; children first.
+        let nodes = vec![
+            node("m", 5, &["b", "s"]),
+            node("s", 4, &["root"]),
+            node("b", 3, &["a"]),
+            node("a", 2, &["root"]),
+            node("root", 1, &[]),
+        ];
+        let candidates = ["s", "a", "missing", "a", "m", "root"].map(str::to_string);
+        let found: Vec<(String, u32, u32)> = reachable_distances(&nodes, &candidates)
+            .into_iter()
+            .map(|hit| (hit.commit.id, hit.distance, hit.first_parent_distance))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("s".to_string(), 3, 3),
+                ("a".to_string(), 3, 2),
+                ("m".to_string(), 0, 0),
+                ("root".to_string(), 4, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn classifies_common_remote_failures() {
+        assert_eq!(
+            classify_remote_failure(
+                "fatal: 'nope' does not appear to be a git repository\nfatal: Could not read from remote repository."
+            ),
+            RemoteFailureKind::MissingRemote
+        );
+        assert_eq!(
+            classify_remote_failure("fatal: Authentication failed for 'https://example.com/'"),
+            RemoteFailureKind::Auth
+        );
+        assert_eq!(
+            classify_remote_failure(
+                "fatal: unable to access 'https://example.com/': Could not resolve host: example.com"
+            ),
+            RemoteFailureKind::Network
+        );
+        assert_eq!(
+            classify_remote_failure("fatal: couldn't find remote ref refs/tags/v1"),
+            RemoteFailureKind::MissingRefspec
+        );
+        assert_eq!(classify_remote_failure("boom"), RemoteFailureKind::Other);
+    }
+
+    #[test]
+    fn stderr_summary_is_single_line() {
+        assert_eq!(
+            stderr_summary("fatal: a\n\nfatal: b\n"),
+            "fatal: a | fatal: b"
+        );
+    }
+}
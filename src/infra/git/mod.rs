@@ -6,6 +6,7 @@
 //!
 //! Responsibility:
 //! - Define the capability contract for commit, ancestry, tag, and file-history lookups.
+//! - Derive the bulk queries from the pairwise primitives for backends that do not specialize them.
 //!
 //! Architectural Position:
 //! - Infrastructure abstraction boundary between core semantics and concrete Git backends.
@@ -15,14 +16,44 @@
 //! Copyright (c) 2026-2027 easynet. All rights reserved.
 
 pub mod cli;
+pub mod warm;
 
-use std::path::{Path, PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
 
 use crate::{
     core::model::{CommitInfo, TagRef},
-    error::TideResult,
+    error::{TideError, TideResult},
 };
 
+/// How far a shallow clone should be deepened in one round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepenStep {
+    /// `fetch --deepen=<n>` from the current shallow boundary.
+    Commits(u32),
+    /// `fetch --shallow-since=<timestamp>`.
+    Since(i64),
+}
+
+/// A commit with its parent ids, as needed to extend the ancestry index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitNode {
+    pub commit: CommitInfo,
+    pub parents: Vec<String>,
+}
+
+/// A candidate commit reachable from a target, with both history-mode distances to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReachableCommit {
+    pub commit: CommitInfo,
+    /// `rev-list --count <commit>..<target>`.
+    pub distance: u32,
+    /// `rev-list --first-parent --count <commit>..<target>`.
+    pub first_parent_distance: u32,
+}
+
 pub trait GitProvider {
     fn repo_root(&self) -> &Path;
     fn git_dir(&self) -> TideResult<PathBuf>;
@@ -30,15 +61,149 @@ pub trait GitProvider {
     fn resolve_commit(&self, rev: &str) -> TideResult<CommitInfo>;
     fn commit_exists(&self, rev: &str) -> TideResult<bool>;
     fn list_local_tags(&self, prefix: &str) -> TideResult<Vec<TagRef>>;
+    /// Read remote tags via `ls-remote` without writing refs or fetching objects; annotation
+    /// status comes from the peeled `^{}` lines.
     fn list_remote_tags(&self, remote: &str, prefix: &str) -> TideResult<Vec<TagRef>>;
+    /// Fetch the objects behind the named remote tags without writing refs, for tags whose
+    /// ancestry must be checked. The default fetches nothing, which leaves them unreachable.
+    fn fetch_tag_objects(&self, _remote: &str, _tags: &[String]) -> TideResult<()> {
+        Ok(())
+    }
+    /// Mirror remote tags into `refs/tidemark/remote-tags/*` and read them back.
+    fn fetch_remote_tags(&self, remote: &str, prefix: &str) -> TideResult<Vec<TagRef>>;
     fn is_ancestor(&self, ancestor: &str, descendant: &str) -> TideResult<bool>;
     fn commit_distance(&self, ancestor: &str, descendant: &str) -> TideResult<u32>;
+    /// The `candidates` that exist and are ancestors of (or equal to) `target`, deduplicated in
+    /// input order, with their distances.
+    ///
+    /// The default asks the pairwise primitives once per candidate; backends that can walk the
+    /// graph should answer from one traversal instead.
+    fn reachable_with_distances(
+        &self,
+        target: &str,
+        candidates: &[String],
+    ) -> TideResult<Vec<ReachableCommit>> {
+        let mut seen = HashSet::new();
+        let mut found = Vec::new();
+        for candidate in candidates {
+            if !seen.insert(candidate.as_str())
+                || !self.commit_exists(candidate)?
+                || !self.is_ancestor(candidate, target)?
+            {
+                continue;
+            }
+            found.push(ReachableCommit {
+                commit: self.resolve_commit(candidate)?,
+                distance: self.commit_distance(candidate, target)?,
+                first_parent_distance: self.first_parent_distance(candidate, target)?,
+            });
+        }
+        Ok(found)
+    }
     fn ancestry_path_commits(
         &self,
         ancestor: &str,
         descendant: &str,
     ) -> TideResult<Vec<CommitInfo>>;
+    /// Ancestry-path commits in `ancestor..descendant` that are not ancestors of any `known`
+    /// commit, parents before children.
+    ///
+    /// The default lists every path commit that is an ancestor as a parent rather than only the
+    /// direct parents, which gives the same reachability; it asks `is_ancestor` per pair of path
+    /// commits, so backends should override it with one graph walk.
+    fn ancestry_path_graph(
+        &self,
+        ancestor: &str,
+        descendant: &str,
+        known: &[String],
+    ) -> TideResult<Vec<CommitNode>> {
+        let path = self.ancestry_path_commits(ancestor, descendant)?;
+        let mut nodes = Vec::new();
+        'path: for commit in &path {
+            for tip in known {
+                if self.is_ancestor(commit.id.as_str(), tip.as_str())? {
+                    continue 'path;
+                }
+            }
+            let mut parents = Vec::new();
+            for other in &path {
+                if other.id != commit.id
+                    && self.is_ancestor(other.id.as_str(), commit.id.as_str())?
+                {
+                    parents.push(other.id.clone());
+                }
+            }
+            nodes.push(CommitNode {
+                commit: commit.clone(),
+                parents,
+            });
+        }
+        // A commit has strictly more path ancestors than any of its ancestors.
+        nodes.sort_by_key(|node| node.parents.len());
+        Ok(nodes)
+    }
+    fn first_parent_distance(&self, ancestor: &str, descendant: &str) -> TideResult<u32>;
+    /// Mainline commits in `ancestor..descendant`, oldest first.
+    fn first_parent_commits(&self, ancestor: &str, descendant: &str)
+    -> TideResult<Vec<CommitInfo>>;
     fn last_modifying_commit(&self, path: &Path, follow_renames: bool) -> TideResult<CommitInfo>;
+    /// `last_modifying_commit` for many repository-relative paths; paths without history are
+    /// absent. The default asks `last_modifying_commit` once per path.
+    fn last_modifying_commits(
+        &self,
+        paths: &[PathBuf],
+        follow_renames: bool,
+    ) -> TideResult<HashMap<PathBuf, CommitInfo>> {
+        let mut found = HashMap::new();
+        for path in paths {
+            match self.last_modifying_commit(path, follow_renames) {
+                Ok(commit) => {
+                    found.insert(path.clone(), commit);
+                }
+                Err(TideError::FileHistoryNotFound { .. }) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(found)
+    }
+    /// Index entries matching git pathspecs (magic such as `:(glob)` allowed), repository-relative.
+    ///
+    /// Required: pathspec semantics are git's own, so backends answer from their index rather
+    /// than approximating the matcher.
+    fn tracked_files(&self, pathspecs: &[String]) -> TideResult<Vec<PathBuf>>;
     fn current_branch(&self) -> TideResult<Option<String>>;
-    fn root_commit(&self) -> TideResult<CommitInfo>;
+    /// Whether tracked files differ from HEAD (untracked files are ignored).
+    fn is_dirty(&self) -> TideResult<bool>;
+    /// Every parentless commit reachable from `rev`, in no guaranteed order.
+    fn root_commits(&self, rev: &str) -> TideResult<Vec<CommitInfo>>;
+    /// The parentless commit at the end of `rev`'s first-parent chain.
+    fn first_parent_root(&self, rev: &str) -> TideResult<CommitInfo>;
+    /// Commits listed in the shallow file; empty for complete repositories.
+    fn shallow_boundaries(&self) -> TideResult<Vec<String>>;
+    fn deepen(&self, remote: &str, step: &DeepenStep) -> TideResult<()>;
+    /// The subset of `commits` reachable from any ref or `HEAD`, in input order.
+    ///
+    /// The default only knows `HEAD` and local tags, so commits kept alive by other branches
+    /// count as unreachable; backends that can list every ref should override it.
+    fn reachable_from_refs(&self, commits: &[String]) -> TideResult<Vec<String>> {
+        let mut tips = vec![self.head_commit()?.id];
+        tips.extend(
+            self.list_local_tags("")?
+                .into_iter()
+                .map(|tag| tag.commit_id),
+        );
+        let mut reachable = Vec::new();
+        'commits: for commit in commits {
+            if !self.commit_exists(commit)? {
+                continue;
+            }
+            for tip in &tips {
+                if self.is_ancestor(commit, tip)? {
+                    reachable.push(commit.clone());
+                    continue 'commits;
+                }
+            }
+        }
+        Ok(reachable)
+    }
 }
@@ -0,0 +1,294 @@
+//! TideMark
+//! ========
+//!
+//! File: src/infra/git/warm.rs
+//! Description: Memoizing `GitProvider` wrapper for long-lived resolvers.
+//!
+//! Responsibility:
+//! - Answer repeated ref and history queries from memory until the owner reports that refs moved.
+//!
+//! Architectural Position:
+//! - Infrastructure adapter layered over any provider; used by `tide serve` to keep tag listings and history walks warm.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    hash::Hash,
+    path::{Path, PathBuf},
+};
+
+use super::{CommitNode, DeepenStep, GitProvider, ReachableCommit};
+use crate::{
+    core::model::{CommitInfo, TagRef},
+    error::TideResult,
+};
+
+type Pair = (String, String);
+
+/// Remote listings, working-tree and index state, and ancestry-graph extensions always reach the inner
+/// provider; everything else is memoized until [`WarmGit::invalidate`].
+pub struct WarmGit<G> {
+    inner: G,
+    git_dir: RefCell<Option<PathBuf>>,
+    memo: RefCell<Memo>,
+}
+
+#[derive(Default)]
+struct Memo {
+    head: Option<CommitInfo>,
+    branch: Option<Option<String>>,
+    shallow: Option<Vec<String>>,
+    commits: HashMap<String, CommitInfo>,
+    exists: HashMap<String, bool>,
+    local_tags: HashMap<String, Vec<TagRef>>,
+    ancestor: HashMap<Pair, bool>,
+    distance: HashMap<Pair, u32>,
+    first_parent_distance: HashMap<Pair, u32>,
+    reachable: HashMap<(String, Vec<String>), Vec<ReachableCommit>>,
+    ancestry_path: HashMap<Pair, Vec<CommitInfo>>,
+    first_parent_path: HashMap<Pair, Vec<CommitInfo>>,
+    last_modifying: HashMap<(PathBuf, bool), CommitInfo>,
+    last_modifying_many: HashMap<(Vec<PathBuf>, bool), HashMap<PathBuf, CommitInfo>>,
+    roots: HashMap<String, Vec<CommitInfo>>,
+    first_parent_root: HashMap<String, CommitInfo>,
+}
+
+impl<G: GitProvider> WarmGit<G> {
+    pub fn new(inner: G) -> Self {
+        Self {
+            inner,
+            git_dir: RefCell::new(None),
+            memo: RefCell::default(),
+        }
+    }
+
+    /// Forget every memoized answer; call whenever a ref, `HEAD`, or the shallow file changes.
+    pub fn invalidate(&self) {
+        *self.memo.borrow_mut() = Memo::default();
+    }
+
+    fn cached<K: Eq + Hash, V: Clone>(
+        &self,
+        table: impl Fn(&mut Memo) -> &mut HashMap<K, V>,
+        key: K,
+        load: impl FnOnce() -> TideResult<V>,
+    ) -> TideResult<V> {
+        if let Some(value) = table(&mut self.memo.borrow_mut()).get(&key) {
+            return Ok(value.clone());
+        }
+        let value = load()?;
+        table(&mut self.memo.borrow_mut()).insert(key, value.clone());
+        Ok(value)
+    }
+
+    fn cached_once<V: Clone>(
+        &self,
+        slot: impl Fn(&mut Memo) -> &mut Option<V>,
+        load: impl FnOnce() -> TideResult<V>,
+    ) -> TideResult<V> {
+        if let Some(value) = slot(&mut self.memo.borrow_mut()).as_ref() {
+            return Ok(value.clone());
+        }
+        let value = load()?;
+        *slot(&mut self.memo.borrow_mut()) = Some(value.clone());
+        Ok(value)
+    }
+}
+
+fn pair(a: &str, b: &str) -> Pair {
+    (a.to_string(), b.to_string())
+}
+
+impl<G: GitProvider> GitProvider for WarmGit<G> {
+    fn repo_root(&self) -> &Path {
+        self.inner.repo_root()
+    }
+
+    fn git_dir(&self) -> TideResult<PathBuf> {
+        if let Some(dir) = self.git_dir.borrow().as_ref() {
+            return Ok(dir.clone());
+        }
+        let dir = self.inner.git_dir()?;
+        *self.git_dir.borrow_mut() = Some(dir.clone());
+        Ok(dir)
+    }
+
+    fn head_commit(&self) -> TideResult<CommitInfo> {
+        self.cached_once(|memo| &mut memo.head, || self.inner.head_commit())
+    }
+
+    fn resolve_commit(&self, rev: &str) -> TideResult<CommitInfo> {
+        self.cached(
+            |memo| &mut memo.commits,
+            rev.to_string(),
+            || self.inner.resolve_commit(rev),
+        )
+    }
+
+    fn commit_exists(&self, rev: &str) -> TideResult<bool> {
+        self.cached(
+            |memo| &mut memo.exists,
+            rev.to_string(),
+            || self.inner.commit_exists(rev),
+        )
+    }
+
+    fn list_local_tags(&self, prefix: &str) -> TideResult<Vec<TagRef>> {
+        self.cached(
+            |memo| &mut memo.local_tags,
+            prefix.to_string(),
+            || self.inner.list_local_tags(prefix),
+        )
+    }
+
+    fn list_remote_tags(&self, remote: &str, prefix: &str) -> TideResult<Vec<TagRef>> {
+        self.inner.list_remote_tags(remote, prefix)
+    }
+
+    fn fetch_tag_objects(&self, remote: &str, tags: &[String]) -> TideResult<()> {
+        let fetched = self.inner.fetch_tag_objects(remote, tags);
+        self.invalidate();
+        fetched
+    }
+
+    fn fetch_remote_tags(&self, remote: &str, prefix: &str) -> TideResult<Vec<TagRef>> {
+        let tags = self.inner.fetch_remote_tags(remote, prefix);
+        self.invalidate();
+        tags
+    }
+
+    fn is_ancestor(&self, ancestor: &str, descendant: &str) -> TideResult<bool> {
+        self.cached(
+            |memo| &mut memo.ancestor,
+            pair(ancestor, descendant),
+            || self.inner.is_ancestor(ancestor, descendant),
+        )
+    }
+
+    fn commit_distance(&self, ancestor: &str, descendant: &str) -> TideResult<u32> {
+        self.cached(
+            |memo| &mut memo.distance,
+            pair(ancestor, descendant),
+            || self.inner.commit_distance(ancestor, descendant),
+        )
+    }
+
+    fn reachable_with_distances(
+        &self,
+        target: &str,
+        candidates: &[String],
+    ) -> TideResult<Vec<ReachableCommit>> {
+        self.cached(
+            |memo| &mut memo.reachable,
+            (target.to_string(), candidates.to_vec()),
+            || self.inner.reachable_with_distances(target, candidates),
+        )
+    }
+
+    fn ancestry_path_commits(
+        &self,
+        ancestor: &str,
+        descendant: &str,
+    ) -> TideResult<Vec<CommitInfo>> {
+        self.cached(
+            |memo| &mut memo.ancestry_path,
+            pair(ancestor, descendant),
+            || self.inner.ancestry_path_commits(ancestor, descendant),
+        )
+    }
+
+    fn ancestry_path_graph(
+        &self,
+        ancestor: &str,
+        descendant: &str,
+        known: &[String],
+    ) -> TideResult<Vec<CommitNode>> {
+        self.inner.ancestry_path_graph(ancestor, descendant, known)
+    }
+
+    fn first_parent_distance(&self, ancestor: &str, descendant: &str) -> TideResult<u32> {
+        self.cached(
+            |memo| &mut memo.first_parent_distance,
+            pair(ancestor, descendant),
+            || self.inner.first_parent_distance(ancestor, descendant),
+        )
+    }
+
+    fn first_parent_commits(
+        &self,
+        ancestor: &str,
+        descendant: &str,
+    ) -> TideResult<Vec<CommitInfo>> {
+        self.cached(
+            |memo| &mut memo.first_parent_path,
+            pair(ancestor, descendant),
+            || self.inner.first_parent_commits(ancestor, descendant),
+        )
+    }
+
+    fn last_modifying_commit(&self, path: &Path, follow_renames: bool) -> TideResult<CommitInfo> {
+        self.cached(
+            |memo| &mut memo.last_modifying,
+            (path.to_path_buf(), follow_renames),
+            || self.inner.last_modifying_commit(path, follow_renames),
+        )
+    }
+
+    fn last_modifying_commits(
+        &self,
+        paths: &[PathBuf],
+        follow_renames: bool,
+    ) -> TideResult<HashMap<PathBuf, CommitInfo>> {
+        self.cached(
+            |memo| &mut memo.last_modifying_many,
+            (paths.to_vec(), follow_renames),
+            || self.inner.last_modifying_commits(paths, follow_renames),
+        )
+    }
+
+    fn tracked_files(&self, pathspecs: &[String]) -> TideResult<Vec<PathBuf>> {
+        self.inner.tracked_files(pathspecs)
+    }
+
+    fn current_branch(&self) -> TideResult<Option<String>> {
+        self.cached_once(|memo| &mut memo.branch, || self.inner.current_branch())
+    }
+
+    fn is_dirty(&self) -> TideResult<bool> {
+        self.inner.is_dirty()
+    }
+
+    fn root_commits(&self, rev: &str) -> TideResult<Vec<CommitInfo>> {
+        self.cached(
+            |memo| &mut memo.roots,
+            rev.to_string(),
+            || self.inner.root_commits(rev),
+        )
+    }
+
+    fn first_parent_root(&self, rev: &str) -> TideResult<CommitInfo> {
+        self.cached(
+            |memo| &mut memo.first_parent_root,
+            rev.to_string(),
+            || self.inner.first_parent_root(rev),
+        )
+    }
+
+    fn shallow_boundaries(&self) -> TideResult<Vec<String>> {
+        self.cached_once(|memo| &mut memo.shallow, || self.inner.shallow_boundaries())
+    }
+
+    fn deepen(&self, remote: &str, step: &DeepenStep) -> TideResult<()> {
+        let deepened = self.inner.deepen(remote, step);
+        self.invalidate();
+        deepened
+    }
+
+    fn reachable_from_refs(&self, commits: &[String]) -> TideResult<Vec<String>> {
+        self.inner.reachable_from_refs(commits)
+    }
+}
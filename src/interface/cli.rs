@@ -5,7 +5,7 @@
 //! Description: Command-line schema definitions using Clap.
 //!
 //! Responsibility:
-//! - Declare stable command and flag contracts for mark, file, release, config, and service operations.
+//! - Declare stable command and flag contracts for mark, file, stamp, generate, release, config, cache, and service operations.
 //!
 //! Architectural Position:
 //! - Interface input boundary consumed by binary entrypoints.
@@ -16,13 +16,19 @@
 
 use std::path::PathBuf;
 
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 
 #[derive(Debug, Parser)]
 #[command(name = "tide")]
 #[command(version)]
 #[command(about = "Git-native deterministic version coordinates")]
 pub struct Cli {
+    /// Override a config value for this invocation (repeatable), e.g. -c remote.strategy=local-only
+    #[arg(short = 'c', value_name = "KEY=VALUE", global = true)]
+    pub overrides: Vec<String>,
+    /// Resolve in this process even when a `tide serve` daemon is running for the repository
+    #[arg(long, global = true)]
+    pub no_daemon: bool,
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -31,14 +37,26 @@ pub struct Cli {
 pub enum Commands {
     /// Resolve version coordinate for HEAD
     Mark(MarkArgs),
-    /// Resolve version coordinate for the last commit that modified <path>
+    /// Resolve version coordinates for the last commits that modified files, directories, or globs
     File(FileArgs),
+    /// Write the resolved coordinate into project manifests
+    Stamp(StampArgs),
+    /// Generate a source file with version constants
+    Generate(GenerateArgs),
+    /// List the commits that resolve to a version coordinate
+    Locate(LocateArgs),
     /// Release-anchor queries
     Release(ReleaseCommand),
     /// Configuration commands
     Config(ConfigCommand),
+    /// Inspect and maintain .git/tidemark-cache
+    Cache(CacheCommand),
     /// Systemd user service management
     Service(ServiceCommand),
+    /// Answer mark/file/releases/locate queries over a Unix socket, keeping state warm
+    Serve(ServeArgs),
+    /// Language server on stdio: hover and code lens show each open file's coordinate
+    Lsp,
 }
 
 #[derive(Debug, clap::Args)]
@@ -49,6 +67,9 @@ pub struct MarkArgs {
     /// Disable remote tag query and use only local tags
     #[arg(long)]
     pub local_only: bool,
+    /// Fail instead of falling back to local tags when the remote refresh fails
+    #[arg(long, conflicts_with = "local_only")]
+    pub require_remote: bool,
     /// Optional metadata suffix appended as x.y.z.<tag>
     #[arg(long = "tag")]
     pub metadata_suffix: Option<String>,
@@ -56,15 +77,98 @@ pub struct MarkArgs {
 
 #[derive(Debug, clap::Args)]
 pub struct FileArgs {
-    pub path: PathBuf,
+    /// Files, directories (recursed), or quoted glob patterns, relative to the repository root
+    #[arg(value_name = "PATH", required_unless_present = "stdin")]
+    pub paths: Vec<String>,
+    /// Also read paths and patterns from stdin, one per line
+    #[arg(long)]
+    pub stdin: bool,
+    /// Print a JSON array instead of tab-separated lines
+    #[arg(long)]
+    pub json: bool,
+    /// Disable remote tag query and use only local tags
+    #[arg(long)]
+    pub local_only: bool,
+    /// Fail instead of falling back to local tags when the remote refresh fails
+    #[arg(long, conflicts_with = "local_only")]
+    pub require_remote: bool,
+    /// Optional metadata suffix appended as x.y.z.<tag>
+    #[arg(long = "tag")]
+    pub metadata_suffix: Option<String>,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct LocateArgs {
+    /// Coordinate to look up (x.y.z; channel and metadata suffixes are ignored)
+    pub coordinate: String,
+    /// Search commits reachable from this revision instead of HEAD
+    #[arg(long)]
+    pub from: Option<String>,
+    /// Disable remote tag query and use only local tags
+    #[arg(long)]
+    pub local_only: bool,
+    /// Fail instead of falling back to local tags when the remote refresh fails
+    #[arg(long, conflicts_with = "local_only")]
+    pub require_remote: bool,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct ServeArgs {
+    /// Unix socket to listen on; advertised to the CLI through .git/tidemark-daemon
+    #[arg(long)]
+    pub socket: PathBuf,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct StampArgs {
+    /// Comma-separated manifests: Cargo.toml, package.json, pyproject.toml, debian/changelog
+    #[arg(long, value_delimiter = ',', required = true)]
+    pub into: Vec<PathBuf>,
+    /// Do not write; fail with exit code 1 if any manifest is out of date
+    #[arg(long)]
+    pub check: bool,
+    /// Disable remote tag query and use only local tags
+    #[arg(long)]
+    pub local_only: bool,
+    /// Fail instead of falling back to local tags when the remote refresh fails
+    #[arg(long, conflicts_with = "local_only")]
+    pub require_remote: bool,
+    /// Optional metadata suffix appended as x.y.z.<tag>
+    #[arg(long = "tag")]
+    pub metadata_suffix: Option<String>,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct GenerateArgs {
+    /// Target language
+    #[arg(long, value_enum)]
+    pub lang: GenerateLang,
+    /// Output file; rewritten only when its content changes. Prints to stdout when omitted
+    #[arg(long)]
+    pub out: Option<PathBuf>,
+    /// Package name for generated Go sources
+    #[arg(long, default_value = "version")]
+    pub package: String,
     /// Disable remote tag query and use only local tags
     #[arg(long)]
     pub local_only: bool,
+    /// Fail instead of falling back to local tags when the remote refresh fails
+    #[arg(long, conflicts_with = "local_only")]
+    pub require_remote: bool,
     /// Optional metadata suffix appended as x.y.z.<tag>
     #[arg(long = "tag")]
     pub metadata_suffix: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum GenerateLang {
+    Rust,
+    C,
+    Go,
+    Python,
+    Ts,
+}
+
 #[derive(Debug, clap::Args)]
 pub struct ReleaseCommand {
     #[command(subcommand)]
@@ -79,9 +183,15 @@ pub enum ReleaseSubcommand {
 
 #[derive(Debug, clap::Args)]
 pub struct ReleaseListArgs {
+    /// Print a JSON array, including local/remote tag conflicts, instead of tab-separated lines
+    #[arg(long)]
+    pub json: bool,
     /// Disable remote tag query and use only local tags
     #[arg(long)]
     pub local_only: bool,
+    /// Fail instead of falling back to local tags when the remote refresh fails
+    #[arg(long, conflicts_with = "local_only")]
+    pub require_remote: bool,
 }
 
 #[derive(Debug, clap::Args)]
@@ -94,6 +204,65 @@ pub struct ConfigCommand {
 pub enum ConfigSubcommand {
     /// Create .tidemark.toml if absent
     Init,
+    /// Print effective config values after layering
+    Show(ConfigShowArgs),
+    /// Report every config problem at once; exits 2 when any error is found
+    Validate(ConfigValidateArgs),
+    /// Rewrite the global config and .tidemark.toml to the current schema, preserving comments
+    Migrate,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct ConfigShowArgs {
+    /// Also print the layer each value came from
+    #[arg(long)]
+    pub origin: bool,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct ConfigValidateArgs {
+    /// Print the report as JSON
+    #[arg(long)]
+    pub json: bool,
+    /// Skip remote reachability checks
+    #[arg(long)]
+    pub local_only: bool,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct CacheCommand {
+    #[command(subcommand)]
+    pub command: CacheSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CacheSubcommand {
+    /// Print entry counts and sizes per namespace
+    Stats,
+    /// Remove entries by age and/or reachability
+    Prune(CachePruneArgs),
+    /// Remove all entries, or one namespace
+    Clear(CacheClearArgs),
+    /// Check that every entry deserializes; exits 1 when any does not
+    Verify,
+}
+
+#[derive(Debug, clap::Args)]
+#[group(required = true, multiple = true)]
+pub struct CachePruneArgs {
+    /// Remove entries written more than this many days ago
+    #[arg(long)]
+    pub older_than_days: Option<u32>,
+    /// Remove entries whose commit is not reachable from any ref
+    #[arg(long)]
+    pub unreachable: bool,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct CacheClearArgs {
+    /// Only clear this namespace (mark or file)
+    #[arg(long)]
+    pub namespace: Option<String>,
 }
 
 #[derive(Debug, clap::Args)]
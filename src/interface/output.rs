@@ -14,7 +14,19 @@
 //! Email: silan.hu@u.nus.edu
 //! Copyright (c) 2026-2027 easynet. All rights reserved.
 
-use crate::core::model::{FileResult, MarkResult, ReleaseTag};
+use std::collections::BTreeMap;
+
+use crate::{
+    config::{layer::ConfigEntry, migrate::MigrationOutcome, validate::ValidationReport},
+    core::model::{
+        CacheStatus, FileResult, IndexStatus, LocateResult, MarkResult, ReleaseInventory,
+        TagConflict,
+    },
+    ops::{
+        cache::{CacheProblem, CacheTally},
+        stamp::StampOutcome,
+    },
+};
 
 pub fn format_mark(mark: &MarkResult, explain: bool) -> String {
     if !explain {
@@ -27,41 +39,182 @@ pub fn format_mark(mark: &MarkResult, explain: bool) -> String {
         .clone()
         .unwrap_or_else(|| "detached".to_string());
 
-    [
+    let mut lines = vec![
         format!("version={}", mark.explain.version),
         format!("anchor_tag={}", mark.explain.anchor_tag),
+        format!("anchor_kind={}", mark.explain.anchor_kind),
         format!("anchor_commit={}", mark.explain.anchor_commit.id),
         format!("anchor_timestamp={}", mark.explain.anchor_commit.timestamp),
         format!("target_commit={}", mark.explain.target_commit.id),
         format!("target_timestamp={}", mark.explain.target_commit.timestamp),
         format!("day_delta={}", mark.explain.day_delta),
         format!("commit_index={}", mark.explain.commit_index),
+        format!("history={}", mark.explain.history),
         format!("timezone={}", mark.explain.timezone),
         format!("branch={branch}"),
+        format!(
+            "channel={}",
+            mark.explain.version.channel.as_deref().unwrap_or("none")
+        ),
         format!("remote_status={}", mark.explain.remote_status),
-    ]
-    .join("\n")
-        + "\n"
+        format!("shallow={}", mark.explain.shallow),
+        format!("deepen_rounds={}", mark.explain.deepen_rounds),
+        format!("cache={}", mark.explain.cache),
+    ];
+    if let CacheStatus::Rewritten(kind) = mark.explain.cache {
+        lines.push(format!("cache_invalid={kind}"));
+    }
+    lines.push(format!("ancestry_index={}", mark.explain.ancestry_index));
+    if let IndexStatus::Extended { new_commits } = mark.explain.ancestry_index {
+        lines.push(format!("ancestry_index_walked={new_commits}"));
+    }
+    if let Some(failure) = &mark.explain.remote_failure {
+        lines.push(format!("remote_failure={}", failure.kind));
+        lines.push(format!("remote_stderr={}", failure.stderr));
+    }
+    for remote in &mark.explain.remotes {
+        lines.push(format!("remote.{}.status={}", remote.name, remote.status));
+        if let Some(failure) = &remote.failure {
+            lines.push(format!("remote.{}.failure={}", remote.name, failure.kind));
+        }
+    }
+    for conflict in &mark.explain.tag_conflicts {
+        lines.push(format!("tag_conflict={conflict}"));
+    }
+    lines.join("\n") + "\n"
+}
+
+pub fn format_tag_conflict_warning(conflict: &TagConflict) -> String {
+    format!("warning: release tag conflict {conflict}\n")
 }
 
 pub fn format_file(file: &FileResult) -> String {
     format!("{}\n", file.mark.coordinate)
 }
 
-pub fn format_release_list(releases: &[ReleaseTag]) -> String {
-    let mut lines = Vec::with_capacity(releases.len());
-    for release in releases {
+/// `path<TAB>coordinate<TAB>commit` per file, or a JSON array of the same fields.
+pub fn format_files(files: &[FileResult], json: bool) -> String {
+    if json {
+        let body: Vec<_> = files
+            .iter()
+            .map(|file| {
+                serde_json::json!({
+                    "path": file.path,
+                    "coordinate": file.mark.coordinate.to_string(),
+                    "commit": file.last_commit.id,
+                    "anchor_tag": file.mark.explain.anchor_tag,
+                })
+            })
+            .collect();
+        return format!("{}\n", serde_json::Value::Array(body));
+    }
+    files
+        .iter()
+        .map(|file| {
+            format!(
+                "{}\t{}\t{}\n",
+                file.path, file.mark.coordinate, file.last_commit.id
+            )
+        })
+        .collect()
+}
+
+/// Markdown hover for an editor: coordinate, then the commit and anchor tag behind it.
+pub fn format_file_hover(file: &FileResult) -> String {
+    format!(
+        "**{}**\n\nlast modified in `{}` · anchor `{}`",
+        file.mark.coordinate,
+        short_id(file.last_commit.id.as_str()),
+        file.mark.explain.anchor_tag
+    )
+}
+
+/// Single-line code lens title: `<coordinate> · <anchor tag> · <short commit>`.
+pub fn format_file_lens(file: &FileResult) -> String {
+    format!(
+        "{} · {} · {}",
+        file.mark.coordinate,
+        file.mark.explain.anchor_tag,
+        short_id(file.last_commit.id.as_str())
+    )
+}
+
+fn short_id(id: &str) -> &str {
+    &id[..id.len().min(7)]
+}
+
+/// One matching commit id per line, oldest first.
+pub fn format_locate(located: &LocateResult) -> String {
+    located
+        .commits
+        .iter()
+        .map(|commit| format!("{}\n", commit.id))
+        .collect()
+}
+
+pub fn format_stamp(outcomes: &[StampOutcome]) -> String {
+    outcomes
+        .iter()
+        .map(|outcome| {
+            format!(
+                "{}\t{}\t{}\t{}\n",
+                outcome.path.display(),
+                outcome.kind,
+                outcome.version,
+                outcome.status
+            )
+        })
+        .collect()
+}
+
+/// Tab-separated `tag, anchor value, commit, annotated|lightweight, source` rows, or with `json`
+/// an array that also carries each tag's local/remote conflict.
+pub fn format_release_list(inventory: &ReleaseInventory, json: bool) -> String {
+    let kind = |annotated: bool| {
+        if annotated {
+            "annotated"
+        } else {
+            "lightweight"
+        }
+    };
+    if json {
+        let body: Vec<_> = inventory
+            .releases
+            .iter()
+            .map(|release| {
+                let conflict = inventory
+                    .conflicts
+                    .iter()
+                    .find(|conflict| conflict.name == release.tag.name)
+                    .map(|conflict| {
+                        serde_json::json!({
+                            "local_commit": conflict.local_commit,
+                            "remote_commit": conflict.remote_commit,
+                            "kept": conflict.kept.to_string(),
+                        })
+                    });
+                serde_json::json!({
+                    "tag": release.tag.name,
+                    "anchor_value": release.anchor_value,
+                    "commit": release.tag.commit_id,
+                    "kind": kind(release.tag.is_annotated),
+                    "source": release.tag.source.to_string(),
+                    "conflict": conflict,
+                })
+            })
+            .collect();
+        return format!("{}\n", serde_json::Value::Array(body));
+    }
+
+    let mut lines = Vec::with_capacity(inventory.releases.len());
+    for release in &inventory.releases {
         lines.push(format!(
             "{}\t{}\t{}\t{}\t{}",
             release.tag.name,
             release.anchor_value,
             release.tag.commit_id,
-            if release.tag.is_annotated {
-                "annotated"
-            } else {
-                "lightweight"
-            },
-            release.tag.source
+            kind(release.tag.is_annotated),
+            release.tag.source,
         ));
     }
 
@@ -71,3 +224,93 @@ pub fn format_release_list(releases: &[ReleaseTag]) -> String {
         lines.join("\n") + "\n"
     }
 }
+
+pub fn format_config(entries: &[ConfigEntry], with_origin: bool) -> String {
+    entries
+        .iter()
+        .map(|entry| {
+            if with_origin {
+                format!("{}\t{}\t{}\n", entry.key, entry.value, entry.origin)
+            } else {
+                format!("{}\t{}\n", entry.key, entry.value)
+            }
+        })
+        .collect()
+}
+
+pub fn format_migration(outcome: &MigrationOutcome) -> String {
+    format!(
+        "{}\t{}\t{}\t{}\n",
+        outcome.path.display(),
+        outcome.from,
+        outcome.to,
+        if outcome.changed {
+            "updated"
+        } else {
+            "unchanged"
+        }
+    )
+}
+
+/// `source:line:column: severity[kind]: key: message` per issue, or a JSON document.
+pub fn format_validation(report: &ValidationReport, json: bool) -> String {
+    if json {
+        let body = serde_json::json!({
+            "valid": report.is_valid(),
+            "errors": report.error_count(),
+            "issues": report.issues,
+        });
+        return format!("{body}\n");
+    }
+    if report.issues.is_empty() {
+        return "ok\n".to_string();
+    }
+    report
+        .issues
+        .iter()
+        .map(|issue| {
+            let mut location = issue.source.clone();
+            if let (Some(line), Some(column)) = (issue.line, issue.column) {
+                location.push_str(&format!(":{line}:{column}"));
+            }
+            let key = issue
+                .key
+                .as_deref()
+                .map(|key| format!("{key}: "))
+                .unwrap_or_default();
+            format!(
+                "{location}: {}[{}]: {key}{}\n",
+                issue.severity, issue.kind, issue.message
+            )
+        })
+        .collect()
+}
+
+pub fn format_cache_stats(stats: &BTreeMap<String, CacheTally>) -> String {
+    let mut total = CacheTally::default();
+    let mut out = String::new();
+    for (namespace, tally) in stats {
+        out.push_str(&format!(
+            "{namespace}\t{}\t{}\n",
+            tally.entries, tally.bytes
+        ));
+        total.entries += tally.entries;
+        total.bytes += tally.bytes;
+    }
+    out.push_str(&format!("total\t{}\t{}\n", total.entries, total.bytes));
+    out
+}
+
+pub fn format_cache_removed(removed: &CacheTally) -> String {
+    format!("removed\t{}\t{}\n", removed.entries, removed.bytes)
+}
+
+pub fn format_cache_problems(problems: &[CacheProblem]) -> String {
+    if problems.is_empty() {
+        return "ok\n".to_string();
+    }
+    problems
+        .iter()
+        .map(|problem| format!("{}\t{}\n", problem.path.display(), problem.message))
+        .collect()
+}
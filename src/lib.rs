@@ -5,7 +5,7 @@
 //! Description: Crate root module declarations for TideMark layered architecture.
 //!
 //! Responsibility:
-//! - Expose application, core, infrastructure, interface, operations, and cross-cutting modules.
+//! - Expose the stable `Resolver` facade plus application, core, infrastructure, interface, operations, and cross-cutting modules.
 //!
 //! Architectural Position:
 //! - Top-level library assembly boundary for binaries and tests.
@@ -14,10 +14,28 @@
 //! Email: silan.hu@u.nus.edu
 //! Copyright (c) 2026-2027 easynet. All rights reserved.
 
+//! Embedding entry point: [`Resolver`]. The re-exports below are the semver-stable surface;
+//! `app`, `core`, `infra`, and `interface` are internal wiring shared with the binaries.
+
+pub mod api;
+#[doc(hidden)]
 pub mod app;
 pub mod config;
+#[doc(hidden)]
 pub mod core;
 pub mod error;
+#[doc(hidden)]
 pub mod infra;
+#[doc(hidden)]
 pub mod interface;
 pub mod ops;
+
+pub use api::{Resolver, ResolverBuilder};
+pub use config::TideConfig;
+pub use core::model::{
+    AnchorKind, CommitInfo, FileResult, LocateResult, MarkExplain, MarkResult, ReleaseInventory,
+    ReleaseTag, RemoteFailure, RemoteFailureKind, RemoteLoadStatus, RemoteOutcome, TagConflict,
+    TagRef, TagSource, VersionCoordinate,
+};
+pub use error::{TideError, TideResult};
+pub use infra::git::{CommitNode, DeepenStep, GitProvider, ReachableCommit, cli::GitCli};
@@ -0,0 +1,168 @@
+//! TideMark
+//! ========
+//!
+//! File: src/ops/cache.rs
+//! Description: Cache maintenance operations behind `tide cache`.
+//!
+//! Responsibility:
+//! - Report, prune, clear, and verify `.git/tidemark-cache` entries.
+//!
+//! Architectural Position:
+//! - Operations layer over the cache store and git provider; knows which result type each namespace holds.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use serde::de::DeserializeOwned;
+
+use crate::{
+    core::{
+        ancestry::{ANCESTRY_CACHE_NAMESPACE, AncestryIndex},
+        model::{FileResult, MarkResult},
+        resolver::{file::FILE_CACHE_NAMESPACE, mark::MARK_CACHE_NAMESPACE},
+    },
+    error::TideResult,
+    infra::{
+        cache::{CacheEntry, CacheStore},
+        git::GitProvider,
+    },
+};
+
+/// Entry count and on-disk size for a namespace or for a set of removed entries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheTally {
+    pub entries: usize,
+    pub bytes: u64,
+}
+
+impl CacheTally {
+    fn add(&mut self, entry: &CacheEntry) {
+        self.entries += 1;
+        self.bytes += entry.bytes;
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PruneRequest {
+    /// Remove entries written longer ago than this.
+    pub older_than: Option<Duration>,
+    /// Remove entries whose target commit is no longer reachable from any ref.
+    pub unreachable: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct CacheProblem {
+    pub path: PathBuf,
+    pub message: String,
+}
+
+/// Per-namespace tallies, sorted by namespace.
+pub fn stats(store: &CacheStore) -> TideResult<BTreeMap<String, CacheTally>> {
+    let mut out: BTreeMap<String, CacheTally> = BTreeMap::new();
+    for entry in store.entries()? {
+        out.entry(entry.namespace.clone()).or_default().add(&entry);
+    }
+    Ok(out)
+}
+
+pub fn prune(
+    store: &CacheStore,
+    git: &dyn GitProvider,
+    req: &PruneRequest,
+) -> TideResult<CacheTally> {
+    let now = SystemTime::now();
+    let entries = store.entries()?;
+
+    let targets: Vec<Option<String>> = entries
+        .iter()
+        .map(|entry| {
+            if req.unreachable {
+                target_commit(store, entry)
+            } else {
+                None
+            }
+        })
+        .collect();
+    let candidates: Vec<String> = targets
+        .iter()
+        .flatten()
+        .cloned()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let reachable: HashSet<String> = git.reachable_from_refs(&candidates)?.into_iter().collect();
+
+    let mut removed = CacheTally::default();
+    for (entry, target) in entries.iter().zip(&targets) {
+        let expired = req.older_than.is_some_and(|max| entry.age(now) > max);
+        let orphaned = target
+            .as_ref()
+            .is_some_and(|commit| !reachable.contains(commit));
+        if expired || orphaned {
+            store.remove(entry)?;
+            removed.add(entry);
+        }
+    }
+    Ok(removed)
+}
+
+/// Remove every entry, or only those in `namespace`.
+pub fn clear(store: &CacheStore, namespace: Option<&str>) -> TideResult<CacheTally> {
+    let mut removed = CacheTally::default();
+    for entry in store.entries()? {
+        if namespace.is_some_and(|namespace| namespace != entry.namespace) {
+            continue;
+        }
+        store.remove(&entry)?;
+        removed.add(&entry);
+    }
+    Ok(removed)
+}
+
+/// Entries that are damaged, written by another format or tool version, or not of their namespace's result type.
+pub fn verify(store: &CacheStore) -> TideResult<Vec<CacheProblem>> {
+    let mut problems = Vec::new();
+    for entry in store.entries()? {
+        let raw = store.read_raw(&entry)?;
+        let outcome = match entry.namespace.as_str() {
+            MARK_CACHE_NAMESPACE => check::<MarkResult>(&raw),
+            FILE_CACHE_NAMESPACE => check::<FileResult>(&raw),
+            ANCESTRY_CACHE_NAMESPACE => check::<AncestryIndex>(&raw),
+            other => Err(format!("unknown cache namespace `{other}`")),
+        };
+        if let Err(message) = outcome {
+            problems.push(CacheProblem {
+                path: entry.path,
+                message,
+            });
+        }
+    }
+    Ok(problems)
+}
+
+fn check<T: DeserializeOwned>(raw: &[u8]) -> Result<(), String> {
+    CacheStore::decode::<T>(raw)
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+/// Commit an entry was resolved for; ancestry indexes and unreadable entries yield `None`.
+fn target_commit(store: &CacheStore, entry: &CacheEntry) -> Option<String> {
+    let raw = store.read_raw(entry).ok()?;
+    match entry.namespace.as_str() {
+        MARK_CACHE_NAMESPACE => CacheStore::decode::<MarkResult>(&raw)
+            .ok()
+            .map(|mark| mark.explain.target_commit.id),
+        FILE_CACHE_NAMESPACE => CacheStore::decode::<FileResult>(&raw)
+            .ok()
+            .map(|file| file.mark.explain.target_commit.id),
+        _ => None,
+    }
+}
@@ -0,0 +1,210 @@
+//! TideMark
+//! ========
+//!
+//! File: src/ops/generate.rs
+//! Description: Source-code constant generation and Cargo build-script integration.
+//!
+//! Responsibility:
+//! - Render byte-stable version constants per language and rewrite files only on change.
+//!
+//! Architectural Position:
+//! - Operational adapter used by `tide generate` and by downstream `build.rs` scripts.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    api::Resolver,
+    config,
+    core::model::MarkResult,
+    error::{TideResult, io_err},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetLang {
+    Rust,
+    C,
+    Go,
+    Python,
+    TypeScript,
+}
+
+/// Values baked into generated sources and build-script environment variables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildInfo {
+    pub version: String,
+    pub anchor_tag: String,
+    pub commit: String,
+    pub timestamp: i64,
+    pub dirty: bool,
+}
+
+impl BuildInfo {
+    pub fn from_mark(mark: &MarkResult, dirty: bool) -> Self {
+        Self {
+            version: mark.coordinate.to_string(),
+            anchor_tag: mark.explain.anchor_tag.clone(),
+            commit: mark.explain.target_commit.id.clone(),
+            timestamp: mark.explain.target_commit.timestamp,
+            dirty,
+        }
+    }
+}
+
+/// Render constants for `lang`. Output depends only on `info` (no generation time), so
+/// regenerating an unchanged coordinate yields identical bytes.
+pub fn render(lang: TargetLang, info: &BuildInfo, go_package: &str) -> String {
+    let version = quote(info.version.as_str());
+    let anchor_tag = quote(info.anchor_tag.as_str());
+    let commit = quote(info.commit.as_str());
+    let timestamp = info.timestamp;
+    let dirty = info.dirty;
+    match lang {
+        TargetLang::Rust => format!(
+            "// @generated by `tide generate`; do not edit.\n\npub const TIDEMARK_VERSION: &str = {version};\npub const TIDEMARK_ANCHOR_TAG: &str = {anchor_tag};\npub const TIDEMARK_COMMIT: &str = {commit};\npub const TIDEMARK_TIMESTAMP: i64 = {timestamp};\npub const TIDEMARK_DIRTY: bool = {dirty};\n"
+        ),
+        TargetLang::C => format!(
+            "/* Generated by `tide generate`; do not edit. */\n#ifndef TIDEMARK_VERSION_H\n#define TIDEMARK_VERSION_H\n\n#define TIDEMARK_VERSION {version}\n#define TIDEMARK_ANCHOR_TAG {anchor_tag}\n#define TIDEMARK_COMMIT {commit}\n#define TIDEMARK_TIMESTAMP {timestamp}LL\n#define TIDEMARK_DIRTY {}\n\n#endif /* TIDEMARK_VERSION_H */\n",
+            u8::from(dirty)
+        ),
+        TargetLang::Go => format!(
+            "// Code generated by tide generate. DO NOT EDIT.\n\npackage {go_package}\n\nconst (\n\tVersion   = {version}\n\tAnchorTag = {anchor_tag}\n\tCommit    = {commit}\n\tTimestamp = int64({timestamp})\n\tDirty     = {dirty}\n)\n"
+        ),
+        TargetLang::Python => format!(
+            "# Generated by `tide generate`; do not edit.\n\nVERSION = {version}\nANCHOR_TAG = {anchor_tag}\nCOMMIT = {commit}\nTIMESTAMP = {timestamp}\nDIRTY = {}\n",
+            if dirty { "True" } else { "False" }
+        ),
+        TargetLang::TypeScript => format!(
+            "// Generated by `tide generate`; do not edit.\n\nexport const TIDEMARK_VERSION = {version};\nexport const TIDEMARK_ANCHOR_TAG = {anchor_tag};\nexport const TIDEMARK_COMMIT = {commit};\nexport const TIDEMARK_TIMESTAMP = {timestamp};\nexport const TIDEMARK_DIRTY = {dirty};\n"
+        ),
+    }
+}
+
+/// Write `content` unless the file already holds exactly these bytes, so build systems that
+/// track mtimes do not rebuild for an unchanged coordinate. Returns whether a write happened.
+pub fn write_if_changed(path: &Path, content: &str) -> TideResult<bool> {
+    if fs::read(path).ok().as_deref() == Some(content.as_bytes()) {
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(|err| io_err(parent, err))?;
+    }
+    fs::write(path, content.as_bytes()).map_err(|err| io_err(path, err))?;
+    Ok(true)
+}
+
+/// `cargo:` directives exposing [`BuildInfo`] as `TIDEMARK_*` compile-time env vars, plus
+/// rerun triggers on `HEAD`, refs, packed refs, and the TideMark config.
+pub fn cargo_directives(info: &BuildInfo, git_dir: &Path, repo_root: &Path) -> Vec<String> {
+    let mut lines = vec![
+        format!("cargo:rustc-env=TIDEMARK_VERSION={}", info.version),
+        format!("cargo:rustc-env=TIDEMARK_ANCHOR_TAG={}", info.anchor_tag),
+        format!("cargo:rustc-env=TIDEMARK_COMMIT={}", info.commit),
+        format!("cargo:rustc-env=TIDEMARK_TIMESTAMP={}", info.timestamp),
+        format!("cargo:rustc-env=TIDEMARK_DIRTY={}", info.dirty),
+    ];
+    let watched: [PathBuf; 4] = [
+        git_dir.join("HEAD"),
+        git_dir.join("refs"),
+        git_dir.join("packed-refs"),
+        repo_root.join(config::CONFIG_FILE_NAME),
+    ];
+    for path in watched {
+        lines.push(format!("cargo:rerun-if-changed={}", path.display()));
+    }
+    lines
+}
+
+/// Entry point for a downstream `build.rs` (`fn main`):
+///
+/// ```no_run
+/// tidemark::ops::generate::emit_cargo_build_env().expect("tidemark");
+/// ```
+///
+/// Resolves `HEAD` of the repository containing `CARGO_MANIFEST_DIR` with local tags only
+/// (build scripts must not touch the network) and prints the directives to stdout.
+pub fn emit_cargo_build_env() -> TideResult<()> {
+    let start = match env::var_os("CARGO_MANIFEST_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => env::current_dir().map_err(|err| io_err(".", err))?,
+    };
+    let resolver = Resolver::builder().repo(start).local_only(true).build()?;
+    let git = resolver.git();
+    let info = BuildInfo::from_mark(&resolver.mark(None)?, git.is_dirty()?);
+    for line in cargo_directives(&info, git.git_dir()?.as_path(), git.repo_root()) {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+/// Double-quoted literal valid in every target language.
+fn quote(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for ch in value.chars() {
+        match ch {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            _ => out.push(ch),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info() -> BuildInfo {
+        BuildInfo {
+            version: "1.2.3".to_string(),
+            anchor_tag: "v1".to_string(),
+            commit: "abc".to_string(),
+            timestamp: 1_704_067_200,
+            dirty: true,
+        }
+    }
+
+    #[test]
+    fn renders_each_language() {
+        let rust = render(TargetLang::Rust, &info(), "version");
+        assert!(rust.contains("pub const TIDEMARK_VERSION: &str = \"1.2.3\";\n"));
+        assert!(rust.contains("pub const TIDEMARK_DIRTY: bool = true;\n"));
+
+        let c = render(TargetLang::C, &info(), "version");
+        assert!(c.contains("#define TIDEMARK_TIMESTAMP 1704067200LL\n"));
+        assert!(c.contains("#define TIDEMARK_DIRTY 1\n"));
+
+        let go = render(TargetLang::Go, &info(), "buildinfo");
+        assert!(go.starts_with(
+            "// Code generated by tide generate. DO NOT EDIT.\n\npackage buildinfo\n"
+        ));
+
+        let py = render(TargetLang::Python, &info(), "version");
+        assert!(py.contains("DIRTY = True\n"));
+
+        let ts = render(TargetLang::TypeScript, &info(), "version");
+        assert!(ts.contains("export const TIDEMARK_COMMIT = \"abc\";\n"));
+    }
+
+    #[test]
+    fn quote_escapes_specials() {
+        assert_eq!(quote("a\"b\\c"), "\"a\\\"b\\\\c\"");
+    }
+
+    #[test]
+    fn cargo_directives_watch_refs() {
+        let lines = cargo_directives(&info(), Path::new("/r/.git"), Path::new("/r"));
+        assert_eq!(lines[0], "cargo:rustc-env=TIDEMARK_VERSION=1.2.3");
+        assert!(lines.contains(&"cargo:rerun-if-changed=/r/.git/HEAD".to_string()));
+        assert!(lines.contains(&"cargo:rerun-if-changed=/r/.git/refs".to_string()));
+    }
+}
@@ -5,7 +5,7 @@
 //! Description: Operations module index.
 //!
 //! Responsibility:
-//! - Expose service-management capabilities for scheduled TideMark execution.
+//! - Expose service-management, manifest-stamping, and source-generation capabilities.
 //!
 //! Architectural Position:
 //! - Operational boundary layer outside core version semantics.
@@ -14,4 +14,7 @@
 //! Email: silan.hu@u.nus.edu
 //! Copyright (c) 2026-2027 easynet. All rights reserved.
 
+pub mod cache;
+pub mod generate;
 pub mod service;
+pub mod stamp;
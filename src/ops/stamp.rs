@@ -0,0 +1,412 @@
+//! TideMark
+//! ========
+//!
+//! File: src/ops/stamp.rs
+//! Description: Writes resolved coordinates into project manifests with formatting preserved.
+//!
+//! Responsibility:
+//! - Map a coordinate onto each ecosystem's version scheme and patch only the version token.
+//!
+//! Architectural Position:
+//! - Operational adapter used by `tide stamp`; never influences coordinate resolution.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+use std::{
+    fmt, fs,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    core::model::VersionCoordinate,
+    error::{TideError, TideResult, io_err},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestKind {
+    Cargo,
+    Npm,
+    Python,
+    Debian,
+}
+
+impl ManifestKind {
+    /// Detect the manifest format from the file name.
+    pub fn detect(path: &Path) -> Option<Self> {
+        match path.file_name()?.to_str()? {
+            "Cargo.toml" => Some(Self::Cargo),
+            "package.json" => Some(Self::Npm),
+            "pyproject.toml" => Some(Self::Python),
+            "changelog" => Some(Self::Debian),
+            _ => None,
+        }
+    }
+
+    /// Version string in this ecosystem's scheme.
+    ///
+    /// - Cargo / npm (SemVer): `x.y.z[-channel][+meta]`
+    /// - Python (PEP 440): `x.y.z[+channel.meta]` as a local version label
+    /// - Debian: `x.y.z[~channel][+meta]`, so channel builds sort before the bare coordinate
+    pub fn version_for(self, coordinate: &VersionCoordinate) -> String {
+        let base = format!("{}.{}.{}", coordinate.x, coordinate.y, coordinate.z);
+        let channel = coordinate.channel.as_deref().filter(|v| !v.is_empty());
+        let meta = coordinate.metadata.as_deref().filter(|v| !v.is_empty());
+        match self {
+            Self::Cargo | Self::Npm => {
+                let mut out = base;
+                if let Some(channel) = channel {
+                    out.push_str(&format!("-{channel}"));
+                }
+                if let Some(meta) = meta {
+                    out.push_str(&format!("+{meta}"));
+                }
+                out
+            }
+            Self::Python => {
+                let local: Vec<String> = channel
+                    .into_iter()
+                    .chain(meta)
+                    .map(|part| part.replace('-', "."))
+                    .collect();
+                if local.is_empty() {
+                    base
+                } else {
+                    format!("{base}+{}", local.join("."))
+                }
+            }
+            Self::Debian => {
+                let mut out = base;
+                if let Some(channel) = channel {
+                    out.push_str(&format!("~{}", channel.replace('-', ".")));
+                }
+                if let Some(meta) = meta {
+                    out.push_str(&format!("+{}", meta.replace('-', ".")));
+                }
+                out
+            }
+        }
+    }
+}
+
+impl fmt::Display for ManifestKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cargo => write!(f, "cargo"),
+            Self::Npm => write!(f, "npm"),
+            Self::Python => write!(f, "python"),
+            Self::Debian => write!(f, "debian"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StampStatus {
+    Updated,
+    Unchanged,
+    /// `--check` found a manifest that does not carry the resolved version.
+    Stale,
+}
+
+impl fmt::Display for StampStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Updated => write!(f, "updated"),
+            Self::Unchanged => write!(f, "unchanged"),
+            Self::Stale => write!(f, "stale"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StampOutcome {
+    pub path: PathBuf,
+    pub kind: ManifestKind,
+    pub version: String,
+    pub status: StampStatus,
+}
+
+#[derive(Debug, Clone)]
+pub struct StampRequest {
+    pub repo_root: PathBuf,
+    pub targets: Vec<PathBuf>,
+    pub check: bool,
+}
+
+/// Stamp every target, or only compare when `check` is set. All manifests are patched in
+/// memory before anything is written, so one unsupported file leaves the tree untouched.
+pub fn stamp_manifests(
+    request: &StampRequest,
+    coordinate: &VersionCoordinate,
+) -> TideResult<Vec<StampOutcome>> {
+    let mut planned = Vec::with_capacity(request.targets.len());
+    for target in &request.targets {
+        let full_path = if target.is_absolute() {
+            target.clone()
+        } else {
+            request.repo_root.join(target)
+        };
+        let kind = ManifestKind::detect(target).ok_or_else(|| TideError::ManifestStamp {
+            path: target.clone(),
+            message: "unsupported manifest; expected Cargo.toml, package.json, pyproject.toml, or debian/changelog".to_string(),
+        })?;
+        let version = kind.version_for(coordinate);
+        let content = fs::read_to_string(&full_path).map_err(|err| io_err(&full_path, err))?;
+        let stamped =
+            stamp_content(kind, content.as_str(), version.as_str()).map_err(|message| {
+                TideError::ManifestStamp {
+                    path: target.clone(),
+                    message,
+                }
+            })?;
+        let status = if stamped == content {
+            StampStatus::Unchanged
+        } else if request.check {
+            StampStatus::Stale
+        } else {
+            StampStatus::Updated
+        };
+        planned.push((
+            full_path,
+            stamped,
+            StampOutcome {
+                path: target.clone(),
+                kind,
+                version,
+                status,
+            },
+        ));
+    }
+
+    let mut outcomes = Vec::with_capacity(planned.len());
+    for (full_path, stamped, outcome) in planned {
+        if outcome.status == StampStatus::Updated {
+            fs::write(&full_path, stamped.as_bytes()).map_err(|err| io_err(&full_path, err))?;
+        }
+        outcomes.push(outcome);
+    }
+    Ok(outcomes)
+}
+
+/// Replace the version token in `content`; everything else is kept byte-for-byte.
+pub fn stamp_content(kind: ManifestKind, content: &str, version: &str) -> Result<String, String> {
+    let span = match kind {
+        ManifestKind::Cargo => toml_version_span(content, &["package", "workspace.package"]),
+        ManifestKind::Python => toml_version_span(content, &["project", "tool.poetry"]),
+        ManifestKind::Npm => json_version_span(content),
+        ManifestKind::Debian => debian_version_span(content),
+    }?;
+
+    let replacement = match kind {
+        // Keep an existing Debian revision (`-1`) so packaging bumps stay independent.
+        ManifestKind::Debian => match content[span.clone()].rfind('-') {
+            Some(idx) => format!("{version}{}", &content[span.start + idx..span.end]),
+            None => version.to_string(),
+        },
+        _ => version.to_string(),
+    };
+
+    let mut out = String::with_capacity(content.len() + replacement.len());
+    out.push_str(&content[..span.start]);
+    out.push_str(replacement.as_str());
+    out.push_str(&content[span.end..]);
+    Ok(out)
+}
+
+/// Byte range of the quoted `version = "..."` value in the first matching table.
+fn toml_version_span(content: &str, tables: &[&str]) -> Result<Range<usize>, String> {
+    for table in tables {
+        let mut in_table = false;
+        let mut offset = 0;
+        for line in content.split_inclusive('\n') {
+            let start = offset;
+            offset += line.len();
+            let trimmed = line.trim();
+            if trimmed.starts_with('[') {
+                let name = trimmed
+                    .trim_start_matches('[')
+                    .split(']')
+                    .next()
+                    .unwrap_or_default()
+                    .trim();
+                in_table = !trimmed.starts_with("[[") && name == *table;
+                continue;
+            }
+            if !in_table {
+                continue;
+            }
+            let Some(rest) = trimmed.strip_prefix("version") else {
+                continue;
+            };
+            let rest = rest.trim_start();
+            if rest.starts_with('.') {
+                return Err(format!(
+                    "`[{table}] version` is inherited or computed; stamp the defining manifest instead"
+                ));
+            }
+            let Some(value) = rest.strip_prefix('=') else {
+                continue;
+            };
+            let value = value.trim_start();
+            let quote = match value.chars().next() {
+                Some(q @ ('"' | '\'')) => q,
+                _ => return Err(format!("`[{table}] version` is not a string literal")),
+            };
+            let value_start = start + (value.as_ptr() as usize - line.as_ptr() as usize) + 1;
+            let len = value[1..]
+                .find(quote)
+                .ok_or_else(|| format!("unterminated `[{table}] version` string"))?;
+            return Ok(value_start..value_start + len);
+        }
+    }
+    Err(format!(
+        "no `version` key in {}",
+        tables
+            .iter()
+            .map(|t| format!("[{t}]"))
+            .collect::<Vec<_>>()
+            .join(" or ")
+    ))
+}
+
+/// Byte range of the top-level `"version"` string value in a JSON document.
+fn json_version_span(content: &str) -> Result<Range<usize>, String> {
+    let bytes = content.as_bytes();
+    let mut depth = 0usize;
+    let mut idx = 0;
+    let mut pending_key: Option<Range<usize>> = None;
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            b'"' => {
+                let start = idx + 1;
+                let end = json_string_end(bytes, start)?;
+                let is_version_value = pending_key
+                    .take()
+                    .is_some_and(|key| &content[key] == "version");
+                if depth == 1 && is_version_value {
+                    return Ok(start..end);
+                }
+                let next = content[end + 1..].trim_start();
+                if depth == 1 && next.starts_with(':') {
+                    pending_key = Some(start..end);
+                }
+                idx = end;
+            }
+            b',' => pending_key = None,
+            _ => {}
+        }
+        idx += 1;
+    }
+    Err("no top-level string `\"version\"` field".to_string())
+}
+
+fn json_string_end(bytes: &[u8], mut idx: usize) -> Result<usize, String> {
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'\\' => idx += 2,
+            b'"' => return Ok(idx),
+            _ => idx += 1,
+        }
+    }
+    Err("unterminated JSON string".to_string())
+}
+
+/// Byte range inside the parentheses of the newest `package (version) dist; ...` entry.
+fn debian_version_span(content: &str) -> Result<Range<usize>, String> {
+    let header = content
+        .split_inclusive('\n')
+        .scan(0, |offset, line| {
+            let start = *offset;
+            *offset += line.len();
+            Some((start, line))
+        })
+        .find(|(_, line)| !line.trim().is_empty())
+        .ok_or_else(|| "changelog is empty".to_string())?;
+    let (start, line) = header;
+    let open = line
+        .find('(')
+        .ok_or_else(|| "first changelog entry has no `(version)`".to_string())?;
+    let close = line[open..]
+        .find(')')
+        .ok_or_else(|| "first changelog entry has no `(version)`".to_string())?;
+    Ok(start + open + 1..start + open + close)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coordinate(channel: Option<&str>, metadata: Option<&str>) -> VersionCoordinate {
+        VersionCoordinate {
+            x: 1,
+            y: 2,
+            z: 3,
+            channel: channel.map(str::to_string),
+            metadata: metadata.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn versions_follow_each_scheme() {
+        let c = coordinate(Some("feat-x-0a1b2c3d"), Some("ci.42"));
+        assert_eq!(
+            ManifestKind::Cargo.version_for(&c),
+            "1.2.3-feat-x-0a1b2c3d+ci.42"
+        );
+        assert_eq!(
+            ManifestKind::Python.version_for(&c),
+            "1.2.3+feat.x.0a1b2c3d.ci.42"
+        );
+        assert_eq!(
+            ManifestKind::Debian.version_for(&c),
+            "1.2.3~feat.x.0a1b2c3d+ci.42"
+        );
+        assert_eq!(
+            ManifestKind::Python.version_for(&coordinate(None, None)),
+            "1.2.3"
+        );
+    }
+
+    #[test]
+    fn toml_stamp_touches_only_the_package_version() {
+        let raw = "[package]\nname = \"a\"\nversion   = \"0.1.0\" # keep\n\n[dependencies]\nx = { version = \"1\" }\n";
+        let out = stamp_content(ManifestKind::Cargo, raw, "1.2.3").unwrap();
+        assert_eq!(
+            out,
+            "[package]\nname = \"a\"\nversion   = \"1.2.3\" # keep\n\n[dependencies]\nx = { version = \"1\" }\n"
+        );
+
+        let inherited = "[package]\nversion.workspace = true\n";
+        assert!(stamp_content(ManifestKind::Cargo, inherited, "1.2.3").is_err());
+
+        let poetry = "[tool.poetry]\nversion = '0.0.0'\n";
+        assert_eq!(
+            stamp_content(ManifestKind::Python, poetry, "1.2.3").unwrap(),
+            "[tool.poetry]\nversion = '1.2.3'\n"
+        );
+    }
+
+    #[test]
+    fn json_stamp_ignores_nested_versions() {
+        let raw = "{\n  \"engines\": {\"version\": \"x\"},\n  \"name\": \"version\",\n  \"version\": \"0.0.1\"\n}\n";
+        let out = stamp_content(ManifestKind::Npm, raw, "1.2.3").unwrap();
+        assert_eq!(
+            out,
+            "{\n  \"engines\": {\"version\": \"x\"},\n  \"name\": \"version\",\n  \"version\": \"1.2.3\"\n}\n"
+        );
+        assert!(stamp_content(ManifestKind::Npm, "{\"name\": \"a\"}", "1.2.3").is_err());
+    }
+
+    #[test]
+    fn debian_stamp_keeps_revision() {
+        let raw = "pkg (0.1.0-1) unstable; urgency=low\n\n  * old (1.0)\n";
+        assert_eq!(
+            stamp_content(ManifestKind::Debian, raw, "1.2.3").unwrap(),
+            "pkg (1.2.3-1) unstable; urgency=low\n\n  * old (1.0)\n"
+        );
+    }
+}
@@ -0,0 +1,74 @@
+//! TideMark
+//! ========
+//!
+//! File: tests/api_integration.rs
+//! Description: Integration tests for the embedding `Resolver` facade.
+//!
+//! Responsibility:
+//! - Ensure the library API matches CLI output and honors config/provider overrides.
+//!
+//! Architectural Position:
+//! - Public-surface verification for downstream Rust consumers.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+mod common;
+
+use common::RepoFixture;
+use tidemark::{GitCli, Resolver, TideConfig, TideError};
+
+#[test]
+fn resolver_matches_cli_and_honors_overrides() {
+    let repo = RepoFixture::init();
+
+    let c1 = repo.write_file_and_commit("a.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    repo.write_file_and_commit("b.txt", "b\n", "c2", "2024-01-01T01:00:00+00:00");
+    repo.tag_annotated("rel2", "release 2", "2024-01-01T01:00:00+00:00");
+    repo.write_file_and_commit("b.txt", "c\n", "c3", "2024-01-02T00:00:00+00:00");
+
+    let resolver = Resolver::builder()
+        .repo(repo.root())
+        .local_only(true)
+        .build()
+        .expect("open repository");
+    let head = resolver.mark(None).unwrap();
+    let cli = repo.run_tide(&["mark", "--local-only"]);
+    assert_eq!(
+        String::from_utf8_lossy(&cli.stdout),
+        format!("{}\n", head.coordinate)
+    );
+    assert_eq!(head.coordinate.to_string(), "1.1.1");
+    assert_eq!(
+        resolver
+            .mark(Some(c1.as_str()))
+            .unwrap()
+            .coordinate
+            .to_string(),
+        "1.0.0"
+    );
+    assert_eq!(
+        resolver.file("a.txt").unwrap().mark.coordinate.to_string(),
+        "1.0.0"
+    );
+    assert_eq!(resolver.releases().unwrap().releases.len(), 1);
+
+    let mut config = TideConfig::default();
+    config.release.tag_prefix = "rel".to_string();
+    let overridden = Resolver::builder()
+        .provider(Box::new(GitCli::discover(repo.root()).unwrap()))
+        .config(config)
+        .local_only(true)
+        .metadata_suffix(Some("{anchor_tag}".to_string()))
+        .build()
+        .unwrap();
+    assert_eq!(
+        overridden.mark(None).unwrap().coordinate.to_string(),
+        "1.1.1.rel2"
+    );
+
+    let missing = overridden.file("missing.txt").unwrap_err();
+    assert!(matches!(missing, TideError::FileHistoryNotFound { .. }));
+}
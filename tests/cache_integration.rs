@@ -0,0 +1,279 @@
+//! TideMark
+//! ========
+//!
+//! File: tests/cache_integration.rs
+//! Description: Integration tests for `tide cache` maintenance commands.
+//!
+//! Responsibility:
+//! - Verify stats, reachability pruning, verification, clearing, tag-driven invalidation, and shared remote tiers.
+//!
+//! Architectural Position:
+//! - End-to-end coverage for the cache maintenance command boundary.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+mod common;
+
+use std::fs;
+
+use common::{RepoFixture, cache_server::StandInCacheServer};
+
+fn stdout(output: &std::process::Output) -> String {
+    assert!(
+        output.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn cache_commands_report_prune_verify_and_clear() {
+    let repo = RepoFixture::init();
+
+    repo.write_file_and_commit("a.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    stdout(&repo.run_tide(&["mark", "--local-only"]));
+
+    repo.git(&["checkout", "-q", "-b", "scratch"], &[]);
+    repo.write_file_and_commit("b.txt", "b\n", "c2", "2024-01-02T00:00:00+00:00");
+    stdout(&repo.run_tide(&["mark", "--local-only"]));
+    stdout(&repo.run_tide(&["file", "a.txt", "--local-only"]));
+
+    let stats = stdout(&repo.run_tide(&["cache", "stats"]));
+    // Only the `scratch` mark is off its anchor, so it alone builds an ancestry index.
+    assert!(stats.starts_with("ancestry\t1\t"));
+    assert!(stats.contains("\nfile\t1\t"));
+    // `file` resolves its commit through the mark resolver, so it adds a mark entry too.
+    assert!(stats.contains("\nmark\t3\t"));
+    assert!(stats.contains("\ntotal\t5\t"));
+
+    repo.git(&["checkout", "-q", "main"], &[]);
+    repo.git(&["branch", "-q", "-D", "scratch"], &[]);
+    let pruned = stdout(&repo.run_tide(&["cache", "prune", "--unreachable"]));
+    assert!(pruned.starts_with("removed\t1\t"));
+    assert!(stdout(&repo.run_tide(&["cache", "stats"])).contains("mark\t2\t"));
+
+    assert_eq!(stdout(&repo.run_tide(&["cache", "verify"])), "ok\n");
+    let mark_dir = repo.cache_dir().join("mark");
+    let entry = fs::read_dir(&mark_dir)
+        .expect("read mark cache")
+        .next()
+        .expect("one mark entry")
+        .expect("dir entry")
+        .path();
+    fs::write(&entry, "{\"coordinate\":").expect("truncate entry");
+    assert_eq!(repo.run_tide(&["cache", "verify"]).status.code(), Some(1));
+
+    let cleared = stdout(&repo.run_tide(&["cache", "clear", "--namespace", "mark"]));
+    assert!(cleared.starts_with("removed\t2\t"));
+    assert!(stdout(&repo.run_tide(&["cache", "stats"])).contains("\nfile\t1\t"));
+
+    assert_eq!(repo.run_tide(&["cache", "prune"]).status.code(), Some(2));
+}
+
+#[test]
+fn cached_coordinates_follow_added_moved_and_deleted_tags() {
+    let repo = RepoFixture::init();
+
+    repo.write_file_and_commit("a.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    repo.write_file_and_commit("a.txt", "b\n", "c2", "2024-01-01T01:00:00+00:00");
+    let mark = |repo: &RepoFixture| stdout(&repo.run_tide(&["mark", "--local-only"]));
+    let file = |repo: &RepoFixture| stdout(&repo.run_tide(&["file", "a.txt", "--local-only"]));
+
+    assert_eq!(mark(&repo), "1.0.1\n");
+    assert_eq!(file(&repo), "1.0.1\n");
+
+    // Move v1 onto HEAD: same tag name, different peeled commit.
+    repo.git(&["tag", "-d", "v1"], &[]);
+    repo.tag_annotated("v1", "release 1 again", "2024-01-01T01:00:00+00:00");
+    assert_eq!(mark(&repo), "1.0.0\n");
+    assert_eq!(file(&repo), "1.0.0\n");
+
+    repo.write_file_and_commit("a.txt", "c\n", "c3", "2024-01-01T02:00:00+00:00");
+    assert_eq!(mark(&repo), "1.0.1\n");
+    repo.tag_annotated("v2", "release 2", "2024-01-01T02:00:00+00:00");
+    assert_eq!(mark(&repo), "2.0.0\n");
+
+    repo.git(&["tag", "-d", "v2"], &[]);
+    assert_eq!(mark(&repo), "1.0.1\n");
+}
+
+#[test]
+fn local_timezone_entries_are_keyed_by_resolved_offset() {
+    let repo = RepoFixture::init();
+
+    repo.write_file_and_commit("app.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    // 20:00 UTC is already the next morning at UTC+9.
+    repo.write_file_and_commit("app.txt", "b\n", "c2", "2024-01-01T20:00:00+00:00");
+
+    for (tz, mark) in [
+        ("UTC0", "1.0.1\n"),
+        ("JST-9", "1.1.1\n"),
+        ("UTC0", "1.0.1\n"),
+    ] {
+        let env = [("TZ", tz)];
+        assert_eq!(
+            stdout(&repo.run_tide_with_env(&["mark", "--local-only"], &env)),
+            mark,
+            "TZ={tz}"
+        );
+        let file = stdout(&repo.run_tide_with_env(&["file", "app.txt", "--local-only"], &env));
+        assert!(file.starts_with(mark.trim_end()), "TZ={tz}: {file}");
+    }
+}
+
+#[test]
+fn damaged_or_foreign_entries_are_rewritten_and_reported() {
+    let repo = RepoFixture::init();
+    repo.write_file_and_commit("a.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+
+    let explain =
+        |repo: &RepoFixture| stdout(&repo.run_tide(&["mark", "--local-only", "--explain"]));
+    let coordinate = stdout(&repo.run_tide(&["mark", "--local-only"]));
+    assert!(explain(&repo).contains("\ncache=hit\n"));
+
+    let entry = fs::read_dir(repo.cache_dir().join("mark"))
+        .expect("read mark cache")
+        .next()
+        .expect("one mark entry")
+        .expect("dir entry")
+        .path();
+    let original = fs::read(&entry).expect("read entry");
+
+    // Truncated write: header intact, payload cut short.
+    fs::write(&entry, &original[..original.len() - 10]).expect("truncate entry");
+    let out = explain(&repo);
+    assert!(
+        out.contains("\ncache=rewritten\ncache_invalid=checksum\n"),
+        "{out}"
+    );
+    assert!(explain(&repo).contains("\ncache=hit\n"));
+
+    // Entry written by another tidemark release.
+    let text = String::from_utf8(original.clone()).expect("utf8 entry");
+    let version = env!("CARGO_PKG_VERSION");
+    fs::write(
+        &entry,
+        text.replacen(
+            &format!("\"tidemark\":\"{version}\""),
+            "\"tidemark\":\"0.0.0-old\"",
+            1,
+        ),
+    )
+    .expect("rewrite header");
+    assert!(explain(&repo).contains("\ncache_invalid=tool-version\n"));
+
+    // Pre-header entries are plain JSON and read as corrupt.
+    fs::write(&entry, "{\"coordinate\":").expect("garbage entry");
+    assert!(explain(&repo).contains("\ncache_invalid=corrupt\n"));
+
+    assert_eq!(
+        stdout(&repo.run_tide(&["mark", "--local-only"])),
+        coordinate
+    );
+    assert_eq!(stdout(&repo.run_tide(&["cache", "verify"])), "ok\n");
+}
+
+fn explain_with_remote(repo: &RepoFixture, remote: &str) -> String {
+    let override_arg = format!("cache.remote={remote}");
+    stdout(&repo.run_tide(&[
+        "-c",
+        override_arg.as_str(),
+        "mark",
+        "--local-only",
+        "--explain",
+    ]))
+}
+
+#[test]
+fn http_remote_tier_is_read_through_and_write_behind() {
+    let repo = RepoFixture::init();
+    repo.write_file_and_commit("a.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    let server = StandInCacheServer::start();
+    let url = server.url("/ci/tide");
+
+    let first = explain_with_remote(&repo, url.as_str());
+    assert!(first.contains("\ncache=miss\n"), "{first}");
+    let uploaded = server.paths();
+    assert_eq!(uploaded.len(), 1);
+    assert!(uploaded[0].starts_with("/ci/tide/mark/"));
+
+    // A fresh CI job: empty local cache, warm shared cache.
+    stdout(&repo.run_tide(&["cache", "clear"]));
+    let second = explain_with_remote(&repo, url.as_str());
+    assert!(second.contains("\ncache=remote-hit\n"), "{second}");
+    assert_eq!(first.lines().next(), second.lines().next());
+    assert!(stdout(&repo.run_tide(&["cache", "stats"])).starts_with("mark\t1\t"));
+    assert!(explain_with_remote(&repo, url.as_str()).contains("\ncache=hit\n"));
+
+    // An unreachable remote degrades to a local miss instead of failing the build.
+    stdout(&repo.run_tide(&["cache", "clear"]));
+    let offline = explain_with_remote(&repo, "http://127.0.0.1:1/tide");
+    assert!(offline.contains("\ncache=miss\n"), "{offline}");
+}
+
+#[test]
+fn shared_directory_remote_tier_serves_other_jobs() {
+    let repo = RepoFixture::init();
+    repo.write_file_and_commit("a.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    let shared = tempfile::tempdir().expect("shared dir");
+    let remote = shared.path().display().to_string();
+
+    assert!(explain_with_remote(&repo, remote.as_str()).contains("\ncache=miss\n"));
+    assert_eq!(
+        fs::read_dir(shared.path().join("mark"))
+            .expect("shared mark dir")
+            .count(),
+        1
+    );
+
+    stdout(&repo.run_tide(&["cache", "clear"]));
+    assert!(explain_with_remote(&repo, remote.as_str()).contains("\ncache=remote-hit\n"));
+
+    let relative = repo.run_tide(&["-c", "cache.remote=ci-cache", "mark", "--local-only"]);
+    assert_eq!(relative.status.code(), Some(2));
+}
+
+#[test]
+fn unreachable_remote_tier_is_skipped_after_the_first_failure() {
+    let repo = RepoFixture::init();
+    for index in 0..12 {
+        repo.write_file_and_commit(
+            format!("f{index}.txt").as_str(),
+            "x\n",
+            "c",
+            "2024-01-01T00:00:00+00:00",
+        );
+    }
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+
+    // A closed port refuses at once; a listener that never accepts leaves every request to
+    // time out, which must not be paid once per path.
+    let closed = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+    let closed_url = format!("http://{}/tide", closed.local_addr().expect("addr"));
+    drop(closed);
+    let silent = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+    let silent_url = format!("http://{}/tide", silent.local_addr().expect("addr"));
+
+    for url in [closed_url, silent_url] {
+        stdout(&repo.run_tide(&["cache", "clear"]));
+        let override_arg = format!("cache.remote={url}");
+        let started = std::time::Instant::now();
+        let output =
+            stdout(&repo.run_tide(&["-c", override_arg.as_str(), "file", ".", "--local-only"]));
+        let elapsed = started.elapsed();
+        assert_eq!(output.lines().count(), 12, "{output}");
+        assert!(
+            elapsed < std::time::Duration::from_secs(10),
+            "{url} took {elapsed:?}"
+        );
+    }
+}
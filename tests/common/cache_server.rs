@@ -0,0 +1,108 @@
+//! TideMark
+//! ========
+//!
+//! File: tests/common/cache_server.rs
+//! Description: In-memory stand-in for the HTTP cache protocol.
+//!
+//! Responsibility:
+//! - Answer `GET`/`PUT <path>` with stored blobs so remote cache tiers can be exercised without a real service.
+//!
+//! Architectural Position:
+//! - Test support layer for remote cache integration suites.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+#![allow(dead_code)]
+
+use std::{
+    collections::BTreeMap,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+type Blobs = Arc<Mutex<BTreeMap<String, Vec<u8>>>>;
+
+/// Serves until the test process exits; `GET` misses return 404, `PUT` returns 204.
+pub struct StandInCacheServer {
+    addr: String,
+    blobs: Blobs,
+}
+
+impl StandInCacheServer {
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind stand-in cache");
+        let addr = listener.local_addr().expect("local addr").to_string();
+        let blobs: Blobs = Arc::default();
+        let shared = Arc::clone(&blobs);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                serve(stream, &shared);
+            }
+        });
+        Self { addr, blobs }
+    }
+
+    /// Base URL with `prefix`, e.g. `http://127.0.0.1:PORT/tide`.
+    pub fn url(&self, prefix: &str) -> String {
+        format!("http://{}{prefix}", self.addr)
+    }
+
+    /// Stored request paths, sorted.
+    pub fn paths(&self) -> Vec<String> {
+        self.blobs.lock().expect("blobs").keys().cloned().collect()
+    }
+}
+
+fn serve(stream: TcpStream, blobs: &Blobs) {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (
+        parts.next().unwrap_or_default().to_string(),
+        parts.next().unwrap_or_default().to_string(),
+    );
+
+    let mut length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).is_err() || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            length = value.trim().parse().unwrap_or(0);
+        }
+    }
+    let mut body = vec![0u8; length];
+    if reader.read_exact(&mut body).is_err() {
+        return;
+    }
+
+    let (status, payload) = match method.as_str() {
+        "GET" => match blobs.lock().expect("blobs").get(&path) {
+            Some(blob) => ("200 OK", blob.clone()),
+            None => ("404 Not Found", Vec::new()),
+        },
+        "PUT" => {
+            blobs.lock().expect("blobs").insert(path, body);
+            ("204 No Content", Vec::new())
+        }
+        _ => ("405 Method Not Allowed", Vec::new()),
+    };
+    let mut stream = reader.into_inner();
+    let head = format!(
+        "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        payload.len()
+    );
+    let _ = stream
+        .write_all(head.as_bytes())
+        .and_then(|()| stream.write_all(&payload));
+}
@@ -14,6 +14,8 @@
 //! Email: silan.hu@u.nus.edu
 //! Copyright (c) 2026-2027 easynet. All rights reserved.
 
+pub mod cache_server;
+
 use std::{
     env, fs,
     path::{Path, PathBuf},
@@ -78,6 +80,10 @@ impl RepoFixture {
         run_git(self.root(), &["tag", name], &[]);
     }
 
+    pub fn git(&self, args: &[&str], envs: &[(&str, &str)]) {
+        run_git(self.root(), args, envs);
+    }
+
     pub fn rev_parse(&self, rev: &str) -> String {
         git_output(self.root(), &["rev-parse", rev])
             .trim()
@@ -94,11 +100,23 @@ impl RepoFixture {
     pub fn run_tide(&self, args: &[&str]) -> Output {
         Command::new(cargo::cargo_bin!("tide"))
             .args(args)
+            .env("XDG_CONFIG_HOME", self.xdg_config_home())
             .current_dir(self.root())
             .output()
             .expect("run tidemark")
     }
 
+    pub fn run_tide_with_env(&self, args: &[&str], envs: &[(&str, &str)]) -> Output {
+        let mut cmd = Command::new(cargo::cargo_bin!("tide"));
+        cmd.args(args)
+            .env("XDG_CONFIG_HOME", self.xdg_config_home())
+            .current_dir(self.root());
+        for (k, v) in envs {
+            cmd.env(k, v);
+        }
+        cmd.output().expect("run tidemark")
+    }
+
     pub fn run_git_tide(&self, args: &[&str]) -> Output {
         Command::new(cargo::cargo_bin!("git-tide"))
             .args(args)
@@ -129,6 +147,17 @@ impl RepoFixture {
         fs::write(self.root().join(".tidemark.toml"), raw).expect("write config");
     }
 
+    /// Isolated `XDG_CONFIG_HOME` so the developer's global config never leaks into tests.
+    pub fn xdg_config_home(&self) -> PathBuf {
+        self.root().join(".git").join("xdg-config")
+    }
+
+    pub fn write_global_config(&self, raw: &str) {
+        let dir = self.xdg_config_home().join("tidemark");
+        fs::create_dir_all(&dir).expect("create global config dir");
+        fs::write(dir.join("config.toml"), raw).expect("write global config");
+    }
+
     pub fn cache_dir(&self) -> PathBuf {
         self.root().join(".git").join("tidemark-cache")
     }
@@ -0,0 +1,349 @@
+//! TideMark
+//! ========
+//!
+//! File: tests/daemon_integration.rs
+//! Description: Integration tests for `tide serve` and the CLI's transparent use of it.
+//!
+//! Responsibility:
+//! - Validate JSON-RPC answers and errors, ref-change invalidation, context fallback, and stale-daemon recovery.
+//!
+//! Architectural Position:
+//! - End-to-end verification of the daemon front end against the in-process CLI.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+#![cfg(unix)]
+
+mod common;
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    os::unix::net::UnixStream,
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+};
+
+use assert_cmd::cargo;
+use common::RepoFixture;
+use serde_json::{Value, json};
+
+/// `tide serve` child process, killed on drop.
+struct ServedRepo {
+    child: Child,
+    socket: PathBuf,
+}
+
+impl ServedRepo {
+    fn start(repo: &RepoFixture) -> Self {
+        Self::start_with_env(repo, &[])
+    }
+
+    fn start_with_env(repo: &RepoFixture, envs: &[(&str, &str)]) -> Self {
+        let socket = repo.root().join(".git").join("tide.sock");
+        let mut child = Command::new(cargo::cargo_bin!("tide"))
+            .args(["serve", "--socket"])
+            .arg(&socket)
+            .env("XDG_CONFIG_HOME", repo.xdg_config_home())
+            .envs(envs.iter().copied())
+            .current_dir(repo.root())
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("spawn tide serve");
+        let mut ready = String::new();
+        BufReader::new(child.stdout.take().expect("daemon stdout"))
+            .read_line(&mut ready)
+            .expect("read ready line");
+        assert_eq!(ready, format!("serving\t{}\n", socket.display()));
+        Self { child, socket }
+    }
+
+    fn call(&self, method: &str, params: Value) -> Value {
+        let mut stream = UnixStream::connect(&self.socket).expect("connect daemon");
+        let request = json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params});
+        stream
+            .write_all(format!("{request}\n").as_bytes())
+            .expect("send request");
+        let mut line = String::new();
+        BufReader::new(stream)
+            .read_line(&mut line)
+            .expect("read response");
+        serde_json::from_str(line.as_str()).expect("response json")
+    }
+}
+
+impl Drop for ServedRepo {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// A `PATH` without git: only the daemon can answer.
+fn no_git_path(repo: &RepoFixture) -> PathBuf {
+    repo.root().join(".git").join("empty-bin")
+}
+
+fn stdout(output: &std::process::Output) -> String {
+    assert!(
+        output.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+fn seeded_repo() -> RepoFixture {
+    let repo = RepoFixture::init();
+    repo.write_file_and_commit("app.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    repo.write_file_and_commit("app.txt", "b\n", "c2", "2024-01-02T01:00:00+00:00");
+    repo.write_file_and_commit("lib.txt", "b\n", "c3", "2024-01-02T02:00:00+00:00");
+    std::fs::create_dir_all(no_git_path(&repo)).expect("create empty PATH dir");
+    repo
+}
+
+fn run_without_git(repo: &RepoFixture, args: &[&str]) -> std::process::Output {
+    let path = no_git_path(repo);
+    repo.run_tide_with_env(args, &[("PATH", path.to_str().expect("utf-8 path"))])
+}
+
+#[test]
+fn cli_answers_through_the_daemon_and_tracks_ref_changes() {
+    let repo = seeded_repo();
+    let expected_mark = stdout(&repo.run_tide(&["--no-daemon", "mark", "--local-only"]));
+    let expected_file = stdout(&repo.run_tide(&["--no-daemon", "file", "app.txt", "--local-only"]));
+    let daemon = ServedRepo::start(&repo);
+    assert_eq!(
+        std::fs::read_to_string(repo.root().join(".git").join("tidemark-daemon")).unwrap(),
+        format!("{}\n", daemon.socket.display())
+    );
+
+    assert_eq!(
+        stdout(&run_without_git(&repo, &["mark", "--local-only"])),
+        expected_mark
+    );
+    assert_eq!(
+        stdout(&run_without_git(
+            &repo,
+            &["file", "app.txt", "--local-only"]
+        )),
+        expected_file
+    );
+    let explain = stdout(&run_without_git(
+        &repo,
+        &["mark", "--explain", "--local-only"],
+    ));
+    assert!(explain.contains("version=1.1.2\n"), "{explain}");
+    // --no-daemon really resolves in process, which needs git.
+    assert!(
+        !run_without_git(&repo, &["--no-daemon", "mark", "--local-only"])
+            .status
+            .success()
+    );
+
+    // A new commit and a new tag are visible on the next request.
+    let c4 = repo.write_file_and_commit("app.txt", "c\n", "c4", "2024-01-02T03:00:00+00:00");
+    assert_eq!(
+        stdout(&run_without_git(&repo, &["mark", "--local-only"])),
+        "1.1.3\n"
+    );
+    assert_eq!(
+        stdout(&run_without_git(
+            &repo,
+            &["locate", "1.1.3", "--local-only"]
+        )),
+        format!("{c4}\n")
+    );
+    repo.tag_annotated("v2", "release 2", "2024-01-02T03:00:00+00:00");
+    assert_eq!(
+        stdout(&run_without_git(&repo, &["mark", "--local-only"])),
+        "2.0.0\n"
+    );
+    assert_eq!(
+        stdout(&run_without_git(
+            &repo,
+            &["release", "list", "--local-only"]
+        ))
+        .lines()
+        .count(),
+        2
+    );
+
+    // Config edits are picked up without restarting the daemon.
+    repo.write_config("[time]\ntimezone = \"-05:00\"\n");
+    let explain = stdout(&run_without_git(
+        &repo,
+        &["mark", "--explain", "--local-only"],
+    ));
+    assert!(explain.contains("timezone=-05:00\n"), "{explain}");
+
+    // Overrides the daemon was not started with resolve in process.
+    assert!(
+        !run_without_git(&repo, &["-c", "time.timezone=UTC", "mark", "--local-only"])
+            .status
+            .success()
+    );
+    let output = repo.run_tide(&["-c", "time.timezone=UTC", "mark", "--local-only"]);
+    assert_eq!(stdout(&output), "2.0.0\n");
+}
+
+#[test]
+fn json_rpc_methods_report_results_and_typed_errors() {
+    let repo = seeded_repo();
+    let c2 = repo.rev_parse("HEAD~1");
+    let daemon = ServedRepo::start(&repo);
+
+    let mark = daemon.call("mark", json!({"rev": c2, "local_only": true}));
+    assert_eq!(mark["id"], 1);
+    assert_eq!(mark["result"]["coordinate"]["y"], 1);
+    assert_eq!(
+        mark["result"]["explain"]["target_commit"]["id"],
+        c2.as_str()
+    );
+    assert_eq!(mark["result"]["warnings"], json!([]));
+
+    let file = daemon.call("file", json!({"path": "lib.txt", "local_only": true}));
+    assert_eq!(file["result"]["path"], "lib.txt");
+    assert_eq!(file["result"]["mark"]["coordinate"]["z"], 2);
+
+    let releases = daemon.call("releases", Value::Null);
+    assert_eq!(releases["result"]["releases"][0]["tag"]["name"], "v1");
+
+    let located = daemon.call("locate", json!({"coordinate": "1.1.1", "local_only": true}));
+    assert_eq!(located["result"]["anchor_tag"], "v1");
+    assert_eq!(located["result"]["commits"][0]["id"], c2.as_str());
+
+    let missing = daemon.call("locate", json!({"coordinate": "7.0.0"}));
+    assert_eq!(missing["error"]["code"], -32000);
+    assert_eq!(missing["error"]["data"]["exit_code"], 4);
+
+    assert_eq!(daemon.call("file", json!({}))["error"]["code"], -32602);
+    assert_eq!(daemon.call("stamp", Value::Null)["error"]["code"], -32601);
+    let mismatch = daemon.call(
+        "mark",
+        json!({"context": {"overrides": [["time.timezone", "UTC"]]}}),
+    );
+    assert_eq!(mismatch["error"]["code"], -32001);
+
+    // The CLI carries daemon failures through with the in-process message and exit code.
+    let output = run_without_git(&repo, &["locate", "7.0.0"]);
+    assert_eq!(output.status.code(), Some(4));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "error: no commit reachable from HEAD resolves to 7.0.0\n"
+    );
+}
+
+#[test]
+fn stale_daemon_advertisements_fall_back_and_are_replaced() {
+    let repo = seeded_repo();
+    let expected = stdout(&repo.run_tide(&["mark", "--local-only"]));
+
+    let daemon = ServedRepo::start(&repo);
+    let socket = daemon.socket.clone();
+    drop(daemon);
+    assert!(
+        Path::new(&socket).exists(),
+        "killed daemon leaves its socket"
+    );
+    assert_eq!(stdout(&repo.run_tide(&["mark", "--local-only"])), expected);
+
+    let restarted = ServedRepo::start(&repo);
+    assert_eq!(
+        stdout(&run_without_git(&repo, &["mark", "--local-only"])),
+        expected
+    );
+    // The socket is created privately and moved into place; no staging directory is left.
+    let mode = std::os::unix::fs::PermissionsExt::mode(
+        &std::fs::metadata(&socket).expect("socket").permissions(),
+    );
+    assert_eq!(mode & 0o777, 0o600);
+    let leftovers: Vec<_> = std::fs::read_dir(repo.root().join(".git"))
+        .expect("git dir")
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with(".tide-bind")
+        })
+        .collect();
+    assert!(leftovers.is_empty(), "{leftovers:?}");
+    drop(restarted);
+}
+
+/// Stand-in daemon that records each request line and answers with a context mismatch.
+fn fake_daemon(repo: &RepoFixture) -> (std::os::unix::net::UnixListener, PathBuf) {
+    use std::os::unix::fs::PermissionsExt;
+
+    let socket = repo.root().join(".git").join("fake.sock");
+    let listener = std::os::unix::net::UnixListener::bind(&socket).expect("bind fake daemon");
+    std::fs::set_permissions(&socket, std::fs::Permissions::from_mode(0o600)).unwrap();
+    let discovery = repo.root().join(".git").join("tidemark-daemon");
+    std::fs::write(&discovery, format!("{}\n", socket.display())).unwrap();
+    std::fs::set_permissions(&discovery, std::fs::Permissions::from_mode(0o644)).unwrap();
+    (listener, discovery)
+}
+
+#[test]
+fn clients_send_only_resolution_env_and_only_to_private_daemons() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let repo = seeded_repo();
+    let expected = stdout(&repo.run_tide(&["--no-daemon", "mark", "--local-only"]));
+    let (listener, discovery) = fake_daemon(&repo);
+    let recorder = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().expect("client connects");
+        let mut writer = stream.try_clone().unwrap();
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line).unwrap();
+        let reply = json!({"jsonrpc": "2.0", "id": 1, "error": {"code": -32001, "message": "no"}});
+        writer.write_all(format!("{reply}\n").as_bytes()).unwrap();
+        (line, listener)
+    });
+    let env = [("GITHUB_REF_TYPE", "branch"), ("DEPLOY_TOKEN", "s3cret")];
+    let output = repo.run_tide_with_env(&["mark", "--local-only"], &env);
+    assert_eq!(stdout(&output), expected);
+    let (request, listener) = recorder.join().expect("fake daemon");
+    let request: Value = serde_json::from_str(request.as_str()).expect("request json");
+    let sent = &request["params"]["context"]["env"];
+    assert_eq!(sent["GITHUB_REF_TYPE"], "branch");
+    assert!(sent.get("DEPLOY_TOKEN").is_none(), "{sent}");
+    assert!(sent.get("PATH").is_none(), "{sent}");
+
+    // A discovery file others can write is ignored; the client never connects.
+    std::fs::set_permissions(&discovery, std::fs::Permissions::from_mode(0o666)).unwrap();
+    assert_eq!(stdout(&repo.run_tide(&["mark", "--local-only"])), expected);
+    listener.set_nonblocking(true).unwrap();
+    assert_eq!(
+        listener.accept().map(|_| ()).unwrap_err().kind(),
+        std::io::ErrorKind::WouldBlock
+    );
+}
+
+#[test]
+fn clients_in_another_local_timezone_resolve_in_process() {
+    let repo = RepoFixture::init();
+    repo.write_file_and_commit("app.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    // 20:00 UTC is already the next morning at UTC+9.
+    repo.write_file_and_commit("app.txt", "b\n", "c2", "2024-01-01T20:00:00+00:00");
+    std::fs::create_dir_all(no_git_path(&repo)).expect("create empty PATH dir");
+    let utc = [("TZ", "UTC0")];
+    let tokyo = [("TZ", "JST-9")];
+    let in_process = |env: &[(&str, &str)]| {
+        stdout(&repo.run_tide_with_env(&["--no-daemon", "mark", "--local-only"], env))
+    };
+    let (utc_mark, tokyo_mark) = (in_process(&utc), in_process(&tokyo));
+    assert_ne!(utc_mark, tokyo_mark);
+
+    let _daemon = ServedRepo::start_with_env(&repo, &utc);
+    let path = no_git_path(&repo);
+    let path = path.to_str().expect("utf-8 path");
+    let served = repo.run_tide_with_env(&["mark", "--local-only"], &[utc[0], ("PATH", path)]);
+    assert_eq!(stdout(&served), utc_mark);
+    let fallback = repo.run_tide_with_env(&["mark", "--local-only"], &tokyo);
+    assert_eq!(stdout(&fallback), tokyo_mark);
+}
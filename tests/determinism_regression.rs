@@ -5,7 +5,7 @@
 //! Description: Determinism regression tests for stable repeated mark outputs.
 //!
 //! Responsibility:
-//! - Verify byte-equal results and cache creation under repeated local-only resolution.
+//! - Verify byte-equal results, cache creation, and incremental-index agreement under repeated local-only resolution.
 //!
 //! Architectural Position:
 //! - Integration verification for deterministic resolver guarantees.
@@ -64,3 +64,108 @@ fn repeated_runs_are_byte_equal() {
         "cache directory should be created"
     );
 }
+
+#[test]
+fn incremental_ancestry_index_matches_full_history_walk() {
+    let repo = RepoFixture::init();
+    repo.write_config("[time]\ntimezone = \"UTC\"\n");
+
+    let anchor = repo.write_file_and_commit("x.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    let m1 = repo.write_file_and_commit("main.txt", "1\n", "m1", "2024-01-02T09:00:00+00:00");
+    let m2 = repo.write_file_and_commit("main.txt", "2\n", "m2", "2024-01-02T11:00:00+00:00");
+    repo.git(&["checkout", "-q", "-b", "side", anchor.as_str()], &[]);
+    let s1 = repo.write_file_and_commit("side.txt", "1\n", "s1", "2024-01-02T10:00:00+00:00");
+    let s2 = repo.write_file_and_commit("side.txt", "2\n", "s2", "2024-01-02T12:00:00+00:00");
+    repo.git(&["checkout", "-q", "main"], &[]);
+    let merge_date = "2024-01-02T13:00:00+00:00";
+    repo.git(
+        &["merge", "--no-ff", "-m", "merge side", "side"],
+        &[
+            ("GIT_AUTHOR_DATE", merge_date),
+            ("GIT_COMMITTER_DATE", merge_date),
+        ],
+    );
+    let merge = repo.rev_parse("HEAD");
+    // Clock skew: dated the day before its parent.
+    let skewed = repo.write_file_and_commit("main.txt", "3\n", "k", "2024-01-01T23:00:00+00:00");
+    let after = repo.write_file_and_commit("main.txt", "4\n", "l", "2024-01-02T14:00:00+00:00");
+
+    let explain_at = |rev: &str, extra: &[&str]| {
+        repo.git(&["checkout", "-q", "--detach", rev], &[]);
+        let mut args = extra.to_vec();
+        args.extend(["mark", "--local-only", "--explain"]);
+        let out = repo.run_tide(&args);
+        assert!(
+            out.status.success(),
+            "stderr={}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+        String::from_utf8_lossy(&out.stdout).to_string()
+    };
+    let version = |explain: &str| explain.lines().next().unwrap_or_default().to_string();
+
+    let mut walked = Vec::new();
+    for rev in [&m1, &m2, &s1, &s2, &merge, &skewed, &after] {
+        let indexed = explain_at(rev, &[]);
+        let full = explain_at(rev, &["-c", "cache.enabled=false"]);
+        assert_eq!(version(&indexed), version(&full), "{rev}");
+        assert!(full.contains("\nancestry_index=unused\n"));
+        walked.push(
+            indexed
+                .lines()
+                .find_map(|line| line.strip_prefix("ancestry_index_walked="))
+                .map(str::to_string),
+        );
+    }
+    // Each target past the first walks only commits the index has not seen.
+    assert!(
+        walked.iter().all(|count| count.as_deref() == Some("1")),
+        "{walked:?}"
+    );
+    assert_eq!(version(&explain_at(&merge, &[])), "version=1.1.5");
+
+    let cleared = repo.run_tide(&["cache", "clear", "--namespace", "mark"]);
+    assert!(cleared.status.success());
+    assert!(explain_at(&m2, &[]).contains("\nancestry_index=hit\n"));
+}
+
+#[test]
+fn branches_forking_below_indexed_tips_join_the_ancestry_index() {
+    let repo = RepoFixture::init();
+    repo.write_config("[time]\ntimezone = \"UTC\"\n");
+
+    repo.write_file_and_commit("x.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    let fork = repo.write_file_and_commit("x.txt", "b\n", "d", "2024-01-02T00:30:00+00:00");
+    let tip = repo.write_file_and_commit("x.txt", "c\n", "c2", "2024-01-02T02:00:00+00:00");
+    repo.git(&["checkout", "-q", "-b", "side", fork.as_str()], &[]);
+    repo.write_file_and_commit("side.txt", "1\n", "s1", "2024-01-02T01:00:00+00:00");
+    repo.git(&["checkout", "-q", "main"], &[]);
+    let merge_date = "2024-01-02T03:00:00+00:00";
+    repo.git(
+        &["merge", "--no-ff", "-m", "merge side", "side"],
+        &[
+            ("GIT_AUTHOR_DATE", merge_date),
+            ("GIT_COMMITTER_DATE", merge_date),
+        ],
+    );
+    let merge = repo.rev_parse("HEAD");
+
+    let mark_at = |rev: &str, extra: &[&str]| {
+        repo.git(&["checkout", "-q", "--detach", rev], &[]);
+        let mut args = extra.to_vec();
+        args.extend(["mark", "--local-only"]);
+        let out = repo.run_tide(&args);
+        assert!(
+            out.status.success(),
+            "stderr={}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+        String::from_utf8_lossy(&out.stdout).to_string()
+    };
+    // Index up to `c2` first; `s1` forks below that tip and must still count for the merge.
+    assert_eq!(mark_at(&tip, &[]), "1.1.2\n");
+    assert_eq!(mark_at(&merge, &["-c", "cache.enabled=false"]), "1.1.4\n");
+    assert_eq!(mark_at(&merge, &[]), "1.1.4\n");
+}
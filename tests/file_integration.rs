@@ -5,7 +5,7 @@
 //! Description: Integration tests for file-path based coordinate resolution.
 //!
 //! Responsibility:
-//! - Validate last-modifying-commit lookup and file-history failure behavior.
+//! - Validate last-modifying-commit lookup, file-history failure behavior, and batch resolution of directories, globs, and stdin paths.
 //!
 //! Architectural Position:
 //! - End-to-end verification of file resolver semantics.
@@ -46,3 +46,256 @@ fn file_without_history_returns_data_error() {
     let output = repo.run_tide(&["file", "missing.txt"]);
     assert_eq!(output.status.code(), Some(4));
 }
+
+fn stdout(output: &std::process::Output) -> String {
+    assert!(
+        output.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+/// One `path\tcoordinate\tcommit` row built from a single-file invocation and `git log`.
+fn single_file_row(repo: &RepoFixture, path: &str) -> String {
+    single_file_row_with(repo, path, true)
+}
+
+fn single_file_row_with(repo: &RepoFixture, path: &str, follow_renames: bool) -> String {
+    let follow = format!("output.follow_renames={follow_renames}");
+    let coordinate = stdout(&repo.run_tide(&["-c", follow.as_str(), "file", path, "--local-only"]));
+    let mut log = std::process::Command::new("git");
+    log.current_dir(repo.root())
+        .args(["log", "-n1", "--format=%H"]);
+    if follow_renames {
+        log.arg("--follow");
+    }
+    let commit = log.args(["--", path]).output().expect("run git log");
+    format!(
+        "{path}\t{}\t{}\n",
+        coordinate.trim(),
+        String::from_utf8_lossy(&commit.stdout).trim()
+    )
+}
+
+fn docs_repo() -> RepoFixture {
+    let repo = RepoFixture::init();
+    repo.write_file_and_commit("README.md", "r\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v2", "release 2", "2024-01-01T00:00:00+00:00");
+    repo.write_file_and_commit("docs/intro.md", "i\n", "c2", "2024-01-02T01:00:00+00:00");
+    repo.write_file_and_commit(
+        "docs/guide/setup.md",
+        "s\n",
+        "c3",
+        "2024-01-02T02:00:00+00:00",
+    );
+    repo.write_file_and_commit("src/lib.rs", "l\n", "c4", "2024-01-03T01:00:00+00:00");
+    repo.write_file_and_commit("docs/intro.md", "i2\n", "c5", "2024-01-03T02:00:00+00:00");
+    repo
+}
+
+#[test]
+fn file_batch_expands_directories_and_globs_in_path_order() {
+    let repo = docs_repo();
+    let expected: String = ["README.md", "docs/guide/setup.md", "docs/intro.md"]
+        .iter()
+        .map(|path| single_file_row(&repo, path))
+        .collect();
+
+    let table = stdout(&repo.run_tide(&["file", "docs", "*.md", "--local-only"]));
+    assert_eq!(table, expected);
+    // `*` stays within a directory; `**` crosses directories.
+    let nested = stdout(&repo.run_tide(&["file", "docs/*.md", "--local-only"]));
+    assert_eq!(nested, single_file_row(&repo, "docs/intro.md"));
+    let deep = stdout(&repo.run_tide(&["file", "**/*.md", "--local-only"]));
+    assert_eq!(deep, expected);
+
+    let json = stdout(&repo.run_tide(&["file", "src", "--json", "--local-only"]));
+    let rows: serde_json::Value = serde_json::from_str(json.as_str()).expect("json output");
+    assert_eq!(rows.as_array().map(Vec::len), Some(1));
+    assert_eq!(rows[0]["path"], "src/lib.rs");
+    assert_eq!(rows[0]["anchor_tag"], "v2");
+    assert_eq!(rows[0]["commit"], repo.rev_parse("HEAD~1"));
+    assert_eq!(
+        format!("{}\n", rows[0]["coordinate"].as_str().unwrap()),
+        stdout(&repo.run_tide(&["file", "src/lib.rs", "--local-only"]))
+    );
+
+    // Patterns matching nothing are empty; a named file without history is an error.
+    assert_eq!(
+        stdout(&repo.run_tide(&["file", "*.txt", "--local-only"])),
+        ""
+    );
+    let missing = repo.run_tide(&["file", "docs", "missing.md", "--local-only"]);
+    assert_eq!(missing.status.code(), Some(4));
+}
+
+#[test]
+fn file_batch_reads_paths_from_stdin() {
+    let repo = docs_repo();
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin!("tide"))
+        .args(["file", "--stdin", "README.md", "--local-only"])
+        .env("XDG_CONFIG_HOME", repo.xdg_config_home())
+        .current_dir(repo.root())
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .expect("spawn tide file --stdin");
+    std::io::Write::write_all(
+        child.stdin.as_mut().expect("stdin"),
+        b"src/lib.rs\n\n./docs/intro.md\nREADME.md\n",
+    )
+    .expect("write stdin");
+    let output = child.wait_with_output().expect("wait for tide");
+
+    let expected: String = ["README.md", "docs/intro.md", "src/lib.rs"]
+        .iter()
+        .map(|path| single_file_row(&repo, path))
+        .collect();
+    assert_eq!(stdout(&output), expected);
+}
+
+#[test]
+fn file_batch_agrees_with_single_file_lookup_across_merges() {
+    let repo = RepoFixture::init();
+    repo.write_file_and_commit("a.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.write_file_and_commit("b.txt", "b\n", "c2", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    repo.git(&["checkout", "-q", "-b", "side"], &[]);
+    repo.write_file_and_commit("a.txt", "side\n", "side", "2024-01-03T00:00:00+00:00");
+    repo.git(&["checkout", "-q", "-"], &[]);
+    repo.write_file_and_commit("a.txt", "main\n", "main", "2024-01-02T00:00:00+00:00");
+    let date = "2024-01-04T00:00:00+00:00";
+    repo.git(
+        &["merge", "-q", "-s", "ours", "side", "-m", "merge"],
+        &[("GIT_AUTHOR_DATE", date), ("GIT_COMMITTER_DATE", date)],
+    );
+    repo.write_file_and_commit("c.txt", "c\n", "c3", "2024-01-05T00:00:00+00:00");
+
+    let expected: String = ["a.txt", "b.txt", "c.txt"]
+        .iter()
+        .map(|path| single_file_row(&repo, path))
+        .collect();
+    assert_eq!(
+        stdout(&repo.run_tide(&["file", ".", "--local-only"])),
+        expected
+    );
+    // Without rename following, git simplifies history per path.
+    let unfollowed = stdout(&repo.run_tide(&[
+        "-c",
+        "output.follow_renames=false",
+        "file",
+        "a.txt",
+        "b.txt",
+        "--local-only",
+    ]));
+    assert_ne!(
+        single_file_row_with(&repo, "a.txt", false),
+        single_file_row(&repo, "a.txt")
+    );
+    assert_eq!(
+        unfollowed,
+        single_file_row_with(&repo, "a.txt", false) + &single_file_row_with(&repo, "b.txt", false)
+    );
+}
+
+#[test]
+fn file_lookup_judges_merges_against_every_parent() {
+    let repo = RepoFixture::init();
+    for path in ["a.txt", "b.txt"] {
+        std::fs::write(repo.root().join(path), "root\n").expect("write file");
+    }
+    repo.write_file_and_commit("e.txt", "root\n", "root", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    repo.git(&["checkout", "-q", "-b", "side"], &[]);
+    std::fs::write(repo.root().join("e.txt"), "side\n").expect("write file");
+    let side = repo.write_file_and_commit("a.txt", "side\n", "side", "2024-01-02T00:00:00+00:00");
+    repo.git(&["checkout", "-q", "main"], &[]);
+    repo.write_file_and_commit("b.txt", "main\n", "main", "2024-01-03T00:00:00+00:00");
+    // An evil merge: b.txt and e.txt end up differing from both parents.
+    let date = "2024-01-04T00:00:00+00:00";
+    let dates = [("GIT_AUTHOR_DATE", date), ("GIT_COMMITTER_DATE", date)];
+    repo.git(&["merge", "-q", "--no-commit", "side"], &dates);
+    for path in ["b.txt", "e.txt"] {
+        std::fs::write(repo.root().join(path), "merged\n").expect("write file");
+    }
+    repo.git(&["commit", "-q", "-a", "-m", "evil merge"], &dates);
+    let evil = repo.rev_parse("HEAD");
+    repo.git(&["checkout", "-q", "-b", "other"], &[]);
+    let other =
+        repo.write_file_and_commit("a.txt", "other\n", "other", "2024-01-05T00:00:00+00:00");
+    repo.git(&["checkout", "-q", "main"], &[]);
+    let date = "2024-01-06T00:00:00+00:00";
+    repo.git(
+        &["merge", "-q", "-s", "ours", "other", "-m", "ours merge"],
+        &[("GIT_AUTHOR_DATE", date), ("GIT_COMMITTER_DATE", date)],
+    );
+
+    let commits = |follow_renames: bool| -> Vec<String> {
+        let follow = format!("output.follow_renames={follow_renames}");
+        let batch = stdout(&repo.run_tide(&[
+            "-c",
+            follow.as_str(),
+            "file",
+            "a.txt",
+            "b.txt",
+            "e.txt",
+            "--local-only",
+        ]));
+        let single: String = ["a.txt", "b.txt", "e.txt"]
+            .iter()
+            .map(|path| {
+                let coordinate =
+                    stdout(&repo.run_tide(&["-c", follow.as_str(), "file", path, "--local-only"]));
+                format!("{path}\t{}\t", coordinate.trim())
+            })
+            .collect();
+        let rows: Vec<&str> = batch.lines().collect();
+        let prefixes: String = rows
+            .iter()
+            .map(|row| &row[..=row.rfind('\t').expect("commit column")])
+            .collect();
+        assert_eq!(prefixes, single);
+        rows.iter()
+            .map(|row| row.rsplit('\t').next().unwrap_or_default().to_string())
+            .collect()
+    };
+
+    // Without rename following, answers match git's per-path history simplification.
+    let simplified: Vec<String> = ["a.txt", "b.txt", "e.txt"]
+        .iter()
+        .map(|path| {
+            let output = std::process::Command::new("git")
+                .current_dir(repo.root())
+                .args(["log", "-n1", "--format=%H", "--", path])
+                .output()
+                .expect("run git log");
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        })
+        .collect();
+    assert_eq!(simplified, [side, evil.clone(), evil.clone()]);
+    assert_eq!(commits(false), simplified);
+    // With it, the newest commit changing the path wins, including merges that change it
+    // against every parent, while the `-s ours` merge changes nothing.
+    assert_eq!(commits(true), [other, evil.clone(), evil]);
+}
+
+#[test]
+fn file_batch_lists_remote_tags_once_for_all_commits() {
+    let repo = docs_repo();
+    let remote = repo.root().join(".git").join("upstream.git");
+    let remote = remote.to_str().expect("utf-8 path");
+    repo.git(&["clone", "--quiet", "--bare", ".", remote], &[]);
+    repo.git(&["remote", "add", "origin", remote], &[]);
+    let expected = stdout(&repo.run_tide(&["file", "docs", "src", "README.md", "--local-only"]));
+
+    let trace = repo.root().join(".git").join("trace.log");
+    let output = repo.run_tide_with_env(
+        &["file", "docs", "src", "README.md", "--require-remote"],
+        &[("GIT_TRACE", trace.to_str().expect("utf-8 path"))],
+    );
+    assert_eq!(stdout(&output), expected);
+    let trace = std::fs::read_to_string(trace).expect("read git trace");
+    // Four files last changed in four commits still share one tag listing.
+    assert_eq!(trace.matches("git ls-remote").count(), 1, "{trace}");
+}
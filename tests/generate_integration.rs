@@ -0,0 +1,71 @@
+//! TideMark
+//! ========
+//!
+//! File: tests/generate_integration.rs
+//! Description: Integration tests for generated source-code version constants.
+//!
+//! Responsibility:
+//! - Validate byte-stable rendering, write-on-change behavior, and dirty detection.
+//!
+//! Architectural Position:
+//! - End-to-end verification of the `tide generate` operations adapter.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+mod common;
+
+use std::fs;
+
+use common::RepoFixture;
+
+#[test]
+fn generate_is_byte_stable_and_rewrites_only_on_change() {
+    let repo = RepoFixture::init();
+
+    repo.write_file_and_commit("a.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    let head = repo.write_file_and_commit("a.txt", "b\n", "c2", "2024-01-01T01:00:00+00:00");
+
+    let args = [
+        "generate",
+        "--lang",
+        "rust",
+        "--out",
+        "gen/version.rs",
+        "--local-only",
+    ];
+    let first = repo.run_tide(&args);
+    assert!(
+        first.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&first.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&first.stdout),
+        "gen/version.rs\tupdated\n"
+    );
+    let path = repo.root().join("gen/version.rs");
+    let content = fs::read_to_string(&path).unwrap();
+    assert!(content.contains("pub const TIDEMARK_VERSION: &str = \"1.0.1\";\n"));
+    assert!(content.contains(&format!("pub const TIDEMARK_COMMIT: &str = \"{head}\";\n")));
+    assert!(content.contains("pub const TIDEMARK_TIMESTAMP: i64 = 1704070800;\n"));
+    assert!(content.contains("pub const TIDEMARK_DIRTY: bool = false;\n"));
+    let mtime = fs::metadata(&path).unwrap().modified().unwrap();
+
+    let second = repo.run_tide(&args);
+    assert_eq!(
+        String::from_utf8_lossy(&second.stdout),
+        "gen/version.rs\tunchanged\n"
+    );
+    assert_eq!(fs::metadata(&path).unwrap().modified().unwrap(), mtime);
+    assert_eq!(fs::read_to_string(&path).unwrap(), content);
+
+    fs::write(repo.root().join("a.txt"), "dirty\n").unwrap();
+    let dirty = repo.run_tide(&["generate", "--lang", "python", "--local-only"]);
+    assert!(dirty.status.success());
+    let text = String::from_utf8_lossy(&dirty.stdout);
+    assert!(text.contains("VERSION = \"1.0.1\"\n"));
+    assert!(text.contains("DIRTY = True\n"));
+}
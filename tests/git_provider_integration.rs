@@ -0,0 +1,265 @@
+//! TideMark
+//! ========
+//!
+//! File: tests/git_provider_integration.rs
+//! Description: Integration tests for the `GitProvider` default bulk queries.
+//!
+//! Responsibility:
+//! - Check that backends implementing only the pairwise primitives answer bulk queries like `GitCli`.
+//!
+//! Architectural Position:
+//! - Contract verification for third-party `GitProvider` implementations.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+mod common;
+
+use std::{
+    collections::HashSet,
+    fmt::Write as _,
+    io::Write as _,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use common::RepoFixture;
+use tidemark::{CommitInfo, CommitNode, DeepenStep, GitCli, GitProvider, TagRef, TideResult};
+
+/// A backend that implements only the required methods, so every defaulted bulk query takes its
+/// default.
+struct Primitives(GitCli);
+
+impl GitProvider for Primitives {
+    fn repo_root(&self) -> &Path {
+        self.0.repo_root()
+    }
+    fn git_dir(&self) -> TideResult<PathBuf> {
+        self.0.git_dir()
+    }
+    fn head_commit(&self) -> TideResult<CommitInfo> {
+        self.0.head_commit()
+    }
+    fn resolve_commit(&self, rev: &str) -> TideResult<CommitInfo> {
+        self.0.resolve_commit(rev)
+    }
+    fn commit_exists(&self, rev: &str) -> TideResult<bool> {
+        self.0.commit_exists(rev)
+    }
+    fn list_local_tags(&self, prefix: &str) -> TideResult<Vec<TagRef>> {
+        self.0.list_local_tags(prefix)
+    }
+    fn list_remote_tags(&self, remote: &str, prefix: &str) -> TideResult<Vec<TagRef>> {
+        self.0.list_remote_tags(remote, prefix)
+    }
+    fn fetch_remote_tags(&self, remote: &str, prefix: &str) -> TideResult<Vec<TagRef>> {
+        self.0.fetch_remote_tags(remote, prefix)
+    }
+    fn is_ancestor(&self, ancestor: &str, descendant: &str) -> TideResult<bool> {
+        self.0.is_ancestor(ancestor, descendant)
+    }
+    fn commit_distance(&self, ancestor: &str, descendant: &str) -> TideResult<u32> {
+        self.0.commit_distance(ancestor, descendant)
+    }
+    fn ancestry_path_commits(
+        &self,
+        ancestor: &str,
+        descendant: &str,
+    ) -> TideResult<Vec<CommitInfo>> {
+        self.0.ancestry_path_commits(ancestor, descendant)
+    }
+    fn first_parent_distance(&self, ancestor: &str, descendant: &str) -> TideResult<u32> {
+        self.0.first_parent_distance(ancestor, descendant)
+    }
+    fn first_parent_commits(
+        &self,
+        ancestor: &str,
+        descendant: &str,
+    ) -> TideResult<Vec<CommitInfo>> {
+        self.0.first_parent_commits(ancestor, descendant)
+    }
+    fn last_modifying_commit(&self, path: &Path, follow_renames: bool) -> TideResult<CommitInfo> {
+        self.0.last_modifying_commit(path, follow_renames)
+    }
+    fn current_branch(&self) -> TideResult<Option<String>> {
+        self.0.current_branch()
+    }
+    fn is_dirty(&self) -> TideResult<bool> {
+        self.0.is_dirty()
+    }
+    fn root_commits(&self, rev: &str) -> TideResult<Vec<CommitInfo>> {
+        self.0.root_commits(rev)
+    }
+    fn first_parent_root(&self, rev: &str) -> TideResult<CommitInfo> {
+        self.0.first_parent_root(rev)
+    }
+    fn shallow_boundaries(&self) -> TideResult<Vec<String>> {
+        self.0.shallow_boundaries()
+    }
+    fn deepen(&self, remote: &str, step: &DeepenStep) -> TideResult<()> {
+        self.0.deepen(remote, step)
+    }
+    fn tracked_files(&self, pathspecs: &[String]) -> TideResult<Vec<PathBuf>> {
+        self.0.tracked_files(pathspecs)
+    }
+}
+
+#[test]
+fn default_bulk_queries_match_the_git_cli_backend() {
+    let repo = RepoFixture::init();
+    let c1 = repo.write_file_and_commit("a.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.write_file_and_commit("docs/intro.md", "i\n", "docs", "2024-01-01T01:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T01:00:00+00:00");
+    repo.git(&["checkout", "-q", "-b", "side"], &[]);
+    let s1 = repo.write_file_and_commit("b.txt", "b\n", "s1", "2024-01-02T00:00:00+00:00");
+    repo.git(&["checkout", "-q", "main"], &[]);
+    let c2 = repo.write_file_and_commit("a.txt", "a2\n", "c2", "2024-01-02T01:00:00+00:00");
+    let merge_date = "2024-01-02T02:00:00+00:00";
+    repo.git(
+        &["merge", "-q", "--no-ff", "side", "-m", "merge side"],
+        &[
+            ("GIT_AUTHOR_DATE", merge_date),
+            ("GIT_COMMITTER_DATE", merge_date),
+        ],
+    );
+    repo.write_file_and_commit("docs/guide.md", "g\n", "c3", "2024-01-02T03:00:00+00:00");
+    repo.git(&["checkout", "-q", "-b", "scratch"], &[]);
+    let dangling = repo.write_file_and_commit("c.txt", "c\n", "gone", "2024-01-03T00:00:00+00:00");
+    repo.git(&["checkout", "-q", "main"], &[]);
+    repo.git(&["branch", "-q", "-D", "scratch", "side"], &[]);
+    let head = repo.rev_parse("HEAD");
+
+    let cli = GitCli::discover(repo.root()).expect("discover repo");
+    let primitives = Primitives(cli.clone());
+
+    let candidates = [&c1, &s1, &c2, &dangling, &"0".repeat(40), &c1].map(|id| id.to_string());
+    assert_eq!(
+        primitives
+            .reachable_with_distances(head.as_str(), &candidates)
+            .unwrap(),
+        cli.reachable_with_distances(head.as_str(), &candidates)
+            .unwrap()
+    );
+
+    let paths = ["a.txt", "b.txt", "docs/guide.md", "missing.txt"].map(PathBuf::from);
+    for follow_renames in [false, true] {
+        assert_eq!(
+            primitives
+                .last_modifying_commits(&paths, follow_renames)
+                .unwrap(),
+            cli.last_modifying_commits(&paths, follow_renames).unwrap()
+        );
+    }
+
+    let commits = [&c1, &s1, &dangling].map(|id| id.to_string());
+    assert_eq!(
+        primitives.reachable_from_refs(&commits).unwrap(),
+        cli.reachable_from_refs(&commits).unwrap()
+    );
+
+    for known in [vec![], vec![c2.clone()]] {
+        let expected = cli
+            .ancestry_path_graph(c1.as_str(), head.as_str(), &known)
+            .unwrap();
+        let derived = primitives
+            .ancestry_path_graph(c1.as_str(), head.as_str(), &known)
+            .unwrap();
+        let ids = |nodes: &[CommitNode]| -> HashSet<String> {
+            nodes.iter().map(|node| node.commit.id.clone()).collect()
+        };
+        assert_eq!(ids(&derived), ids(&expected));
+        // Parents come first, and every direct parent on the path is listed.
+        let mut seen = HashSet::new();
+        for node in &derived {
+            let on_path: Vec<&String> = node
+                .parents
+                .iter()
+                .filter(|parent| ids(&derived).contains(*parent))
+                .collect();
+            assert!(on_path.iter().all(|parent| seen.contains(*parent)));
+            let direct = expected
+                .iter()
+                .find(|other| other.commit.id == node.commit.id)
+                .expect("same commits");
+            for parent in direct
+                .parents
+                .iter()
+                .filter(|parent| ids(&expected).contains(*parent))
+            {
+                assert!(
+                    node.parents.contains(parent),
+                    "{} misses {parent}",
+                    node.commit.id
+                );
+            }
+            seen.insert(node.commit.id.clone());
+        }
+    }
+}
+
+/// `commits` empty commits on `main`, imported in one `git fast-import` run.
+fn import_linear_history(repo: &RepoFixture, commits: usize) {
+    let mut stream = String::new();
+    for mark in 1..=commits {
+        let _ = write!(
+            stream,
+            "commit refs/heads/main\nmark :{mark}\ncommitter TideMark Test <tidemark@test.local> {} +0000\ndata 0\n",
+            1_600_000_000 + mark * 60
+        );
+        if mark > 1 {
+            let _ = writeln!(stream, "from :{}", mark - 1);
+        }
+    }
+    let mut child = Command::new("git")
+        .args(["fast-import", "--quiet"])
+        .current_dir(repo.root())
+        .stdin(Stdio::piped())
+        .spawn()
+        .expect("spawn fast-import");
+    child
+        .stdin
+        .take()
+        .expect("fast-import stdin")
+        .write_all(stream.as_bytes())
+        .expect("write fast-import stream");
+    assert!(child.wait().expect("fast-import").success());
+}
+
+#[test]
+fn bulk_distances_above_deep_history_match_the_primitives() {
+    let repo = RepoFixture::init();
+    import_linear_history(&repo, 1_100);
+    let oldest = repo.write_file_and_commit("a.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.git(&["checkout", "-q", "-b", "side"], &[]);
+    let s1 = repo.write_file_and_commit("b.txt", "b\n", "s1", "2024-01-02T00:00:00+00:00");
+    repo.git(&["checkout", "-q", "main"], &[]);
+    let c2 = repo.write_file_and_commit("a.txt", "a2\n", "c2", "2024-01-02T01:00:00+00:00");
+    let merge_date = "2024-01-02T02:00:00+00:00";
+    repo.git(
+        &["merge", "-q", "--no-ff", "side", "-m", "merge side"],
+        &[
+            ("GIT_AUTHOR_DATE", merge_date),
+            ("GIT_COMMITTER_DATE", merge_date),
+        ],
+    );
+    let head = repo.rev_parse("HEAD");
+
+    let cli = GitCli::discover(repo.root()).expect("discover repo");
+    let primitives = Primitives(cli.clone());
+    // Oldest first, as release candidates arrive: the walk stops below their merge base.
+    for candidates in [
+        vec![&oldest, &s1, &c2],
+        vec![&s1, &c2],
+        vec![&oldest, &"0".repeat(40)],
+    ] {
+        let candidates: Vec<String> = candidates.into_iter().cloned().collect();
+        assert_eq!(
+            primitives
+                .reachable_with_distances(head.as_str(), &candidates)
+                .unwrap(),
+            cli.reachable_with_distances(head.as_str(), &candidates)
+                .unwrap()
+        );
+    }
+}
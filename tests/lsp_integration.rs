@@ -0,0 +1,210 @@
+//! TideMark
+//! ========
+//!
+//! File: tests/lsp_integration.rs
+//! Description: Integration tests for the `tide lsp` language server.
+//!
+//! Responsibility:
+//! - Drive a scripted LSP session over stdio and validate lifecycle, hover, and code-lens answers.
+//!
+//! Architectural Position:
+//! - End-to-end verification of the editor front end against `tide file`.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+mod common;
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    path::Path,
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+};
+
+use assert_cmd::cargo;
+use common::RepoFixture;
+use serde_json::{Value, json};
+
+/// Scripted editor: writes framed messages to `tide lsp` and reads framed replies.
+struct LspClient {
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl LspClient {
+    /// Start `tide lsp` in `repo`.
+    fn start(repo: &RepoFixture) -> (Child, Self) {
+        let mut child = Command::new(cargo::cargo_bin!("tide"))
+            .arg("lsp")
+            .env("XDG_CONFIG_HOME", repo.xdg_config_home())
+            .current_dir(repo.root())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("spawn tide lsp");
+        let client = Self {
+            stdin: child.stdin.take().expect("lsp stdin"),
+            stdout: BufReader::new(child.stdout.take().expect("lsp stdout")),
+        };
+        (child, client)
+    }
+
+    fn send(&mut self, message: Value) {
+        let body = message.to_string();
+        write!(self.stdin, "Content-Length: {}\r\n\r\n{body}", body.len()).expect("send message");
+        self.stdin.flush().expect("flush message");
+    }
+
+    fn request(&mut self, id: u64, method: &str, params: Value) -> Value {
+        self.send(json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params}));
+        let response = self.receive();
+        assert_eq!(response["id"], id, "{response}");
+        response
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({"jsonrpc": "2.0", "method": method, "params": params}));
+    }
+
+    fn receive(&mut self) -> Value {
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            self.stdout.read_line(&mut line).expect("read header");
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some(value) = line.strip_prefix("Content-Length: ") {
+                length = value.parse().expect("content length");
+            }
+        }
+        let mut body = vec![0u8; length];
+        self.stdout.read_exact(&mut body).expect("read body");
+        serde_json::from_slice(&body).expect("response json")
+    }
+}
+
+fn document(path: &Path) -> Value {
+    json!({"textDocument": {"uri": format!("file://{}", path.display())}})
+}
+
+#[test]
+fn scripted_session_shows_file_coordinates_in_hover_and_code_lens() {
+    let repo = RepoFixture::init();
+    repo.write_file_and_commit("app.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    let c2 = repo.write_file_and_commit("app.txt", "b\n", "c2", "2024-01-02T01:00:00+00:00");
+    repo.write_file_and_commit("lib.txt", "b\n", "c3", "2024-01-02T02:00:00+00:00");
+    std::fs::write(repo.root().join("scratch.txt"), "untracked\n").expect("write scratch");
+    let output = repo.run_tide(&["file", "app.txt", "--local-only"]);
+    assert!(output.status.success());
+    let coordinate = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let (mut child, mut client) = LspClient::start(&repo);
+    let app = document(&repo.root().join("app.txt"));
+
+    let early = client.request(1, "textDocument/hover", app.clone());
+    assert_eq!(early["error"]["code"], -32002);
+
+    let init = client.request(2, "initialize", json!({"capabilities": {}}));
+    assert_eq!(init["result"]["capabilities"]["hoverProvider"], true);
+    assert!(init["result"]["capabilities"]["codeLensProvider"].is_object());
+    client.notify("initialized", json!({}));
+    client.notify(
+        "textDocument/didOpen",
+        json!({"textDocument": {
+            "uri": app["textDocument"]["uri"],
+            "languageId": "plaintext",
+            "version": 1,
+            "text": "b\n",
+        }}),
+    );
+
+    let hover = client.request(3, "textDocument/hover", app.clone());
+    let text = hover["result"]["contents"]["value"]
+        .as_str()
+        .expect("hover markdown");
+    assert!(
+        text.contains(format!("**{coordinate}**").as_str()),
+        "{text}"
+    );
+    assert!(text.contains(&c2[..7]), "{text}");
+    assert!(text.contains("`v1`"), "{text}");
+
+    let lenses = client.request(4, "textDocument/codeLens", app);
+    assert_eq!(
+        lenses["result"][0]["command"]["title"],
+        format!("{coordinate} · v1 · {}", &c2[..7])
+    );
+    assert_eq!(lenses["result"][0]["range"]["start"]["line"], 0);
+    assert_eq!(
+        lenses["result"][0]["command"]["arguments"],
+        json!(["app.txt"])
+    );
+
+    // Files without committed history get no hover and no lenses.
+    let scratch = document(&repo.root().join("scratch.txt"));
+    let hover = client.request(5, "textDocument/hover", scratch.clone());
+    assert_eq!(hover["result"], Value::Null);
+    assert!(hover.get("error").is_none(), "{hover}");
+    let lenses = client.request(6, "textDocument/codeLens", scratch);
+    assert_eq!(lenses["result"], json!([]));
+
+    let unknown = client.request(7, "workspace/symbol", json!({"query": ""}));
+    assert_eq!(unknown["error"]["code"], -32601);
+
+    let shutdown = client.request(8, "shutdown", Value::Null);
+    assert_eq!(shutdown["result"], Value::Null);
+    assert!(shutdown.get("error").is_none(), "{shutdown}");
+    client.notify("exit", Value::Null);
+    assert!(child.wait().expect("wait for tide lsp").success());
+}
+
+#[test]
+fn nested_repository_files_resolve_against_the_innermost_repository() {
+    let repo = RepoFixture::init();
+    repo.write_file_and_commit("app.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    let inner = RepoFixture::init();
+    inner.write_file_and_commit("lib.txt", "l\n", "n1", "2024-03-01T00:00:00+00:00");
+    inner.tag_annotated("v5", "release 5", "2024-03-01T00:00:00+00:00");
+    let n2 = inner.write_file_and_commit("lib.txt", "m\n", "n2", "2024-03-02T00:00:00+00:00");
+    let inner_path = inner.root().to_str().expect("utf-8 path");
+    repo.git(&["clone", "-q", inner_path, "nested"], &[]);
+    let output = inner.run_tide(&["file", "lib.txt", "--local-only"]);
+    assert!(output.status.success());
+    let coordinate = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let (mut child, mut client) = LspClient::start(&repo);
+    client.request(1, "initialize", json!({"capabilities": {}}));
+    client.notify("initialized", json!({}));
+    // The enclosing repository's resolver exists before the nested file is asked for.
+    let outer = client.request(
+        2,
+        "textDocument/hover",
+        document(&repo.root().join("app.txt")),
+    );
+    assert!(outer["result"]["contents"]["value"].is_string(), "{outer}");
+
+    let nested = document(&repo.root().join("nested").join("lib.txt"));
+    let hover = client.request(3, "textDocument/hover", nested.clone());
+    let text = hover["result"]["contents"]["value"]
+        .as_str()
+        .unwrap_or_else(|| panic!("hover markdown: {hover}"));
+    assert!(
+        text.contains(format!("**{coordinate}**").as_str()),
+        "{text}"
+    );
+    assert!(text.contains("`v5`"), "{text}");
+    let lenses = client.request(4, "textDocument/codeLens", nested);
+    assert_eq!(
+        lenses["result"][0]["command"]["title"],
+        format!("{coordinate} · v5 · {}", &n2[..7])
+    );
+
+    client.request(5, "shutdown", Value::Null);
+    client.notify("exit", Value::Null);
+    assert!(child.wait().expect("wait for tide lsp").success());
+}
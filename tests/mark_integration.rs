@@ -5,7 +5,7 @@
 //! Description: Integration tests for mark command coordinate generation and explain output.
 //!
 //! Responsibility:
-//! - Validate baseline coordinate behavior, explain fields, and annotated-tag policy controls.
+//! - Validate baseline coordinate behavior, explain fields, annotated-tag policy controls, and reverse coordinate lookup.
 //!
 //! Architectural Position:
 //! - End-to-end verification of commit mark semantics.
@@ -71,10 +71,7 @@ fn lightweight_tags_rejected_by_default_but_configurable() {
         "stderr={}",
         String::from_utf8_lossy(&default_output.stderr)
     );
-    assert_eq!(
-        String::from_utf8_lossy(&default_output.stdout),
-        "0.0.1\n"
-    );
+    assert_eq!(String::from_utf8_lossy(&default_output.stdout), "0.0.1\n");
 
     repo.write_config("[release]\nrequire_annotated_tags = false\n\n[time]\ntimezone = \"UTC\"\n");
 
@@ -85,3 +82,291 @@ fn lightweight_tags_rejected_by_default_but_configurable() {
         "1.0.1\n"
     );
 }
+
+#[test]
+fn remote_fallback_reason_is_explained_and_can_be_required() {
+    let repo = RepoFixture::init();
+
+    repo.write_file_and_commit("a.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+
+    let fallback = repo.run_tide(&["mark", "--explain"]);
+    assert!(
+        fallback.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&fallback.stderr)
+    );
+    let text = String::from_utf8_lossy(&fallback.stdout);
+    assert!(text.contains("remote_status=fallback-local"));
+    assert!(text.contains("remote_failure=missing-remote"));
+    assert!(text.contains("remote_stderr=fatal:"));
+
+    let required = repo.run_tide(&["mark", "--require-remote"]);
+    assert_eq!(required.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&required.stderr).contains("(missing-remote)"));
+}
+
+#[test]
+fn missing_anchor_policy_is_explicit_and_multi_root_is_deterministic() {
+    let repo = RepoFixture::init();
+
+    repo.write_file_and_commit("main.txt", "m\n", "main root", "2024-01-02T00:00:00+00:00");
+    repo.git(&["checkout", "--orphan", "other"], &[]);
+    repo.git(&["rm", "-rf", "--cached", "."], &[]);
+    repo.write_file_and_commit(
+        "other.txt",
+        "o\n",
+        "other root",
+        "2024-01-01T00:00:00+00:00",
+    );
+    repo.git(&["checkout", "-f", "main"], &[]);
+    let merge_date = "2024-01-03T00:00:00+00:00";
+    repo.git(
+        &[
+            "merge",
+            "--allow-unrelated-histories",
+            "--no-ff",
+            "-m",
+            "merge other",
+            "other",
+        ],
+        &[
+            ("GIT_AUTHOR_DATE", merge_date),
+            ("GIT_COMMITTER_DATE", merge_date),
+        ],
+    );
+    repo.write_config("[time]\ntimezone = \"UTC\"\n");
+
+    let root = repo.run_tide(&["mark", "--local-only", "--explain"]);
+    assert!(
+        root.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&root.stderr)
+    );
+    let text = String::from_utf8_lossy(&root.stdout);
+    assert!(text.contains("version=0.2.1"));
+    assert!(text.contains("anchor_tag=(none)"));
+    assert!(text.contains("anchor_kind=root-fallback"));
+    assert!(text.contains(&format!("anchor_commit={}", repo.rev_parse("other"))));
+
+    repo.write_config(
+        "[release]\non_missing_anchor = \"first-parent-root\"\n\n[time]\ntimezone = \"UTC\"\n",
+    );
+    let first_parent = repo.run_tide(&["mark", "--local-only", "--explain"]);
+    assert!(first_parent.status.success());
+    let text = String::from_utf8_lossy(&first_parent.stdout);
+    assert!(text.contains("version=0.1.1"));
+    assert!(text.contains("anchor_kind=first-parent-root-fallback"));
+    assert!(text.contains(&format!("anchor_commit={}", repo.rev_parse("HEAD^1"))));
+
+    repo.write_config("[release]\non_missing_anchor = \"error\"\n");
+    let rejected = repo.run_tide(&["mark", "--local-only"]);
+    assert_eq!(rejected.status.code(), Some(4));
+}
+
+#[test]
+fn first_parent_history_advances_index_once_per_merge() {
+    let repo = RepoFixture::init();
+
+    repo.write_file_and_commit("a.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    repo.git(&["checkout", "-b", "feature"], &[]);
+    for hour in ["01", "02", "03"] {
+        repo.write_file_and_commit(
+            "feature.txt",
+            hour,
+            &format!("feature {hour}"),
+            &format!("2024-01-02T{hour}:00:00+00:00"),
+        );
+    }
+    repo.git(&["checkout", "main"], &[]);
+    repo.write_file_and_commit("a.txt", "b\n", "m1", "2024-01-02T00:30:00+00:00");
+    let merge_date = "2024-01-02T04:00:00+00:00";
+    repo.git(
+        &["merge", "--no-ff", "-m", "merge feature", "feature"],
+        &[
+            ("GIT_AUTHOR_DATE", merge_date),
+            ("GIT_COMMITTER_DATE", merge_date),
+        ],
+    );
+
+    repo.write_config("[time]\ntimezone = \"UTC\"\n");
+    let ancestry = repo.run_tide(&["mark", "--local-only"]);
+    assert!(
+        ancestry.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&ancestry.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&ancestry.stdout), "1.1.5\n");
+
+    repo.write_config("[time]\ntimezone = \"UTC\"\n\n[coordinate]\nhistory = \"first-parent\"\n");
+    let first_parent = repo.run_tide(&["mark", "--local-only", "--explain"]);
+    assert!(first_parent.status.success());
+    let text = String::from_utf8_lossy(&first_parent.stdout);
+    assert!(text.contains("version=1.1.2"));
+    assert!(text.contains("history=first-parent"));
+}
+
+#[test]
+fn unprotected_branches_get_channel_or_are_refused() {
+    let repo = RepoFixture::init();
+
+    repo.write_file_and_commit("a.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    repo.write_file_and_commit("a.txt", "b\n", "c2", "2024-01-01T01:00:00+00:00");
+    repo.write_config("[branch]\nunprotected = \"channel\"\n");
+
+    let protected = repo.run_tide(&["mark", "--local-only"]);
+    assert!(
+        protected.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&protected.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&protected.stdout), "1.0.1\n");
+
+    repo.git(&["checkout", "-b", "feature/Login"], &[]);
+    let feature = repo.run_tide(&["mark", "--local-only", "--tag", "dev"]);
+    assert!(feature.status.success());
+    let text = String::from_utf8_lossy(&feature.stdout).to_string();
+    assert!(text.starts_with("1.0.1-feature-login-"), "{text}");
+    assert!(text.ends_with(".dev\n"), "{text}");
+
+    let head = repo.rev_parse("HEAD");
+    repo.git(&["checkout", "--detach", &head], &[]);
+    let detached = repo.run_tide_with_env(
+        &["mark", "--local-only", "--explain"],
+        &[
+            ("GITHUB_HEAD_REF", ""),
+            ("GITHUB_REF_NAME", ""),
+            ("CI_COMMIT_BRANCH", ""),
+            ("BUILDKITE_BRANCH", ""),
+            ("BRANCH_NAME", "feature/Login"),
+        ],
+    );
+    assert!(detached.status.success());
+    let detached_text = String::from_utf8_lossy(&detached.stdout);
+    assert!(detached_text.contains("branch=feature/Login"));
+    assert!(detached_text.contains(&format!("version={}", text.trim_end_matches(".dev\n"))));
+
+    repo.git(&["checkout", "feature/Login"], &[]);
+    repo.write_config("[branch]\nunprotected = \"refuse\"\n");
+    let refused = repo.run_tide(&["mark", "--local-only"]);
+    assert_eq!(refused.status.code(), Some(4));
+}
+
+#[test]
+fn metadata_suffix_templates_render_and_validate() {
+    let repo = RepoFixture::init();
+
+    repo.write_file_and_commit("a.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    repo.write_file_and_commit("a.txt", "b\n", "c2", "2024-01-02T01:00:00+00:00");
+    repo.write_config(
+        "[time]\ntimezone = \"UTC\"\n\n[output]\nmetadata_suffix = \"{anchor_tag}.{date}.{short_sha}.{dirty}\"\n",
+    );
+    let short_sha = repo.rev_parse("HEAD")[..7].to_string();
+
+    let clean = repo.run_tide(&["mark", "--local-only"]);
+    assert!(
+        clean.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&clean.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&clean.stdout),
+        format!("1.1.1.v1.20240102.{short_sha}\n")
+    );
+
+    std::fs::write(repo.root().join("a.txt"), "dirty\n").unwrap();
+    let dirty = repo.run_tide(&["mark", "--local-only"]);
+    assert_eq!(
+        String::from_utf8_lossy(&dirty.stdout),
+        format!("1.1.1.v1.20240102.{short_sha}.dirty\n")
+    );
+
+    let env = repo.run_tide_with_env(
+        &["mark", "--local-only", "--tag", "ci_{env:BUILD_ID}"],
+        &[("BUILD_ID", "42/a b")],
+    );
+    assert_eq!(String::from_utf8_lossy(&env.stdout), "1.1.1.ci_42-a-b\n");
+
+    let bad_arg = repo.run_tide(&["mark", "--local-only", "--tag", "{sha}"]);
+    assert_eq!(bad_arg.status.code(), Some(2));
+
+    repo.write_config("[output]\nmetadata_suffix = \"{branch\"\n");
+    let bad_config = repo.run_tide(&["mark", "--local-only"]);
+    assert_eq!(bad_config.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&bad_config.stderr).contains("invalid metadata suffix template")
+    );
+}
+
+#[test]
+fn locate_lists_commits_that_resolve_to_a_coordinate() {
+    let repo = RepoFixture::init();
+
+    let anchor = repo.write_file_and_commit("app.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    let c2 = repo.write_file_and_commit("app.txt", "b\n", "c2", "2024-01-02T01:00:00+00:00");
+    let c3 = repo.write_file_and_commit("app.txt", "c\n", "c3", "2024-01-02T02:00:00+00:00");
+    repo.write_file_and_commit("app.txt", "d\n", "c4", "2024-01-03T00:00:00+00:00");
+
+    for (coordinate, expected) in [
+        ("1.0.0", &anchor),
+        ("1.1.1", &c2),
+        ("1.1.2-feature.b7", &c3),
+    ] {
+        let output = repo.run_tide(&["locate", coordinate, "--local-only"]);
+        assert!(
+            output.status.success(),
+            "{coordinate}: stderr={}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            format!("{expected}\n")
+        );
+    }
+
+    // Reachability is bounded by --from.
+    let output = repo.run_tide(&["locate", "1.1.2", "--from", c2.as_str(), "--local-only"]);
+    assert_eq!(output.status.code(), Some(4));
+    assert!(String::from_utf8_lossy(&output.stderr).contains(&format!("from {c2} resolves")));
+
+    let output = repo.run_tide(&["locate", "1.x.2"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn locate_lists_remote_tags_once_for_all_candidates() {
+    let repo = RepoFixture::init();
+
+    repo.write_file_and_commit("app.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    for (content, iso_ts) in [
+        ("b\n", "2024-01-02T01:00:00+00:00"),
+        ("c\n", "2024-01-02T02:00:00+00:00"),
+        ("d\n", "2024-01-02T03:00:00+00:00"),
+    ] {
+        repo.write_file_and_commit("app.txt", content, "day one", iso_ts);
+    }
+    let origin = repo.root().display().to_string();
+    repo.git(&["remote", "add", "origin", origin.as_str()], &[]);
+
+    let trace = repo.root().join(".git").join("trace.log");
+    let output = repo.run_tide_with_env(
+        &["locate", "1.1.2"],
+        &[("GIT_TRACE", trace.to_str().expect("utf-8 path"))],
+    );
+    assert!(
+        output.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let listings = std::fs::read_to_string(&trace)
+        .expect("git trace")
+        .lines()
+        .filter(|line| line.contains("git ls-remote"))
+        .count();
+    assert_eq!(listings, 1);
+}
@@ -5,7 +5,7 @@
 //! Description: Integration tests for release listing and config initialization workflows.
 //!
 //! Responsibility:
-//! - Verify script-safe release rows and idempotent config init behavior.
+//! - Verify script-safe release rows, config init/validate/migrate, and layered config precedence.
 //!
 //! Architectural Position:
 //! - End-to-end coverage for release query and config command boundaries.
@@ -60,3 +60,201 @@ fn config_init_creates_file_once() {
     let second = repo.run_tide(&["config", "init"]);
     assert_eq!(second.status.code(), Some(2));
 }
+
+#[test]
+fn config_layers_apply_in_precedence_order() {
+    let repo = RepoFixture::init();
+
+    repo.write_file_and_commit("seed.txt", "x\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    repo.write_global_config("[release]\ntag_prefix = \"rel-\"\n\n[remote]\nname = \"upstream\"\n");
+    repo.write_config("[release]\ntag_prefix = \"v\"\n");
+
+    let output = repo.run_tide_with_env(
+        &["-c", "remote.deepen_step=7", "config", "show", "--origin"],
+        &[("TIDEMARK_REMOTE_STRATEGY", "local-only")],
+    );
+    assert!(
+        output.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let text = String::from_utf8_lossy(&output.stdout);
+    let repo_config = repo.root().join(".tidemark.toml");
+    let global_config = repo.xdg_config_home().join("tidemark").join("config.toml");
+    assert!(text.contains(&format!(
+        "release.tag_prefix\t\"v\"\trepo:{}\n",
+        repo_config.display()
+    )));
+    assert!(text.contains(&format!(
+        "remote.name\t\"upstream\"\tglobal:{}\n",
+        global_config.display()
+    )));
+    assert!(text.contains("remote.strategy\t\"local-only\"\tenv:TIDEMARK_REMOTE_STRATEGY\n"));
+    assert!(text.contains("remote.deepen_step\t7\tcli\n"));
+    assert!(text.contains("time.timezone\t\"local\"\tdefault\n"));
+
+    let bad = repo.run_tide(&["-c", "remote.strategy=sometimes", "mark"]);
+    assert_eq!(bad.status.code(), Some(2));
+}
+
+#[test]
+fn wrapper_cache_dir_env_does_not_break_resolution() {
+    let repo = RepoFixture::init();
+
+    repo.write_file_and_commit("seed.txt", "x\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    let cache_dir = repo.root().join("wrapper-cache");
+
+    let output = repo.run_tide_with_env(
+        &["mark", "--local-only"],
+        &[(
+            "TIDEMARK_CACHE_DIR",
+            cache_dir.to_str().expect("utf-8 path"),
+        )],
+    );
+    assert!(
+        output.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "1.0.0");
+}
+
+#[test]
+fn config_validate_reports_all_problems_as_json() {
+    let repo = RepoFixture::init();
+
+    repo.write_file_and_commit("seed.txt", "x\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+
+    let ok = repo.run_tide(&["config", "validate", "--local-only"]);
+    assert!(
+        ok.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&ok.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&ok.stdout), "ok\n");
+
+    repo.write_config(
+        "schema = 1\n\n[release]\ntag_prefx = \"v\"\n\n[time]\ntimezone = \"Mars\"\n",
+    );
+    let output = repo.run_tide(&["config", "validate", "--json", "--local-only"]);
+    assert_eq!(output.status.code(), Some(2));
+    let report: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("json validation report");
+    assert_eq!(report["valid"], false);
+    assert_eq!(report["errors"], 2);
+    assert_eq!(report["issues"][0]["kind"], "unknown-key");
+    assert_eq!(report["issues"][0]["key"], "release.tag_prefx");
+    assert_eq!(report["issues"][0]["line"], 4);
+    assert_eq!(report["issues"][1]["kind"], "invalid-value");
+    assert_eq!(report["issues"][1]["line"], 7);
+
+    let mark = repo.run_tide(&["mark", "--local-only"]);
+    assert_eq!(mark.status.code(), Some(2));
+}
+
+#[test]
+fn config_validate_reports_every_broken_layer_and_checks_the_repository() {
+    let repo = RepoFixture::init();
+
+    repo.write_file_and_commit("seed.txt", "x\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    repo.write_global_config("[remote\n");
+    repo.write_config("schema = 1\n\n[release]\ntag_prefx = \"v\"\n");
+
+    let output = repo.run_tide_with_env(
+        &[
+            "-c",
+            "remote.strategy=nowhere",
+            "-c",
+            "release.tag_prefix=z",
+            "-c",
+            "coordinate.history=sideways",
+            "config",
+            "validate",
+            "--json",
+            "--local-only",
+        ],
+        &[("TIDEMARK_TIME_TIMEZONE", "Mars")],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    let report: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("json validation report");
+    let issues: Vec<String> = report["issues"]
+        .as_array()
+        .expect("issue list")
+        .iter()
+        .map(|issue| {
+            format!(
+                "{} {}",
+                issue["kind"].as_str().unwrap_or("-"),
+                issue["source"]
+            )
+        })
+        .collect();
+    let global = repo.xdg_config_home().join("tidemark").join("config.toml");
+    let repo_file = repo.root().join(".tidemark.toml");
+    assert_eq!(
+        issues,
+        [
+            format!("syntax \"{}\"", global.display()),
+            format!("unknown-key \"{}\"", repo_file.display()),
+            "invalid-value \"TIDEMARK_TIME_TIMEZONE\"".to_string(),
+            "invalid-value \"-c remote.strategy=nowhere\"".to_string(),
+            "invalid-value \"-c coordinate.history=sideways\"".to_string(),
+            // The valid override still reaches the repository checks.
+            "unmatched-prefix \"cli\"".to_string(),
+        ]
+    );
+    assert_eq!(report["errors"], 5);
+}
+
+#[test]
+fn config_migrate_upgrades_unversioned_file_once() {
+    let repo = RepoFixture::init();
+
+    repo.write_file_and_commit("seed.txt", "x\n", "c1", "2024-01-01T00:00:00+00:00");
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    repo.write_config("# keep me\n[time]\ntimezone = \"UTC\" # pinned\n");
+
+    let first = repo.run_tide(&["config", "migrate"]);
+    assert!(
+        first.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&first.stderr)
+    );
+    let config_path = repo.root().join(".tidemark.toml");
+    assert!(String::from_utf8_lossy(&first.stdout).ends_with("\t0\t1\tupdated\n"));
+    let migrated = std::fs::read_to_string(&config_path).expect("read config");
+    assert_eq!(
+        migrated,
+        "schema = 1\n# keep me\n[time]\ntimezone = \"UTC\" # pinned\n"
+    );
+
+    let second = repo.run_tide(&["config", "migrate"]);
+    assert!(String::from_utf8_lossy(&second.stdout).ends_with("\t1\t1\tunchanged\n"));
+
+    // The global layer is migrated alongside the repository file.
+    repo.write_global_config("[release]\ntag_prefix = \"v\"\n");
+    let global = repo.run_tide(&["config", "migrate"]);
+    let global_path = repo.xdg_config_home().join("tidemark").join("config.toml");
+    assert_eq!(
+        String::from_utf8_lossy(&global.stdout),
+        format!(
+            "{}\t0\t1\tupdated\n{}\t1\t1\tunchanged\n",
+            global_path.display(),
+            config_path.display()
+        )
+    );
+    assert_eq!(
+        std::fs::read_to_string(&global_path).expect("read global config"),
+        "schema = 1\n[release]\ntag_prefix = \"v\"\n"
+    );
+
+    repo.write_config("schema = 99\n");
+    let newer = repo.run_tide(&["mark", "--local-only"]);
+    assert_eq!(newer.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&newer.stderr).contains("declares schema 99"));
+}
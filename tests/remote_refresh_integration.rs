@@ -16,7 +16,7 @@
 
 use std::{
     fs,
-    path::Path,
+    path::{Path, PathBuf},
     process::{Command, Output},
 };
 
@@ -142,6 +142,365 @@ fn remote_same_name_tag_overrides_local_definition() {
     assert_eq!(stdout_text(&local_only_after), "1.2.1\n");
 }
 
+#[test]
+fn tag_conflict_policy_is_configurable_and_recorded() {
+    let sandbox = tempfile::tempdir().expect("create sandbox");
+    let local = moved_tag_fixture(sandbox.path());
+
+    let remote_wins = run_tide(&local, &["mark", "--explain"]);
+    assert_success(&remote_wins);
+    let text = stdout_text(&remote_wins);
+    assert!(text.contains("version=1.1.1"));
+    assert!(text.contains("tag_conflict=v1 local="));
+    assert!(text.contains("kept=remote"));
+
+    fs::write(
+        local.join(".tidemark.toml"),
+        "[remote]\non_conflict = \"local-wins\"\n",
+    )
+    .expect("write config");
+    let local_wins = run_tide(&local, &["mark"]);
+    assert_success(&local_wins);
+    assert_eq!(stdout_text(&local_wins), "1.2.1\n");
+
+    let listed = run_tide(&local, &["release", "list", "--json"]);
+    assert_success(&listed);
+    let listed: serde_json::Value =
+        serde_json::from_str(stdout_text(&listed).as_str()).expect("json release list");
+    let v1 = listed
+        .as_array()
+        .expect("release array")
+        .iter()
+        .find(|release| release["tag"] == "v1")
+        .expect("v1 listed");
+    assert_eq!(v1["conflict"]["kept"], "local");
+    assert_ne!(
+        v1["conflict"]["local_commit"],
+        v1["conflict"]["remote_commit"]
+    );
+    // The tab-separated rows keep their five columns.
+    let rows = run_tide(&local, &["release", "list"]);
+    assert_success(&rows);
+    assert!(
+        stdout_text(&rows)
+            .lines()
+            .all(|row| row.split('\t').count() == 5)
+    );
+
+    fs::write(
+        local.join(".tidemark.toml"),
+        "[remote]\non_conflict = \"warn\"\n",
+    )
+    .expect("write config");
+    let warned = run_tide(&local, &["mark"]);
+    assert_success(&warned);
+    assert_eq!(stdout_text(&warned), "1.1.1\n");
+    assert!(String::from_utf8_lossy(&warned.stderr).contains("warning: release tag conflict v1"));
+    // A batch reports each conflict once, gathered across every file.
+    let batch = run_tide(&local, &["file", "."]);
+    assert_success(&batch);
+    assert_eq!(
+        String::from_utf8_lossy(&batch.stderr)
+            .matches("warning: release tag conflict v1")
+            .count(),
+        1
+    );
+
+    fs::write(
+        local.join(".tidemark.toml"),
+        "[remote]\non_conflict = \"error\"\n",
+    )
+    .expect("write config");
+    let rejected = run_tide(&local, &["mark"]);
+    assert_eq!(rejected.status.code(), Some(4));
+    assert!(String::from_utf8_lossy(&rejected.stderr).contains("release tag `v1` conflicts"));
+}
+
+#[test]
+fn ls_remote_strategy_is_read_only_and_fetch_strategy_mirrors_refs() {
+    let sandbox = tempfile::tempdir().expect("create sandbox");
+    let remote = sandbox.path().join("remote.git");
+    let upstream = sandbox.path().join("upstream");
+    let local = sandbox.path().join("local");
+
+    run_git(sandbox.path(), &["init", "--bare", "remote.git"], &[]);
+    init_repo(&upstream);
+    write_and_commit(
+        &upstream,
+        "app.txt",
+        "a\n",
+        "c1",
+        "2024-01-01T00:00:00+00:00",
+    );
+    run_git(
+        &upstream,
+        &["tag", "-a", "v1", "-m", "release 1"],
+        &[("GIT_COMMITTER_DATE", "2024-01-01T00:10:00+00:00")],
+    );
+    run_git(
+        &upstream,
+        &["remote", "add", "origin", path_text(&remote)],
+        &[],
+    );
+    run_git(&upstream, &["push", "origin", "main", "--tags"], &[]);
+    init_local_checkout_from_remote(&remote, &local);
+
+    // v2 lands on a commit the local checkout has never seen.
+    write_and_commit(
+        &upstream,
+        "app.txt",
+        "b\n",
+        "c2",
+        "2024-01-02T00:00:00+00:00",
+    );
+    run_git(
+        &upstream,
+        &["tag", "-a", "v2", "-m", "release 2"],
+        &[("GIT_COMMITTER_DATE", "2024-01-02T00:10:00+00:00")],
+    );
+    run_git(&upstream, &["push", "origin", "v2"], &[]);
+    let c2 = git_output(&upstream, &["rev-parse", "HEAD"]);
+
+    let refs_before = git_output(&local, &["for-each-ref"]);
+    let listed = run_tide(&local, &["release", "list", "--require-remote"]);
+    assert_success(&listed);
+    let text = stdout_text(&listed);
+    assert!(text.contains("v1\t1\t"));
+    assert!(text.contains(&format!("v2\t2\t{}\tannotated\tremote", c2.trim())));
+    assert_eq!(git_output(&local, &["for-each-ref"]), refs_before);
+    // `v2` is not an ancestor of the complete local history, so its objects are never fetched.
+    let marked = run_tide(&local, &["mark", "--require-remote"]);
+    assert_success(&marked);
+    assert_eq!(stdout_text(&marked), "1.0.0\n");
+    let located = run_tide(&local, &["locate", "2.0.0", "--require-remote"]);
+    assert_eq!(located.status.code(), Some(4));
+    assert!(!has_object(&local, &format!("{}^{{commit}}", c2.trim())));
+    assert_eq!(git_output(&local, &["for-each-ref"]), refs_before);
+
+    fs::write(
+        local.join(".tidemark.toml"),
+        "[remote]\nstrategy = \"fetch\"\n",
+    )
+    .expect("write config");
+    let fetched = run_tide(&local, &["release", "list", "--require-remote"]);
+    assert_success(&fetched);
+    assert_eq!(stdout_text(&fetched), text);
+    let mirrored = git_output(&local, &["for-each-ref", "refs/tidemark/remote-tags"]);
+    assert!(mirrored.contains("refs/tidemark/remote-tags/v2"));
+}
+
+#[test]
+fn multiple_remotes_merge_in_priority_order_with_per_remote_fallback() {
+    let sandbox = tempfile::tempdir().expect("create sandbox");
+    let upstream_remote = sandbox.path().join("upstream.git");
+    let fork_remote = sandbox.path().join("fork.git");
+    let work = sandbox.path().join("work");
+    let local = sandbox.path().join("local");
+
+    run_git(sandbox.path(), &["init", "--bare", "upstream.git"], &[]);
+    run_git(sandbox.path(), &["init", "--bare", "fork.git"], &[]);
+    init_repo(&work);
+    write_and_commit(&work, "app.txt", "a\n", "c1", "2024-01-01T00:00:00+00:00");
+    let c1 = git_output(&work, &["rev-parse", "HEAD"]);
+    write_and_commit(&work, "app.txt", "b\n", "c2", "2024-01-02T00:00:00+00:00");
+    let c2 = git_output(&work, &["rev-parse", "HEAD"]);
+    run_git(
+        &work,
+        &["remote", "add", "upstream", path_text(&upstream_remote)],
+        &[],
+    );
+    run_git(
+        &work,
+        &["remote", "add", "origin", path_text(&fork_remote)],
+        &[],
+    );
+    run_git(&work, &["push", "upstream", "main"], &[]);
+    run_git(&work, &["push", "origin", "main"], &[]);
+
+    // Upstream and the fork disagree on v3; only the fork has v2.
+    let tag_env = [("GIT_COMMITTER_DATE", "2024-01-03T00:00:00+00:00")];
+    run_git(
+        &work,
+        &["tag", "-a", "v3", "-m", "upstream v3", c2.trim()],
+        &tag_env,
+    );
+    run_git(&work, &["push", "upstream", "v3"], &[]);
+    run_git(
+        &work,
+        &["tag", "-fa", "v3", "-m", "fork v3", c1.trim()],
+        &tag_env,
+    );
+    run_git(
+        &work,
+        &["tag", "-a", "v2", "-m", "fork v2", c1.trim()],
+        &tag_env,
+    );
+    run_git(&work, &["push", "origin", "v2", "v3"], &[]);
+
+    init_local_checkout_from_remote(&fork_remote, &local);
+    run_git(&local, &["tag", "-d", "v2", "v3"], &[]);
+    run_git(
+        &local,
+        &["remote", "add", "upstream", path_text(&upstream_remote)],
+        &[],
+    );
+
+    fs::write(
+        local.join(".tidemark.toml"),
+        "[remote]\nnames = [\"upstream\", \"origin\"]\n",
+    )
+    .expect("write config");
+    let listed = run_tide(&local, &["release", "list"]);
+    assert_success(&listed);
+    let text = stdout_text(&listed);
+    assert!(text.contains(&format!("v2\t1\t{}\tannotated\tremote:origin", c1.trim())));
+    assert!(text.contains(&format!("v3\t2\t{}\tannotated\tremote:upstream", c2.trim())));
+
+    fs::write(
+        local.join(".tidemark.toml"),
+        "[remote]\nnames = [\"gone\", \"origin\"]\nfallback_to_local = false\n\n[remote.overrides.gone]\nfallback_to_local = true\n",
+    )
+    .expect("write config");
+    let degraded = run_tide(&local, &["mark", "--explain"]);
+    assert_success(&degraded);
+    let text = stdout_text(&degraded);
+    assert!(text.contains("remote_status=fallback-local"));
+    assert!(text.contains("remote.gone.status=fallback-local"));
+    assert!(text.contains("remote.gone.failure=missing-remote"));
+    assert!(text.contains("remote.origin.status=used-remote"));
+
+    fs::write(
+        local.join(".tidemark.toml"),
+        "[remote]\nnames = [\"gone\", \"origin\"]\nfallback_to_local = false\n",
+    )
+    .expect("write config");
+    let strict = run_tide(&local, &["mark"]);
+    assert_eq!(strict.status.code(), Some(3));
+}
+
+#[test]
+fn shallow_clone_is_refused_unless_deepening_is_enabled() {
+    let sandbox = tempfile::tempdir().expect("create sandbox");
+    let upstream = sandbox.path().join("upstream");
+    let local = sandbox.path().join("local");
+
+    init_repo(&upstream);
+    write_and_commit(
+        &upstream,
+        "app.txt",
+        "0\n",
+        "c0",
+        "2023-12-01T00:00:00+00:00",
+    );
+    write_and_commit(
+        &upstream,
+        "app.txt",
+        "a\n",
+        "c1",
+        "2024-01-01T00:00:00+00:00",
+    );
+    run_git(
+        &upstream,
+        &["tag", "-a", "v1", "-m", "release 1"],
+        &[("GIT_COMMITTER_DATE", "2024-01-01T00:10:00+00:00")],
+    );
+    for (idx, day) in ["02", "03", "03", "04"].iter().enumerate() {
+        write_and_commit(
+            &upstream,
+            "app.txt",
+            &format!("{idx}\n"),
+            &format!("c{}", idx + 2),
+            &format!("2024-01-{day}T0{idx}:00:00+00:00"),
+        );
+    }
+    let expected = run_tide(&upstream, &["mark", "--local-only"]);
+    assert_success(&expected);
+
+    let url = format!("file://{}", path_text(&upstream));
+    run_git(
+        sandbox.path(),
+        &[
+            "clone",
+            "--quiet",
+            "--depth",
+            "1",
+            "--no-tags",
+            &url,
+            "local",
+        ],
+        &[],
+    );
+
+    let refused = run_tide(&local, &["mark"]);
+    assert_eq!(refused.status.code(), Some(4));
+    assert!(String::from_utf8_lossy(&refused.stderr).contains("shallow clone"));
+
+    fs::write(local.join(".tidemark.toml"), "[remote]\ndeepen = true\n").expect("write config");
+    let deepened = run_tide(&local, &["mark", "--explain"]);
+    assert_success(&deepened);
+    let text = stdout_text(&deepened);
+    assert!(text.contains(&format!("version={}", stdout_text(&expected))));
+    assert!(text.contains("anchor_tag=v1"));
+    assert!(text.contains("shallow=true"));
+    assert!(!text.contains("deepen_rounds=0"));
+}
+
+/// Builds a local clone whose `v1` tag disagrees with the remote after an upstream force-push.
+fn moved_tag_fixture(sandbox: &Path) -> PathBuf {
+    let remote = sandbox.join("remote.git");
+    let upstream = sandbox.join("upstream");
+    let local = sandbox.join("local");
+
+    run_git(sandbox, &["init", "--bare", "remote.git"], &[]);
+    init_repo(&upstream);
+    write_and_commit(
+        &upstream,
+        "app.txt",
+        "a\n",
+        "c1",
+        "2024-01-01T00:00:00+00:00",
+    );
+    run_git(
+        &upstream,
+        &["tag", "-a", "v1", "-m", "release 1"],
+        &[("GIT_COMMITTER_DATE", "2024-01-01T00:10:00+00:00")],
+    );
+    write_and_commit(
+        &upstream,
+        "app.txt",
+        "b\n",
+        "c2",
+        "2024-01-02T00:00:00+00:00",
+    );
+    write_and_commit(
+        &upstream,
+        "app.txt",
+        "c\n",
+        "c3",
+        "2024-01-03T00:00:00+00:00",
+    );
+    run_git(
+        &upstream,
+        &["remote", "add", "origin", path_text(&remote)],
+        &[],
+    );
+    run_git(&upstream, &["push", "origin", "main", "--tags"], &[]);
+    init_local_checkout_from_remote(&remote, &local);
+
+    let c2 = git_output(&upstream, &["rev-parse", "HEAD~1"]);
+    run_git(
+        &upstream,
+        &["tag", "-fa", "v1", "-m", "release 1 moved", c2.trim()],
+        &[("GIT_COMMITTER_DATE", "2024-01-04T00:00:00+00:00")],
+    );
+    run_git(
+        &upstream,
+        &["push", "origin", "+refs/tags/v1:refs/tags/v1"],
+        &[],
+    );
+    local
+}
+
 fn init_repo(path: &Path) {
     fs::create_dir_all(path).expect("create repo dir");
     run_git(path, &["init", "-b", "main"], &[]);
@@ -217,6 +576,16 @@ fn git_output(dir: &Path, args: &[&str]) -> String {
     String::from_utf8(output.stdout).expect("utf8")
 }
 
+fn has_object(dir: &Path, rev: &str) -> bool {
+    Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["cat-file", "-e", rev])
+        .status()
+        .expect("run git cat-file")
+        .success()
+}
+
 fn assert_success(output: &Output) {
     assert!(
         output.status.success(),
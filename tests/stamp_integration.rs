@@ -0,0 +1,76 @@
+//! TideMark
+//! ========
+//!
+//! File: tests/stamp_integration.rs
+//! Description: Integration tests for writing coordinates into project manifests.
+//!
+//! Responsibility:
+//! - Validate per-ecosystem version schemes, formatting preservation, and `--check` gating.
+//!
+//! Architectural Position:
+//! - End-to-end verification of the `tide stamp` operations adapter.
+//!
+//! Author: Silan.Hu
+//! Email: silan.hu@u.nus.edu
+//! Copyright (c) 2026-2027 easynet. All rights reserved.
+
+mod common;
+
+use std::fs;
+
+use common::RepoFixture;
+
+#[test]
+fn stamp_writes_manifests_and_check_gates_drift() {
+    let repo = RepoFixture::init();
+
+    repo.write_file_and_commit(
+        "Cargo.toml",
+        "[package]\nname = \"demo\"\nversion = \"0.0.0\"  # stamped\n\n[dependencies]\nserde = { version = \"1\" }\n",
+        "c1",
+        "2024-01-01T00:00:00+00:00",
+    );
+    repo.write_file_and_commit(
+        "package.json",
+        "{\n  \"name\": \"demo\",\n  \"version\": \"0.0.0\",\n  \"dependencies\": { \"x\": \"1\" }\n}\n",
+        "c2",
+        "2024-01-01T00:00:00+00:00",
+    );
+    repo.write_file_and_commit(
+        "debian/changelog",
+        "demo (0.0.0-1) unstable; urgency=medium\n\n  * Initial.\n",
+        "c3",
+        "2024-01-01T00:00:00+00:00",
+    );
+    repo.tag_annotated("v1", "release 1", "2024-01-01T00:00:00+00:00");
+    repo.write_file_and_commit("a.txt", "a\n", "c4", "2024-01-01T01:00:00+00:00");
+
+    let targets = "Cargo.toml,package.json,debian/changelog";
+    let stale = repo.run_tide(&["stamp", "--into", targets, "--check", "--local-only"]);
+    assert_eq!(stale.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&stale.stdout).contains("Cargo.toml\tcargo\t1.0.1\tstale"));
+
+    let stamped = repo.run_tide(&["stamp", "--into", targets, "--local-only"]);
+    assert!(
+        stamped.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&stamped.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&stamped.stdout),
+        "Cargo.toml\tcargo\t1.0.1\tupdated\npackage.json\tnpm\t1.0.1\tupdated\ndebian/changelog\tdebian\t1.0.1\tupdated\n"
+    );
+    let read = |path: &str| fs::read_to_string(repo.root().join(path)).unwrap();
+    assert_eq!(
+        read("Cargo.toml"),
+        "[package]\nname = \"demo\"\nversion = \"1.0.1\"  # stamped\n\n[dependencies]\nserde = { version = \"1\" }\n"
+    );
+    assert!(read("package.json").contains("\"version\": \"1.0.1\",\n"));
+    assert!(read("debian/changelog").starts_with("demo (1.0.1-1) unstable;"));
+
+    let fresh = repo.run_tide(&["stamp", "--into", targets, "--check", "--local-only"]);
+    assert!(fresh.status.success());
+
+    let unsupported = repo.run_tide(&["stamp", "--into", "a.txt", "--local-only"]);
+    assert_eq!(unsupported.status.code(), Some(2));
+}